@@ -1,67 +1,77 @@
-use crate::{
-    AppContext, Command, CreateDIDCommand, CreateVCMenu, CreateVCNormalCommand, CreateVCSDCommand,
-    CreateVPCommand, ExitAppCommand, ListCreatedItems, ListDIDsCommand, ListVCsCommand,
-    MainMenuCommand, ScreenEvent, ScreenFSM, ScreenState, VerifyVCCommand,
-};
-use rust_fsm::StateMachine;
+use crate::{AppContext, CommandRegistry, Output, Progress, ScreenEvent, ScreenFSM, ScreenState};
 
 pub struct App {
-    fsm: StateMachine<ScreenFSM>,
+    fsm: ScreenFSM,
     context: AppContext,
 }
 
 impl App {
     pub fn new(context: AppContext) -> Self {
+        App::with_registry(context, CommandRegistry::with_default_commands())
+    }
+
+    /// App showing the screens of `registry`, e.g. the default ones plus custom workflows.
+    pub fn with_registry(context: AppContext, registry: CommandRegistry) -> Self {
         App {
-            fsm: StateMachine::new(),
+            fsm: ScreenFSM::new(registry),
             context,
         }
     }
 
-    pub fn run(&mut self) {
+    pub async fn run(&mut self) {
         loop {
-            // Match the current state to choose the appropriate command
-            let mut command: Box<dyn Command> = match self.fsm.state() {
-                // Main Menu
-                ScreenState::MainMenu => Box::new(MainMenuCommand::new()),
-
-                // List Created Items
-                ScreenState::ListItemsMenu => Box::new(ListCreatedItems::new()),
-                ScreenState::ListDIDsWorkflow => Box::new(ListDIDsCommand::new(&self.context)),
-                ScreenState::ListVCsWorkflow => Box::new(ListVCsCommand::new(&self.context)),
-
-                // Create DID
-                ScreenState::CreateDIDWorkflow => Box::new(CreateDIDCommand::new(&self.context)),
-
-                // Create VC
-                ScreenState::CreateVCMenu => Box::new(CreateVCMenu::new()),
-                ScreenState::CreateSDVCWorkflow => Box::new(CreateVCSDCommand::new(&self.context)),
-                ScreenState::CreateNormalVCWorkflow => {
-                    Box::new(CreateVCNormalCommand::new(&self.context))
+            // Build the command registered for the current state
+            let mut command = match self.fsm.build_command(&self.context) {
+                Some(command) => command,
+                None if *self.fsm.state() == ScreenState::Error => {
+                    panic!("No command registered for the error screen")
+                }
+                None => {
+                    let message = format!("No command registered for {:?}", self.fsm.state());
+                    self.fsm.fail(message);
+                    continue;
                 }
-
-                // Verify VC
-                ScreenState::VerifyVCWorkflow => Box::new(VerifyVCCommand::new(&self.context)),
-
-                // Create VP
-                ScreenState::CreateVPWorkflow => Box::new(CreateVPCommand::new(&self.context)),
-
-                // Exit App
-                ScreenState::ExitAppWorkflow => Box::new(ExitAppCommand),
             };
+            Output::set_breadcrumbs(self.fsm.breadcrumbs());
 
-            // Execute the command and get the resulting event
-            let event = command.execute();
+            // Execute the command and get the resulting event.
+            // Ctrl+C cancels the running command and returns to the previous menu, or leaves the
+            // app from the main menu
+            let event = tokio::select! {
+                event = command.execute() => event,
+                _ = tokio::signal::ctrl_c() => {
+                    if *self.fsm.state() == ScreenState::MainMenu {
+                        break;
+                    }
+                    Output::print_during_loading("Operation cancelled");
+                    ScreenEvent::Cancel
+                }
+            };
 
             // Check if the event is Exit and the State is ExitAppWorkflow, if so, break the loop
-            if event == ScreenEvent::Exit   && *self.fsm.state() == ScreenState::ExitAppWorkflow {
+            if event == ScreenEvent::Exit && *self.fsm.state() == ScreenState::ExitAppWorkflow {
                 break;
             }
 
             // Update state based on event
-            let _output = self.fsm.consume(&event).unwrap();
-
             drop(command);
+            let event = match event {
+                ScreenEvent::NetworkSelected(name) => self.switch_network(&name).await,
+                event => event,
+            };
+            self.fsm.consume(&event);
+        }
+    }
+
+    async fn switch_network(&mut self, name: &str) -> ScreenEvent {
+        let result = Progress::run(
+            "Connecting to the network",
+            self.context.switch_network(name),
+        )
+        .await;
+        match result {
+            Ok(()) => ScreenEvent::Success,
+            Err(e) => ScreenEvent::Error(e.to_string()),
         }
     }
 }
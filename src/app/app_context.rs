@@ -1,33 +1,50 @@
-use identity_iota::iota::IotaDocument;
+use identity_iota::core::Url;
+use identity_iota::document::CoreDocument;
+use identity_iota::iota::{IotaDID, IotaDocument, IotaIdentityClientExt};
 use identity_iota::resolver::Resolver;
 use crate::{
-    create_database_tables, utils, Config, DBConnector, Output, SQLiteConnector, VariablesConfig,
+    create_database_tables, resolve_offline_did, utils, Config, DBConnector, Did, DidWebResolver,
+    InputSource, NetworkProfile, Output, SQLiteConnector, StrongholdKeyStorage, VariablesConfig,
 };
 use identity_iota::storage::Storage;
 use identity_stronghold::StrongholdStorage;
 use iota_sdk::client::secret::stronghold::StrongholdSecretManager;
 use iota_sdk::client::{Client, Password};
 use iota_sdk::types::block::address::{Address, Ed25519Address, Hrp, ToBech32Ext};
-use tokio::sync::watch;
+use rand::distributions::DistString;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{watch, OnceCell};
 
 
 pub struct AppContext {
-    pub client: Client,
+    /// Connected to the nodes of `network` on the first ledger operation, see
+    /// [`AppContext::client`].
+    client: Arc<OnceCell<Client>>,
     pub db: Box<dyn DBConnector>,
     pub stronghold_storage: StrongholdStorage,
-    pub storage: Storage<StrongholdStorage, StrongholdStorage>,
-    pub address: Address,
-    pub resolver: Resolver<IotaDocument>,
+    pub storage: Storage<StrongholdKeyStorage, StrongholdStorage>,
+    /// Funded on the first ledger operation that pays, see [`AppContext::address`].
+    address: OnceCell<Address>,
+    /// Network profile whose nodes `client` is connected to, where new DIDs are published.
+    pub network: NetworkProfile,
+    /// Resolves the IOTA DIDs from the ledger of any network profile, the `did:web` ones over
+    /// HTTP and the `did:key` and `did:jwk` ones offline.
+    pub resolver: Resolver<CoreDocument>,
+    pub input: Box<dyn InputSource>,
 }
 
 
 impl AppContext {
-    pub async fn build_app_context_with_loading() -> Self {
+    pub async fn build_app_context_with_loading(
+        input: Box<dyn InputSource>,
+        network: Option<&str>,
+    ) -> anyhow::Result<Self> {
         let (tx, rx) = watch::channel(true);
         // Spawn the loading animation as a background task
         let animation_handle = tokio::spawn(Output::loading_animation(rx));
 
-        let context = AppContext::my_app_context().await;
+        let context = AppContext::my_app_context(input, network).await;
 
         // Signal the animation to stop
         let _ = tx.send(false);
@@ -38,33 +55,134 @@ impl AppContext {
         context
     }
 
-    pub async fn my_app_context() -> Self {
+    /// Builds the context from the environment, connected to the `network` profile or else the
+    /// configured one, see [`VariablesConfig::network_profile`].
+    pub async fn my_app_context(
+        input: Box<dyn InputSource>,
+        network: Option<&str>,
+    ) -> anyhow::Result<Self> {
         let config = VariablesConfig::get();
-        let tangle_client = AppContext::get_tangle_client(config).await.unwrap();
+        let network = config.network_profile(network)?.clone();
+        // The nodes are only reached by the ledger operations, the offline methods need none
+        let client = Arc::new(OnceCell::new());
         let db = AppContext::get_sqlite_database(config);
         let stronghold_storage = AppContext::get_stronghold_storage(config);
-        let storage = Storage::new(stronghold_storage.clone(), stronghold_storage.clone());
-        let address = AppContext::get_address(&tangle_client, config)
-            .await
-            .unwrap_or(
-                utils::get_address_with_funds(
-                    &tangle_client,
-                    stronghold_storage.as_secret_manager(),
-                    config.get_value("faucet_endpoint"),
-                )
-                .await
-                .unwrap(),
-            );
-        let mut resolver = Resolver::<IotaDocument>::new();
-        resolver.attach_iota_handler(tangle_client.clone());
-        
-        AppContext {
-            client: tangle_client,
+        let storage = Storage::new(
+            StrongholdKeyStorage::new(stronghold_storage.clone()),
+            stronghold_storage.clone(),
+        );
+        let resolver = AppContext::build_resolver(config, &network, client.clone())?;
+
+        Ok(AppContext {
+            client,
             db,
             stronghold_storage,
             storage,
-            address,
+            address: OnceCell::new(),
+            network,
             resolver,
+            input,
+        })
+    }
+
+    /// Builds a context that does not need the environment configuration nor a node:
+    /// an in-memory database, a throwaway Stronghold and a client without nodes.
+    ///
+    /// Used to drive the app from scripts in tests, where ledger operations are not available.
+    pub async fn headless_app_context(input: Box<dyn InputSource>) -> anyhow::Result<Self> {
+        let client: Client = Client::builder().with_ignore_node_health().finish().await?;
+
+        let sqlite = SQLiteConnector::default();
+        create_database_tables(&sqlite)?;
+
+        let mut stronghold_path = std::env::temp_dir();
+        stronghold_path.push("petrus_headless");
+        std::fs::create_dir_all(&stronghold_path)?;
+        stronghold_path
+            .push(rand::distributions::Alphanumeric.sample_string(&mut rand::thread_rng(), 32));
+        stronghold_path.set_extension("stronghold");
+        let stronghold = StrongholdSecretManager::builder()
+            .password(Password::from("headless".to_owned()))
+            .build(stronghold_path)?;
+        let stronghold_storage = StrongholdStorage::new(stronghold);
+        let storage = Storage::new(
+            StrongholdKeyStorage::new(stronghold_storage.clone()),
+            stronghold_storage.clone(),
+        );
+
+        let mut resolver = Resolver::<CoreDocument>::new();
+        resolver.attach_iota_handler(client.clone());
+        resolver.attach_handler("key".to_owned(), resolve_offline_did);
+        resolver.attach_handler("jwk".to_owned(), resolve_offline_did);
+        DidWebResolver::new(None).attach_to(&mut resolver);
+
+        Ok(AppContext {
+            client: Arc::new(OnceCell::new_with(Some(client))),
+            db: Box::new(sqlite),
+            stronghold_storage,
+            storage,
+            address: OnceCell::new_with(Some(Address::Ed25519(Ed25519Address::new([0; 32])))),
+            network: NetworkProfile::new("headless", &[], None, "tst"),
+            resolver,
+            input,
+        })
+    }
+
+    /// Switches to the `name` network profile: new DIDs are published, and the ledger
+    /// operations done, with its nodes and an address funded on it.
+    pub async fn switch_network(&mut self, name: &str) -> anyhow::Result<()> {
+        let config = VariablesConfig::get();
+        let network = config.network_profile(Some(name))?.clone();
+        let client = Arc::new(OnceCell::new());
+        // The IOTA DIDs of the HRP the profiles share now resolve on the new active network
+        let resolver = AppContext::build_resolver(config, &network, client.clone())?;
+
+        self.client = client;
+        self.address = OnceCell::new();
+        self.network = network;
+        self.resolver = resolver;
+        Ok(())
+    }
+
+    /// Client of the nodes of the active network, connected to them on the first call.
+    pub async fn client(&self) -> anyhow::Result<&Client> {
+        self.client
+            .get_or_try_init(|| AppContext::get_tangle_client(&self.network.endpoints))
+            .await
+    }
+
+    /// Address paying for the DIDs on the active network, funded on the first call, see
+    /// [`AppContext::get_network_address`].
+    pub async fn address(&self) -> anyhow::Result<Address> {
+        let client = self.client().await?;
+        let address = self
+            .address
+            .get_or_try_init(|| {
+                AppContext::get_network_address(
+                    client,
+                    &self.network,
+                    &self.stronghold_storage,
+                    VariablesConfig::get(),
+                )
+            })
+            .await?;
+        Ok(*address)
+    }
+
+    /// The IOTA DID of `did`, which has to be ours and live on the active network: the ledger
+    /// operations go through its nodes.
+    pub fn ledger_did(&self, did: &Did) -> anyhow::Result<IotaDID> {
+        if !did.owned() {
+            anyhow::bail!("{} is a contact, only our DIDs can be changed", did.name());
+        }
+        let iota_did = did.iota_did()?;
+        match did.network() {
+            Some(network) if network != self.network.name => anyhow::bail!(
+                "{} lives on the {} network, switch to it to change the DID",
+                did.name(),
+                network
+            ),
+            _ => Ok(iota_did),
         }
     }
 
@@ -76,14 +194,78 @@ impl AppContext {
         Box::new(sqlite)
     }
 
-    async fn get_tangle_client(config: &dyn Config) -> anyhow::Result<Client> {
-        let client: Client = Client::builder()
-            .with_primary_node(config.get_value("api_endpoint"), None)?
-            .finish()
-            .await?;
+    /// Where the `did:web` documents are fetched from instead of their domain, if configured.
+    fn get_did_web_base_url(config: &dyn Config) -> Option<Url> {
+        let base_url = config.get_value("did_web_base_url");
+        if base_url.is_empty() {
+            return None;
+        }
+        Some(Url::parse(base_url).expect("DID_WEB_BASE_URL must be a URL"))
+    }
+
+    async fn get_tangle_client(endpoints: &[String]) -> anyhow::Result<Client> {
+        let endpoints: Vec<&str> = endpoints.iter().map(String::as_str).collect();
+        let client: Client = Client::builder().with_nodes(&endpoints)?.finish().await?;
         Ok(client)
     }
 
+    /// The resolver of the DIDs of every method, see [`AppContext::resolver`], with the `active`
+    /// network resolving through its `client`.
+    fn build_resolver(
+        config: &VariablesConfig,
+        active: &NetworkProfile,
+        client: Arc<OnceCell<Client>>,
+    ) -> anyhow::Result<Resolver<CoreDocument>> {
+        let mut resolver = Resolver::<CoreDocument>::new();
+        AppContext::attach_network_handlers(
+            &mut resolver,
+            config.network_profiles()?,
+            active,
+            client,
+        );
+        resolver.attach_handler("key".to_owned(), resolve_offline_did);
+        resolver.attach_handler("jwk".to_owned(), resolve_offline_did);
+        DidWebResolver::new(AppContext::get_did_web_base_url(config)).attach_to(&mut resolver);
+        Ok(resolver)
+    }
+
+    /// Resolves the IOTA DIDs with the nodes of the profile of the network in the DID, so the
+    /// DIDs of every profile resolve whichever one is active. When profiles share an HRP the
+    /// active one, or else the first one, resolves their DIDs.
+    fn attach_network_handlers(
+        resolver: &mut Resolver<CoreDocument>,
+        profiles: &[NetworkProfile],
+        active: &NetworkProfile,
+        active_client: Arc<OnceCell<Client>>,
+    ) {
+        let mut clients: HashMap<String, (Vec<String>, Arc<OnceCell<Client>>)> = HashMap::new();
+        clients.insert(
+            active.hrp.clone(),
+            (active.endpoints.clone(), active_client),
+        );
+        for profile in profiles {
+            clients
+                .entry(profile.hrp.clone())
+                .or_insert_with(|| (profile.endpoints.clone(), Arc::default()));
+        }
+
+        let clients = Arc::new(clients);
+        resolver.attach_handler(IotaDID::METHOD.to_owned(), move |did: IotaDID| {
+            let clients = clients.clone();
+            async move {
+                let (endpoints, client) = clients.get(did.network_str()).ok_or_else(|| {
+                    anyhow::anyhow!("No network profile for the {} network", did.network_str())
+                })?;
+                // The nodes are only reached when one of the DIDs of their network is resolved
+                let client = client
+                    .get_or_try_init(|| AppContext::get_tangle_client(endpoints))
+                    .await?;
+                let document: IotaDocument = client.resolve_did(&did).await?;
+                Ok::<IotaDocument, anyhow::Error>(document)
+            }
+        });
+    }
+
     fn get_stronghold_storage(config: &dyn Config) -> StrongholdStorage {
         // Stronghold password.
         let password = Password::from(config.get_value("stronghold_password").to_owned());
@@ -96,25 +278,52 @@ impl AppContext {
             .build(stronghold_path.clone())
             .unwrap();
 
-        let stronghold_storage = StrongholdStorage::new(stronghold);
+        StrongholdStorage::new(stronghold)
+    }
+
+    /// Address paying for the DIDs on `network`. On a network with a faucet it is funded from
+    /// it, elsewhere it has to be funded beforehand.
+    async fn get_network_address(
+        client: &Client,
+        network: &NetworkProfile,
+        stronghold_storage: &StrongholdStorage,
+        config: &VariablesConfig,
+    ) -> anyhow::Result<Address> {
+        let secret_manager = stronghold_storage.as_secret_manager();
+        let Some(faucet) = &network.faucet else {
+            return match AppContext::get_configured_address(config) {
+                Ok(address) => Ok(address),
+                Err(_) => Ok(*utils::get_address(client, secret_manager).await?),
+            };
+        };
 
-        stronghold_storage
+        match AppContext::get_address(client, config, faucet).await {
+            Ok(address) => Ok(address),
+            Err(_) => utils::get_address_with_funds(client, secret_manager, faucet).await,
+        }
     }
 
-    async fn get_address(client: &Client, config: &dyn Config) -> anyhow::Result<Address> {
+    fn get_configured_address(config: &dyn Config) -> anyhow::Result<Address> {
         // Create an address from value
         let address_bytes = hex::decode(config.get_value("network_address"))?;
-
-        // Create an Ed25519Address instance
-        let ed25519_address = Ed25519Address::new(address_bytes.try_into().unwrap());
+        let address_bytes: [u8; 32] = address_bytes
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("NETWORK_ADDRESS must be 32 bytes"))?;
 
         // Wrap it into an Address enum
-        let address = Address::Ed25519(ed25519_address);
+        Ok(Address::Ed25519(Ed25519Address::new(address_bytes)))
+    }
+
+    async fn get_address(
+        client: &Client,
+        config: &VariablesConfig,
+        faucet: &str,
+    ) -> anyhow::Result<Address> {
+        let address = AppContext::get_configured_address(config)?;
         let bech32_hrp: Hrp = client.get_bech32_hrp().await?;
         let bech32_address = address.to_bech32(bech32_hrp);
 
-        utils::request_faucet_funds(client, bech32_address, config.get_value("faucet_endpoint"))
-            .await?;
+        utils::request_faucet_funds(client, bech32_address, faucet).await?;
 
         Ok(*bech32_address)
     }
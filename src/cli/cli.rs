@@ -0,0 +1,151 @@
+use crate::{DidKind, KeyAlgorithm, OutputFormat};
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+/// Command line interface of Petrus.
+///
+/// When no subcommand is given the interactive menu is started.
+#[derive(Parser, Debug)]
+#[command(
+    name = "petrus",
+    version,
+    about = "Manage DIDs, VCs and VPs on the IOTA Tangle"
+)]
+pub struct Cli {
+    /// Format of the results printed by the subcommands
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+
+    /// Network profile to connect to (e.g. testnet, shimmer), instead of the NETWORK_PROFILE one
+    #[arg(long, global = true, value_name = "PROFILE")]
+    pub network: Option<String>,
+
+    /// Drive the interactive menu from a script file, one input per line
+    #[arg(long, value_name = "FILE", conflicts_with = "record")]
+    pub script: Option<PathBuf>,
+
+    /// Record the inputs of the interactive session to a script file that can be replayed
+    #[arg(long, value_name = "FILE")]
+    pub record: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<CliCommand>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CliCommand {
+    /// Manage Decentralized Identifiers
+    #[command(subcommand)]
+    Did(DidCommand),
+    /// Issue and verify Verifiable Credentials
+    #[command(subcommand)]
+    Vc(VcCommand),
+    /// Create Verifiable Presentations
+    #[command(subcommand)]
+    Vp(VpCommand),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DidCommand {
+    /// Create and publish a new DID
+    Create(CreateDidArgs),
+    /// List the stored DIDs
+    List,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum VcCommand {
+    /// Issue a new credential from a template
+    Issue(IssueVcArgs),
+    /// Verify a stored credential, or a JWT or SD-JWT credential from a file
+    Verify(VerifyVcArgs),
+    /// List the stored credentials
+    List,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum VpCommand {
+    /// Create a presentation of a stored credential and verify it as the verifier
+    Create(CreateVpArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct CreateDidArgs {
+    /// Name to be linked with the DID
+    #[arg(long)]
+    pub name: String,
+    /// Key type of the verification method
+    #[arg(long, value_enum, default_value_t = KeyAlgorithm::Ed25519)]
+    pub alg: KeyAlgorithm,
+    /// DID method, `key` and `jwk` DIDs are not published
+    #[arg(long, value_enum, default_value_t = DidKind::Iota)]
+    pub method: DidKind,
+    /// Domain, with an optional path, hosting a `web` DID (e.g. example.com/users/alice)
+    #[arg(long, required_if_eq("method", "web"))]
+    pub domain: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct IssueVcArgs {
+    /// Template file name inside the credentials template directory (e.g. drivers_license.json)
+    #[arg(long)]
+    pub template: String,
+    /// Database id of the issuer DID
+    #[arg(long)]
+    pub issuer: i64,
+    /// Database id of the holder DID
+    #[arg(long)]
+    pub holder: i64,
+    /// JSON file with the claims, following the structure of the template
+    #[arg(long)]
+    pub claims: PathBuf,
+    /// Issue the credential with Selective Disclosure
+    #[arg(long)]
+    pub sd: bool,
+    /// File with the JSON paths to conceal, one per line (defaults to every claim)
+    #[arg(long, requires = "sd")]
+    pub sd_paths: Option<PathBuf>,
+    /// Database id of the status list of the issuer to put the credential in (defaults to the
+    /// revocation bitmap of the issuer, if it has one)
+    #[arg(long, conflicts_with = "sd")]
+    pub status_list: Option<i64>,
+    /// Date the credential is valid from, YYYY-MM-DD or RFC 3339 (defaults to now)
+    #[arg(long)]
+    pub valid_from: Option<String>,
+    /// Date, or duration after the start such as 90d, 6mo or 5y, the credential is valid until,
+    /// `never` for no expiration (defaults to the one of the template in the
+    /// CREDENTIALS_VALIDITY_FILE)
+    #[arg(long)]
+    pub valid_until: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct VerifyVcArgs {
+    /// Database id of the credential
+    #[arg(long, required_unless_present = "file", conflicts_with = "file")]
+    pub vc: Option<i64>,
+    /// Database id of the DID to verify as the issuer (defaults to the one the credential names)
+    #[arg(long, conflicts_with = "file")]
+    pub issuer: Option<i64>,
+    /// File with a JWT or SD-JWT credential to verify instead of a stored one, against the
+    /// issuer DID named by its `iss` claim
+    #[arg(long)]
+    pub file: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+pub struct CreateVpArgs {
+    /// Database id of the credential to present
+    #[arg(long)]
+    pub vc: i64,
+    /// Database id of the verifier DID
+    #[arg(long)]
+    pub verifier: i64,
+    /// Disclosures to reveal for Selective Disclosure credentials, by claim name, or claims to
+    /// reveal for JPT credentials, by path (e.g. `degree.name`)
+    #[arg(long, value_delimiter = ',')]
+    pub disclose: Vec<String>,
+    /// Expiration of the presentation in minutes
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(0..=60))]
+    pub expires: u32,
+}
@@ -0,0 +1,168 @@
+use crate::{utils, CheckResult, Did, ListDIDsCommand, ListVCsCommand, Vc, VerificationReport};
+use clap::ValueEnum;
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Format used by the subcommands to print their results.
+#[derive(ValueEnum, Serialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// Exit codes of the subcommands.
+pub const EXIT_SUCCESS: i32 = 0;
+pub const EXIT_ERROR: i32 = 1;
+pub const EXIT_VERIFICATION_FAILED: i32 = 3;
+
+/// Result of a subcommand.
+///
+/// In JSON mode it is printed as a single document tagged by `operation`,
+/// so the field names below are part of the public interface and must stay stable.
+#[derive(Serialize, Debug)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+pub enum CliReport {
+    #[serde(rename = "did.create")]
+    DidCreate {
+        did: Did,
+        fragment: String,
+        /// The `did.json` to host, for a `did:web`.
+        document_file: Option<String>,
+    },
+    #[serde(rename = "did.list")]
+    DidList { dids: Vec<Did> },
+    #[serde(rename = "vc.issue")]
+    VcIssue { vc: Box<Vc> },
+    #[serde(rename = "vc.list")]
+    VcList { vcs: Vec<Vc> },
+    #[serde(rename = "vc.verify")]
+    VcVerify {
+        /// `None` for a credential verified from a file.
+        vc_id: Option<i64>,
+        issuer: String,
+        valid: bool,
+        /// The failed checks, one after the other.
+        error: Option<String>,
+        checks: Vec<CheckResult>,
+        warnings: Vec<String>,
+        credential: Option<Value>,
+    },
+    #[serde(rename = "vp.create")]
+    VpCreate {
+        vc_id: i64,
+        verifier: String,
+        sd: bool,
+        presentation: String,
+        challenge: String,
+        valid: bool,
+        error: Option<String>,
+    },
+}
+
+#[derive(Serialize)]
+struct ErrorReport<'a> {
+    status: &'a str,
+    error: String,
+}
+
+#[derive(Serialize)]
+struct SuccessReport<'a> {
+    status: &'a str,
+    #[serde(flatten)]
+    report: &'a CliReport,
+}
+
+impl CliReport {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliReport::VcVerify { valid: false, .. } | CliReport::VpCreate { valid: false, .. } => {
+                EXIT_VERIFICATION_FAILED
+            }
+            _ => EXIT_SUCCESS,
+        }
+    }
+
+    pub fn print(&self, format: OutputFormat) {
+        match format {
+            OutputFormat::Json => {
+                let report = SuccessReport {
+                    status: "ok",
+                    report: self,
+                };
+                println!("{}", serde_json::to_string_pretty(&report).unwrap());
+            }
+            OutputFormat::Text => self.print_text(),
+        }
+    }
+
+    pub fn print_error(error: &anyhow::Error, format: OutputFormat) {
+        match format {
+            OutputFormat::Json => {
+                let report = ErrorReport {
+                    status: "error",
+                    error: format!("{:#}", error),
+                };
+                println!("{}", serde_json::to_string_pretty(&report).unwrap());
+            }
+            OutputFormat::Text => eprintln!("Error: {:#}", error),
+        }
+    }
+
+    fn print_text(&self) {
+        match self {
+            CliReport::DidCreate {
+                did,
+                fragment,
+                document_file,
+            } => {
+                println!("DID: {}", did.did());
+                println!("Fragment: {}", fragment);
+                println!("Id: {}", did.id());
+                if let Some(file) = document_file {
+                    println!("Document to host: {}", file);
+                }
+            }
+            CliReport::DidList { dids } => ListDIDsCommand::display_dids_table(dids, 1),
+            CliReport::VcIssue { vc } => {
+                println!("Id: {}", vc.id());
+                println!("{}", vc.vc());
+            }
+            CliReport::VcList { vcs } => ListVCsCommand::display_vcs_table(vcs, 1),
+            CliReport::VcVerify {
+                valid,
+                checks,
+                warnings,
+                credential,
+                ..
+            } => {
+                VerificationReport::print_checks(checks);
+                VerificationReport::print_warnings(warnings);
+                match (valid, credential) {
+                    (true, Some(credential)) => utils::pretty_print_json(
+                        "VC verified successfully",
+                        &credential.to_string(),
+                    ),
+                    _ => println!("{}", "VC verification failed".red().bold()),
+                }
+            }
+            CliReport::VpCreate {
+                presentation,
+                valid,
+                error,
+                ..
+            } => {
+                println!("{}", presentation);
+                if !valid {
+                    println!(
+                        "{} {}",
+                        "VP verification failed:".red().bold(),
+                        error.clone().unwrap_or_default()
+                    );
+                }
+            }
+        }
+    }
+}
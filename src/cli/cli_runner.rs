@@ -0,0 +1,338 @@
+use crate::{
+    utils, AppContext, CliCommand, CliReport, Config, CreateDIDCommand, CreateVCNormalCommand,
+    CreateVCSDCommand, CreateVPCommand, CreateVpArgs, DidCommand, DidKind, DidStatus, IssueVcArgs,
+    KeyAlgorithm, Output, Validity, VariablesConfig, VcCommand, VerifyVCCommand, VerifyVcArgs,
+    VpCommand,
+};
+use anyhow::{Context, Result};
+use identity_iota::core::{Duration as IotaDuration, Timestamp};
+use identity_iota::document::CoreDocument;
+use sd_jwt_payload::SdJwt;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Runs the subcommands of the [`Cli`](crate::Cli) without any user interaction,
+/// reusing the business logic of the interactive commands.
+pub struct CliRunner<'a> {
+    context: &'a AppContext,
+}
+
+impl CliRunner<'_> {
+    pub fn new(context: &AppContext) -> CliRunner<'_> {
+        CliRunner { context }
+    }
+
+    pub async fn run(&self, command: CliCommand) -> Result<CliReport> {
+        match command {
+            CliCommand::Did(DidCommand::Create(args)) => {
+                self.create_did(&args.name, args.alg, args.method, args.domain)
+                    .await
+            }
+            CliCommand::Did(DidCommand::List) => Ok(CliReport::DidList {
+                dids: self.context.db.get_stored_dids()?,
+            }),
+            CliCommand::Vc(VcCommand::Issue(args)) => self.issue_vc(args).await,
+            CliCommand::Vc(VcCommand::Verify(args)) => self.verify_vc(args).await,
+            CliCommand::Vc(VcCommand::List) => Ok(CliReport::VcList {
+                vcs: self.context.db.get_stored_vcs()?,
+            }),
+            CliCommand::Vp(VpCommand::Create(args)) => self.create_vp(args).await,
+        }
+    }
+
+    async fn create_did(
+        &self,
+        name: &str,
+        algorithm: KeyAlgorithm,
+        kind: DidKind,
+        domain: Option<String>,
+    ) -> Result<CliReport> {
+        let command = CreateDIDCommand::new(self.context);
+        let (document, fragment, document_file) = match (kind, domain) {
+            (DidKind::Web, Some(domain)) => {
+                let (document, fragment, file) = command.create_did_web(&domain, algorithm).await?;
+                (document, fragment, Some(file.display().to_string()))
+            }
+            _ => {
+                let (document, fragment) = command.create_did(kind, algorithm).await?;
+                (document, fragment, None)
+            }
+        };
+        let id = command.save_did(&document, name)?;
+
+        Ok(CliReport::DidCreate {
+            did: self.context.db.get_did_from_id(id)?,
+            fragment,
+            document_file,
+        })
+    }
+
+    async fn issue_vc(&self, args: IssueVcArgs) -> Result<CliReport> {
+        let issuer = self.context.db.get_did_from_id(args.issuer)?;
+        let holder = self.context.db.get_did_from_id(args.holder)?;
+        // The issuer signs and the holder will present the VC, so both have to be ours
+        if let Some(contact) = [&issuer, &holder].into_iter().find(|did| !did.owned()) {
+            anyhow::bail!(
+                "{} is a contact, only our DIDs can issue or hold a VC",
+                contact.name()
+            );
+        }
+        if let Some(inactive) = [&issuer, &holder]
+            .into_iter()
+            .find(|did| did.status() != DidStatus::Active)
+        {
+            anyhow::bail!(
+                "{} is {}, only active DIDs can issue or hold a VC",
+                inactive.name(),
+                inactive.status()
+            );
+        }
+        let issuer_document = issuer.resolve_document(&self.context.resolver).await?;
+        let holder_document = holder.resolve_document(&self.context.resolver).await?;
+
+        let claims = Self::read_claims(&args.template, &args.claims)?;
+        let credential_type = Output::snake_to_camel_case(&args.template);
+        let valid_until = match args.valid_until {
+            Some(valid_until) => Some(valid_until),
+            None => CreateVCNormalCommand::default_validity_end(&args.template)?,
+        };
+        let validity = Validity::parse(args.valid_from.as_deref(), valid_until.as_deref())?;
+
+        let mut slot = None;
+        let credential = if args.sd {
+            let json_paths = match &args.sd_paths {
+                Some(path) => utils::read_file_ignoring_comments(&path.to_string_lossy())?,
+                None => utils::generate_json_paths(&claims, "/vc/credentialSubject"),
+            };
+            let (sd_jwt, _) = CreateVCSDCommand::new(self.context)
+                .issue_sd_credential(
+                    &issuer_document,
+                    issuer.fragment(),
+                    &holder_document,
+                    claims,
+                    &credential_type,
+                    &validity,
+                    json_paths,
+                )
+                .await?;
+            sd_jwt
+        } else {
+            let vc_command = CreateVCNormalCommand::new(self.context);
+            // Without a status list, the VC goes in the revocation bitmap if the issuer has one
+            slot = match args.status_list {
+                Some(list_id) => {
+                    let list = self.context.db.get_status_list(list_id)?;
+                    if list.issuer() != issuer.id() {
+                        anyhow::bail!(
+                            "The status list {} is not one of {}",
+                            list_id,
+                            issuer.name()
+                        );
+                    }
+                    Some(vc_command.status_list_slot(&list)?)
+                }
+                None => vc_command.bitmap_slot(&issuer, &issuer_document)?,
+            };
+            let (jwt, _) = vc_command
+                .issue_credential(
+                    &issuer_document,
+                    issuer.fragment(),
+                    &holder_document,
+                    claims,
+                    &credential_type,
+                    &validity,
+                    slot.as_ref().map(|slot| slot.status.clone()),
+                )
+                .await?;
+            jwt.as_str().to_string()
+        };
+
+        let id = self.context.db.save_vc(
+            &credential,
+            issuer.id(),
+            holder.id(),
+            &credential_type,
+            args.sd,
+        )?;
+        let vc_command = CreateVCNormalCommand::new(self.context);
+        vc_command.save_validity(id, &validity)?;
+        if let Some(slot) = &slot {
+            vc_command.save_status_slot(id, slot)?;
+        }
+
+        Ok(CliReport::VcIssue {
+            vc: Box::new(self.context.db.get_vc_from_id(id)?),
+        })
+    }
+
+    /// Reads the claims file and checks that it follows the structure of the chosen template.
+    fn read_claims(template: &str, claims_path: &Path) -> Result<Value> {
+        let template_path =
+            Path::new(VariablesConfig::get().get_value("credentials_template_directory"))
+                .join(template);
+        let template_json = utils::read_json_file(&template_path.to_string_lossy())?;
+        let claims = utils::read_json_file(&claims_path.to_string_lossy())?;
+
+        if !utils::have_same_structure(&claims, &template_json) {
+            anyhow::bail!(
+                "The claims in {} do not have the same structure as the template {}",
+                claims_path.display(),
+                template
+            );
+        }
+
+        Ok(claims)
+    }
+
+    async fn verify_vc(&self, args: VerifyVcArgs) -> Result<CliReport> {
+        let status_list_directory = VerifyVCCommand::status_list_directory();
+        // A credential that fails verification is a result, not an error of the command
+        let report = match (args.file, args.vc) {
+            (Some(file), _) => {
+                let token = fs::read_to_string(&file)
+                    .with_context(|| format!("Cannot read the token from {}", file.display()))?;
+                VerifyVCCommand::verify_token(&self.context.resolver, &token, status_list_directory)
+                    .await?
+            }
+            (None, Some(vc_id)) => {
+                let vc = self.context.db.get_vc_from_id(vc_id)?;
+                let issuer = match args.issuer {
+                    Some(id) => self.context.db.get_did_from_id(id)?.did().to_string(),
+                    None => VerifyVCCommand::stored_vc_issuer(&vc)?,
+                };
+                let issuer_document =
+                    VerifyVCCommand::resolve_did(&self.context.resolver, &issuer).await;
+                VerifyVCCommand::verification_report(
+                    &vc,
+                    &issuer,
+                    issuer_document,
+                    status_list_directory,
+                )
+            }
+            (None, None) => anyhow::bail!("Either a stored VC or a file to verify is needed"),
+        };
+        let error = match report.failures().as_slice() {
+            [] => None,
+            failures => Some(
+                failures
+                    .iter()
+                    .map(|failure| format!("{}: {}", failure.check.label(), failure.reason))
+                    .collect::<Vec<String>>()
+                    .join("; "),
+            ),
+        };
+
+        Ok(CliReport::VcVerify {
+            vc_id: report.vc_id,
+            issuer: report.issuer,
+            valid: report.valid,
+            error,
+            checks: report.checks,
+            warnings: report.warnings,
+            credential: report.credential,
+        })
+    }
+
+    async fn create_vp(&self, args: CreateVpArgs) -> Result<CliReport> {
+        let vc = self.context.db.get_vc_from_id(args.vc)?;
+        let verifier = self.context.db.get_did_from_id(args.verifier)?;
+        let verifier_document: CoreDocument =
+            verifier.resolve_document(&self.context.resolver).await?;
+        let vp_command = CreateVPCommand::new(self.context);
+        let challenge = Uuid::new_v4().to_string();
+
+        let (presentation, verification) = if vc.jpt() {
+            let claims = vp_command.jpt_claims(&vc).await?;
+            if let Some(missing) = args.disclose.iter().find(|path| !claims.contains(path)) {
+                anyhow::bail!(
+                    "The credential has no claim '{}'. Available: {}",
+                    missing,
+                    claims.join(", ")
+                );
+            }
+
+            let presentation = vp_command
+                .build_jpt_presentation(&vc, &args.disclose, &challenge)
+                .await?;
+            let verification = vp_command
+                .verify_jpt_presentation(&presentation, &challenge)
+                .await;
+
+            (presentation, verification)
+        } else if vc.sd() {
+            let sd_jwt = SdJwt::parse(vc.vc())?;
+            let disclosures = Self::select_disclosures(&sd_jwt.disclosures, &args.disclose)?;
+
+            let presentation = vp_command
+                .build_sd_presentation(&vc, disclosures, &challenge, &verifier_document)
+                .await?;
+            let verification = vp_command
+                .verify_sd_jwt_presentation(&presentation, &verifier_document, &challenge)
+                .await;
+
+            (presentation, verification)
+        } else {
+            let expires = Timestamp::now_utc()
+                .checked_add(IotaDuration::minutes(args.expires))
+                .ok_or(anyhow::anyhow!("Invalid expiration time"))?;
+
+            let presentation = vp_command
+                .build_normal_presentation(&vc, expires, &challenge)
+                .await?;
+            let verification = vp_command
+                .verify_jwt_presentation_normal(challenge.clone(), &presentation)
+                .await;
+
+            (presentation.as_str().to_string(), verification)
+        };
+
+        let error = verification.err().map(|e| format!("{:#}", e));
+        Ok(CliReport::VpCreate {
+            vc_id: vc.id(),
+            verifier: verifier.did().to_string(),
+            sd: vc.sd(),
+            presentation,
+            challenge,
+            valid: error.is_none(),
+            error,
+        })
+    }
+
+    /// Picks the disclosures whose claim name was requested on the command line. Every disclosure
+    /// must name its claim, as the ones hiding an array element cannot be requested.
+    pub fn select_disclosures(disclosures: &[String], requested: &[String]) -> Result<Vec<String>> {
+        let named = disclosures
+            .iter()
+            .map(|disclosure| match utils::disclosure_key(disclosure)? {
+                Some(key) => Ok((key, disclosure)),
+                None => Err(anyhow::anyhow!(
+                    "The disclosure {} hides an array element, which cannot be selected by name",
+                    disclosure
+                )),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if let Some(missing) = requested
+            .iter()
+            .find(|name| !named.iter().any(|(key, _)| key == *name))
+        {
+            anyhow::bail!(
+                "The credential has no disclosure named '{}'. Available: {}",
+                missing,
+                named
+                    .iter()
+                    .map(|(key, _)| key.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+
+        Ok(named
+            .into_iter()
+            .filter(|(key, _)| requested.contains(key))
+            .map(|(_, disclosure)| disclosure.clone())
+            .collect())
+    }
+}
@@ -0,0 +1,7 @@
+pub use cli::*;
+pub use cli_report::*;
+pub use cli_runner::*;
+
+mod cli;
+mod cli_report;
+mod cli_runner;
@@ -1,6 +1,8 @@
 use crate::ScreenEvent;
+use async_trait::async_trait;
 
-pub trait Command {
-    fn execute(&mut self) -> ScreenEvent;
+#[async_trait]
+pub trait Command: Send {
+    async fn execute(&mut self) -> ScreenEvent;
     fn print_tile(&self);
 }
@@ -0,0 +1,141 @@
+use crate::{
+    method_scopes, scope_name, utils, AppContext, Checklist, Command, CommandRegistration,
+    CreateDIDCommand, Did, DidQuery, KeyAlgorithm, ListDIDsCommand, Output, Progress, ScreenEvent,
+    ScreenState,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use identity_iota::iota::{IotaDocument, IotaIdentityClientExt};
+use identity_iota::storage::JwkDocumentExt;
+use identity_iota::verification::{MethodRelationship, MethodScope};
+
+/// Adds verification methods to a published DID, with their keys in our Stronghold.
+pub struct AddMethodsCommand<'a> {
+    context: &'a AppContext,
+}
+
+#[async_trait]
+impl Command for AddMethodsCommand<'_> {
+    async fn execute(&mut self) -> ScreenEvent {
+        self.print_tile();
+        self.handle_add_methods()
+            .await
+            .unwrap_or_else(|e| ScreenEvent::Error(e.to_string()))
+    }
+
+    fn print_tile(&self) {
+        Output::clear_screen();
+        Output::print_screen_title("Add Verification Methods")
+    }
+}
+
+impl AddMethodsCommand<'_> {
+    pub fn new(context: &AppContext) -> AddMethodsCommand<'_> {
+        AddMethodsCommand { context }
+    }
+
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::AddMethodsWorkflow, |context, _| {
+            Box::new(AddMethodsCommand::new(context))
+        })
+        .menu_entry(
+            ScreenState::UpdateDIDMenu,
+            "Add verification methods",
+            ScreenEvent::AddMethods,
+        )
+    }
+
+    async fn handle_add_methods(&self) -> Result<ScreenEvent> {
+        if self.context.db.search_dids(&DidQuery::active())?.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No active DIDs found. Please create a DID first."
+            ));
+        }
+        let Some(did) = ListDIDsCommand::select_did(self.context, "Choose a DID to update").await
+        else {
+            return Ok(ScreenEvent::Cancel);
+        };
+
+        // The keys are signing ones, which cannot agree on a key
+        let scopes: Vec<(&str, MethodScope)> = method_scopes()
+            .into_iter()
+            .filter(|(_, scope)| *scope != MethodScope::from(MethodRelationship::KeyAgreement))
+            .collect();
+        let labels = scopes.iter().map(|(name, _)| name.to_string()).collect();
+        let checked = Checklist::new("Scopes of the new methods, one method each", labels)
+            .select(self.context.input.as_ref())
+            .await;
+        if checked.is_empty() {
+            return Ok(ScreenEvent::Cancel);
+        }
+        let scopes: Vec<MethodScope> = checked.into_iter().map(|index| scopes[index].1).collect();
+        let Some(algorithm) =
+            CreateDIDCommand::choose_key_algorithm(self.context, "Key type of the new methods")
+                .await
+        else {
+            return Ok(ScreenEvent::Cancel);
+        };
+
+        let methods =
+            Progress::run("Updating DID", self.add_methods(&did, &scopes, algorithm)).await?;
+
+        Output::print_during_loading("Saving the new methods to database");
+        for (fragment, scope) in &methods {
+            self.context.db.save_did_method(
+                did.id(),
+                fragment,
+                scope_name(scope),
+                algorithm.jws_algorithm().name(),
+            )?;
+            Output::print_during_loading(&format!("Added #{} ({})", fragment, scope_name(scope)));
+        }
+
+        self.context
+            .input
+            .wait_for_user_input("Press any key to continue...")
+            .await;
+        Ok(ScreenEvent::Success)
+    }
+
+    /// Generates a method in each of `scopes` and publishes the updated document. Returns the
+    /// fragments of the new methods.
+    async fn add_methods(
+        &self,
+        did: &Did,
+        scopes: &[MethodScope],
+        algorithm: KeyAlgorithm,
+    ) -> Result<Vec<(String, MethodScope)>> {
+        Output::print_during_loading("Resolving DID...");
+        let mut document: IotaDocument = self
+            .context
+            .client()
+            .await?
+            .resolve_did(&self.context.ledger_did(did)?)
+            .await?;
+
+        let mut methods = Vec::new();
+        for scope in scopes {
+            Output::print_during_loading(&format!("Generating a {} method...", scope_name(scope)));
+            let fragment = document
+                .generate_method(
+                    &self.context.storage,
+                    algorithm.key_type(),
+                    algorithm.jws_algorithm(),
+                    None,
+                    *scope,
+                )
+                .await?;
+            methods.push((fragment, *scope));
+        }
+
+        Output::print_during_loading("Publishing DID...");
+        utils::publish_did_update(
+            self.context.client().await?,
+            self.context.stronghold_storage.as_secret_manager(),
+            document,
+        )
+        .await?;
+
+        Ok(methods)
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{
+    AppContext, Checklist, Command, CommandRegistration, ContactRole, Did, DidQuery, InputSource,
+    ListDIDsCommand, Menu, Output, Progress, ScreenEvent, ScreenState,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use identity_iota::did::{CoreDID, DID};
+use identity_iota::document::CoreDocument;
+
+/// Address book of the DIDs controlled by someone else, which can be chosen as the issuer to
+/// verify a VC against or as the verifier of a VP, but never sign.
+pub struct ContactsCommand<'a> {
+    context: &'a AppContext,
+}
+
+#[async_trait]
+impl Command for ContactsCommand<'_> {
+    async fn execute(&mut self) -> ScreenEvent {
+        self.print_tile();
+        self.handle_contacts()
+            .await
+            .unwrap_or_else(|e| ScreenEvent::Error(e.to_string()))
+    }
+
+    fn print_tile(&self) {
+        Output::clear_screen();
+        Output::print_screen_title("Contacts")
+    }
+}
+
+impl ContactsCommand<'_> {
+    pub fn new(context: &AppContext) -> ContactsCommand<'_> {
+        ContactsCommand { context }
+    }
+
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::ContactsWorkflow, |context, _| {
+            Box::new(ContactsCommand::new(context))
+        })
+        .menu_entry(ScreenState::MainMenu, "Contacts", ScreenEvent::Contacts)
+    }
+
+    async fn handle_contacts(&self) -> Result<ScreenEvent> {
+        let options = vec![
+            "Import a contact".to_string(),
+            "Edit a contact".to_string(),
+            "Re-resolve a contact".to_string(),
+        ];
+        match Menu::new("Contacts", options)
+            .message("DIDs of other parties, to verify their VCs or present VCs to them")
+            .select(self.context.input.as_ref())
+            .await
+        {
+            Some(0) => self.import_contact().await,
+            Some(1) => self.edit_contact().await,
+            Some(_) => self.refresh_contact().await,
+            None => Ok(ScreenEvent::Cancel),
+        }
+    }
+
+    async fn import_contact(&self) -> Result<ScreenEvent> {
+        let input = self.context.input.as_ref();
+        let did = input
+            .wait_for_user_input("DID of the contact (e.g. did:iota:tst:0x...):")
+            .await;
+        let did = CoreDID::parse(did.trim())?;
+        if let Some(stored) = self.context.db.find_did(did.as_str())? {
+            anyhow::bail!("{} is already stored as {}", did, stored.name());
+        }
+
+        let document = Progress::run("Resolving DID", self.resolve(&did)).await?;
+        println!("Resolved {}", document.id());
+        let label = Self::read_label(input, None).await;
+        let roles = self.choose_roles(&[]).await;
+
+        self.context.db.save_contact(&document, &label, &roles)?;
+        Output::print_during_loading(&format!("Contact {} saved", label));
+        input
+            .wait_for_user_input("Press any key to continue...")
+            .await;
+        Ok(ScreenEvent::Success)
+    }
+
+    async fn edit_contact(&self) -> Result<ScreenEvent> {
+        let Some(contact) = self.select_contact("Choose a contact to edit").await? else {
+            return Ok(ScreenEvent::Cancel);
+        };
+        let label = Self::read_label(self.context.input.as_ref(), Some(contact.name())).await;
+        let roles = self.choose_roles(contact.roles()).await;
+
+        self.context
+            .db
+            .update_contact(contact.id(), &label, &roles)?;
+        Ok(ScreenEvent::Success)
+    }
+
+    /// Resolves the contact again, to record the methods and services it has now.
+    async fn refresh_contact(&self) -> Result<ScreenEvent> {
+        let Some(contact) = self
+            .select_contact("Choose a contact to re-resolve")
+            .await?
+        else {
+            return Ok(ScreenEvent::Cancel);
+        };
+        let did = CoreDID::parse(contact.did())?;
+        let document = Progress::run("Resolving DID", self.resolve(&did)).await?;
+
+        self.context.db.refresh_contact(contact.id(), &document)?;
+        println!(
+            "{} has {} methods and {} services",
+            contact.name(),
+            document.methods(None).len(),
+            document.service().len()
+        );
+        self.context
+            .input
+            .wait_for_user_input("Press any key to continue...")
+            .await;
+        Ok(ScreenEvent::Success)
+    }
+
+    async fn resolve(&self, did: &CoreDID) -> Result<CoreDocument> {
+        Output::print_during_loading("Resolving DID...");
+        Ok(self.context.resolver.resolve(did).await?)
+    }
+
+    /// `None` when the user went back.
+    async fn select_contact(&self, title: &str) -> Result<Option<Did>> {
+        if self
+            .context
+            .db
+            .search_dids(&DidQuery::contacts())?
+            .is_empty()
+        {
+            anyhow::bail!("No contacts found. Please import one first.");
+        }
+        Ok(ListDIDsCommand::select_did_among(self.context, title, DidQuery::contacts()).await)
+    }
+
+    /// Asks for the label of the contact, `current` is kept on an empty answer.
+    pub(crate) async fn read_label(input: &dyn InputSource, current: Option<&str>) -> String {
+        loop {
+            let label = match current {
+                Some(current) => {
+                    input
+                        .wait_for_user_input(&format!("Label (empty to keep {}):", current))
+                        .await
+                }
+                None => input.wait_for_user_input("Label of the contact:").await,
+            };
+            match (label.trim(), current) {
+                ("", Some(current)) => return current.to_string(),
+                ("", None) => println!("Input cannot be blank. Please try again."),
+                (label, _) => return label.to_string(),
+            }
+        }
+    }
+
+    /// Asks what the contact can be chosen as, starting from its `current` roles.
+    async fn choose_roles(&self, current: &[ContactRole]) -> Vec<ContactRole> {
+        let roles = ContactRole::all();
+        let labels = roles.iter().map(|role| role.to_string()).collect();
+        let checked: Vec<usize> = (0..roles.len())
+            .filter(|index| current.contains(&roles[*index]))
+            .collect();
+
+        let chosen = Checklist::new("Roles of the contact", labels)
+            .checked(&checked)
+            .select(self.context.input.as_ref())
+            .await;
+        chosen.into_iter().map(|index| roles[index]).collect()
+    }
+}
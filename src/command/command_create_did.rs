@@ -1,26 +1,33 @@
-use crate::{AppContext, Command, Input, Output, ScreenEvent};
-use identity_iota::iota::{IotaClientExt, IotaDocument, IotaIdentityClientExt, NetworkName};
-use identity_iota::storage::{JwkDocumentExt, JwkMemStore};
-use identity_iota::verification::jws::JwsAlgorithm;
+use crate::{
+    create_did_web, create_offline_did, did_web, export_did_web, AppContext, Command,
+    CommandRegistration, Config, DidKind, KeyAlgorithm, Menu, Output, Progress, ScreenEvent,
+    ScreenState, VariablesConfig,
+};
+use async_trait::async_trait;
+use identity_iota::did::DID;
+use identity_iota::document::CoreDocument;
+use identity_iota::iota::{
+    IotaClientExt, IotaDID, IotaDocument, IotaIdentityClientExt, NetworkName,
+};
+use identity_iota::storage::JwkDocumentExt;
 use identity_iota::verification::MethodScope;
 use iota_sdk::types::block::output::AliasOutput;
 use std::io;
 use std::io::Write;
-use tokio::sync::watch;
+use std::path::{Path, PathBuf};
 use tokio::time::Instant;
 
 pub struct CreateDIDCommand<'a> {
     context: &'a AppContext,
 }
 
+#[async_trait]
 impl Command for CreateDIDCommand<'_> {
-    fn execute(&mut self) -> ScreenEvent {
+    async fn execute(&mut self) -> ScreenEvent {
         self.print_tile();
-        // Block on the async function using block_in_place
-        tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current().block_on(self.handle_did_creation())
-        })
-        .unwrap_or_else(|_| ScreenEvent::Cancel)
+        self.handle_did_creation()
+            .await
+            .unwrap_or_else(|e| ScreenEvent::Error(e.to_string()))
     }
 
     fn print_tile(&self) {
@@ -30,22 +37,48 @@ impl Command for CreateDIDCommand<'_> {
 }
 
 impl CreateDIDCommand<'_> {
-    pub fn new(app_context: &AppContext) -> CreateDIDCommand {
+    pub fn new(app_context: &AppContext) -> CreateDIDCommand<'_> {
         CreateDIDCommand {
             context: app_context,
         }
     }
 
-    async fn handle_did_creation(&self) -> anyhow::Result<ScreenEvent> {
-        let owner = self.get_did_owner();
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::CreateDIDWorkflow, |context, _| {
+            Box::new(CreateDIDCommand::new(context))
+        })
+        .menu_entry(
+            ScreenState::MainMenu,
+            "Create a new DID",
+            ScreenEvent::CreateDID,
+        )
+    }
 
-        let (tx, rx) = watch::channel(true);
-        // Spawn the loading animation as a background task
-        let animation_handle = tokio::spawn(Output::loading_animation(rx));
+    async fn handle_did_creation(&self) -> anyhow::Result<ScreenEvent> {
+        let owner = self.get_did_owner().await;
+        let Some(kind) = self.choose_did_kind().await else {
+            return Ok(ScreenEvent::Cancel);
+        };
+        let web_address = match kind {
+            DidKind::Web => Some(self.get_web_address().await),
+            _ => None,
+        };
+        let Some(algorithm) = Self::choose_key_algorithm(self.context, "Key type of the DID").await
+        else {
+            return Ok(ScreenEvent::Cancel);
+        };
 
         let start = Instant::now();
 
-        let (document, _fragment) = self.create_did().await?;
+        let (document, _fragment) = match &web_address {
+            Some(address) => {
+                let (document, fragment, file) =
+                    Progress::run("Creating DID", self.create_did_web(address, algorithm)).await?;
+                println!("Host {} as {}", file.display(), document.id());
+                (document, fragment)
+            }
+            None => Progress::run("Creating DID", self.create_did(kind, algorithm)).await?,
+        };
 
         Output::print_during_loading(
             format!("Time to create DID: {} s", start.elapsed().as_secs()).as_str(),
@@ -53,27 +86,24 @@ impl CreateDIDCommand<'_> {
 
         // Clear the line before printing this
         Output::print_during_loading("Saving DID to database");
-        self.context.db.save_did_document(&document, &owner)?;
-
-        // Signal the animation to stop
-        let _ = tx.send(false);
-        // Wait for the animation task to finish
-        animation_handle.await?;
+        self.save_did(&document, &owner)?;
 
         Output::print_during_loading("DID created successfully!");
-        
-        Input::wait_for_user_input("Press any key to continue...");
+
+        self.context
+            .input
+            .wait_for_user_input("Press any key to continue...")
+            .await;
 
         Ok(ScreenEvent::Success)
     }
 
-    fn get_did_owner(&self) -> String {
+    async fn get_did_owner(&self) -> String {
         loop {
             print!("Please enter a name to be linked with the DID: ");
             io::stdout().flush().unwrap();
 
-            let mut input = String::new();
-            io::stdin().read_line(&mut input).unwrap();
+            let input = self.context.input.read_line().await;
 
             let trimmed_input = input.trim();
 
@@ -87,21 +117,109 @@ impl CreateDIDCommand<'_> {
         }
     }
 
-    pub async fn create_did(&self) -> anyhow::Result<(IotaDocument, String)> {
+    /// Asks for the domain, and optional path, a `did:web` is hosted at.
+    async fn get_web_address(&self) -> String {
+        loop {
+            print!(
+                "Domain hosting the DID, with an optional path (e.g. example.com/users/alice): "
+            );
+            io::stdout().flush().unwrap();
+
+            let input = self.context.input.read_line().await;
+            match did_web(&input) {
+                Ok(_) => return input.trim().to_string(),
+                Err(e) => println!("{} Please try again.", e),
+            }
+        }
+    }
+
+    /// Asks the user which DID method to use, `None` when they went back.
+    async fn choose_did_kind(&self) -> Option<DidKind> {
+        let kinds = DidKind::all();
+        let labels = kinds.iter().map(|kind| kind.label().to_string()).collect();
+        Menu::new("DID Method", labels)
+            .message("Method of the DID")
+            .select(self.context.input.as_ref())
+            .await
+            .map(|index| kinds[index])
+    }
+
+    /// Asks the user which key type to generate, `None` when they went back.
+    pub async fn choose_key_algorithm(context: &AppContext, message: &str) -> Option<KeyAlgorithm> {
+        let algorithms = KeyAlgorithm::all();
+        let labels = algorithms
+            .iter()
+            .map(|algorithm| algorithm.label().to_string())
+            .collect();
+        Menu::new("Key Type", labels)
+            .message(message)
+            .select(context.input.as_ref())
+            .await
+            .map(|index| algorithms[index])
+    }
+
+    /// Creates a DID of `kind` with a key of `algorithm` in our Stronghold. A `did:iota` is
+    /// published, a `did:key` or `did:jwk` only needs its key.
+    pub async fn create_did(
+        &self,
+        kind: DidKind,
+        algorithm: KeyAlgorithm,
+    ) -> anyhow::Result<(CoreDocument, String)> {
+        if kind == DidKind::Web {
+            anyhow::bail!("A did:web needs the domain it is hosted at");
+        }
+        if kind.is_offline() {
+            Output::print_during_loading("Creating DID...");
+            return create_offline_did(&self.context.storage, kind, algorithm).await;
+        }
+
+        let (document, fragment) = self.publish_did(algorithm).await?;
+        Ok((document.into(), fragment))
+    }
+
+    /// Creates a `did:web` hosted at `address` and exports its `did.json` to the directory
+    /// configured for them. Returns the document, the fragment of its method and the file.
+    pub async fn create_did_web(
+        &self,
+        address: &str,
+        algorithm: KeyAlgorithm,
+    ) -> anyhow::Result<(CoreDocument, String, PathBuf)> {
+        Output::print_during_loading("Creating DID...");
+        let (document, fragment) =
+            create_did_web(&self.context.storage, address, algorithm).await?;
+
+        Output::print_during_loading("Exporting did.json...");
+        let directory = VariablesConfig::get().get_value("did_web_directory");
+        let file = export_did_web(&document, Path::new(directory))?;
+
+        Ok((document, fragment, file))
+    }
+
+    /// Stores the DID of `owner`, along with the active network when it was published.
+    /// Returns its ID in the database.
+    pub fn save_did(&self, document: &CoreDocument, owner: &str) -> anyhow::Result<i64> {
+        let id = self.context.db.save_did(document, owner)?;
+        if document.id().method() == IotaDID::METHOD {
+            self.context
+                .db
+                .set_did_network(id, &self.context.network.name)?;
+        }
+        Ok(id)
+    }
+
+    async fn publish_did(&self, algorithm: KeyAlgorithm) -> anyhow::Result<(IotaDocument, String)> {
         Output::print_during_loading("Creating DID...");
-        let (document, fragment): (IotaDocument, String) = self.create_did_document().await?;
+        let (document, fragment): (IotaDocument, String) =
+            self.create_did_document(algorithm).await?;
 
         Output::print_during_loading("Creating Alias...");
-        let alias_output: AliasOutput = self
-            .context
-            .client
-            .new_did_output(self.context.address, document, None)
+        let client = self.context.client().await?;
+        let alias_output: AliasOutput = client
+            .new_did_output(self.context.address().await?, document, None)
             .await?;
 
         Output::print_during_loading("Publishing DID...");
-        let document: IotaDocument = self
-            .context
-            .client
+        let document: IotaDocument = client
             .publish_did_output(
                 self.context.stronghold_storage.as_secret_manager(),
                 alias_output,
@@ -111,16 +229,19 @@ impl CreateDIDCommand<'_> {
         Ok((document, fragment))
     }
 
-    async fn create_did_document(&self) -> anyhow::Result<(IotaDocument, String)> {
-        let network_name: NetworkName = self.context.client.network_name().await?;
+    async fn create_did_document(
+        &self,
+        algorithm: KeyAlgorithm,
+    ) -> anyhow::Result<(IotaDocument, String)> {
+        let network_name: NetworkName = self.context.client().await?.network_name().await?;
 
         let mut document: IotaDocument = IotaDocument::new(&network_name);
 
         let fragment: String = document
             .generate_method(
                 &self.context.storage,
-                JwkMemStore::ED25519_KEY_TYPE,
-                JwsAlgorithm::EdDSA,
+                algorithm.key_type(),
+                algorithm.jws_algorithm(),
                 None,
                 MethodScope::VerificationMethod,
             )
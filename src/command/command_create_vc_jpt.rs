@@ -0,0 +1,200 @@
+use crate::{
+    utils, AppContext, Command, CommandRegistration, CreateVCNormalCommand, Did, Output, Progress,
+    ScreenEvent, ScreenState, Validity, VerifyVCCommand,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use identity_iota::core::{FromJson, Url};
+use identity_iota::credential::{
+    Credential, CredentialBuilder, DecodedJptCredential, Jpt, JwpCredentialOptions, Subject,
+};
+use identity_iota::did::DID;
+use identity_iota::document::CoreDocument;
+use identity_iota::iota::{IotaDocument, IotaIdentityClientExt};
+use identity_iota::storage::{JwkMemStore, JwpDocumentExt};
+use identity_iota::verification::MethodScope;
+use jsonprooftoken::jpa::algs::ProofAlgorithm;
+use serde_json::Value;
+
+/// Issues JSON Proof Token credentials, signed with a BBS+ key of the issuer. The holder can
+/// derive unlinkable presentations revealing only some claims from them.
+pub struct CreateVCJptCommand<'a> {
+    context: &'a AppContext,
+    create_vc: CreateVCNormalCommand<'a>,
+}
+
+#[async_trait]
+impl<'a> Command for CreateVCJptCommand<'a> {
+    async fn execute(&mut self) -> ScreenEvent {
+        self.handle_vc_jpt_creation()
+            .await
+            .unwrap_or_else(|e| ScreenEvent::Error(e.to_string()))
+    }
+
+    fn print_tile(&self) {
+        Output::clear_screen();
+        Output::print_screen_title("Create VC with BBS+ (JPT)")
+    }
+}
+
+impl<'a> CreateVCJptCommand<'a> {
+    pub fn new(context: &'a AppContext) -> Self {
+        Self {
+            context,
+            create_vc: CreateVCNormalCommand::new(context),
+        }
+    }
+
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::CreateJptVCWorkflow, |context, _| {
+            Box::new(CreateVCJptCommand::new(context))
+        })
+        .menu_entry(
+            ScreenState::CreateVCMenu,
+            "Create Verifiable Credential with BBS+ (JPT)",
+            ScreenEvent::CreateJptVC,
+        )
+    }
+
+    async fn handle_vc_jpt_creation(&self) -> Result<ScreenEvent> {
+        let Some((issuer_document, issuer, holder_document, holder)) =
+            self.create_vc.choose_dids().await?
+        else {
+            return Ok(ScreenEvent::Cancel);
+        };
+
+        let (path, template): (String, String) = self.create_vc.create_credential().await?;
+        let credential_type = Output::snake_to_camel_case(&template);
+        let json: Value = utils::read_json_file(&path)?;
+        let validity = self.create_vc.choose_validity(&template).await?;
+
+        let (issuer_document, fragment) = Progress::run(
+            "Preparing the BBS+ key",
+            self.bbs_method(&issuer, issuer_document),
+        )
+        .await?;
+
+        let (jpt, decoded_credential) = self
+            .issue_jpt_credential(
+                &issuer_document,
+                &fragment,
+                &holder_document,
+                json,
+                &credential_type,
+                &validity,
+            )
+            .await?;
+
+        let id = self.context.db.save_jpt_vc(
+            jpt.as_str(),
+            issuer.id(),
+            holder.id(),
+            &credential_type,
+        )?;
+        self.create_vc.save_validity(id, &validity)?;
+
+        utils::pretty_print_json(
+            "VC Created with BBS+ (JPT)",
+            decoded_credential.credential.to_string().as_str(),
+        );
+        self.context
+            .input
+            .wait_for_user_input("Press enter to continue")
+            .await;
+
+        Ok(ScreenEvent::Success)
+    }
+
+    /// Fragment of the BBS+ method of `issuer`. A DID has none until its first JPT credential,
+    /// so the method is then generated in our Stronghold and published.
+    ///
+    /// Returns the issuer document, updated if the method was added, along with the fragment.
+    async fn bbs_method(
+        &self,
+        issuer: &Did,
+        issuer_document: CoreDocument,
+    ) -> Result<(CoreDocument, String)> {
+        let alg = ProofAlgorithm::BLS12381_SHA256.to_string();
+        let existing = self
+            .context
+            .db
+            .get_did_methods(issuer.id())?
+            .into_iter()
+            .find(|method| method.alg() == alg && !method.removed());
+        if let Some(method) = existing {
+            return Ok((issuer_document, method.fragment().to_string()));
+        }
+
+        // Only a published DID can take a new method
+        let mut issuer_document: IotaDocument = self
+            .context
+            .client()
+            .await?
+            .resolve_did(&self.context.ledger_did(issuer)?)
+            .await?;
+        Output::print_during_loading("Generating a BBS+ method...");
+        let fragment = issuer_document
+            .generate_method_jwp(
+                &self.context.storage,
+                JwkMemStore::BLS12381G2_KEY_TYPE,
+                ProofAlgorithm::BLS12381_SHA256,
+                None,
+                MethodScope::VerificationMethod,
+            )
+            .await?;
+
+        Output::print_during_loading("Publishing DID...");
+        let issuer_document = utils::publish_did_update(
+            self.context.client().await?,
+            self.context.stronghold_storage.as_secret_manager(),
+            issuer_document,
+        )
+        .await?;
+        self.context
+            .db
+            .save_did_method(issuer.id(), &fragment, "VerificationMethod", &alg)?;
+
+        Ok((issuer_document.into(), fragment))
+    }
+
+    /// Builds a credential for `holder_document` with the claims in `json` and signs it with
+    /// the BBS+ method `issuer_fragment`, returning the JPT together with its decoded (and
+    /// already verified) form. The VC is valid within the `validity` window.
+    pub async fn issue_jpt_credential(
+        &self,
+        issuer_document: &CoreDocument,
+        issuer_fragment: &str,
+        holder_document: &CoreDocument,
+        mut json: Value,
+        credential_type: &str,
+        validity: &Validity,
+    ) -> Result<(Jpt, DecodedJptCredential)> {
+        json = utils::insert_holder_did(&mut json, holder_document.id().as_str())?;
+
+        let subject: Subject = Subject::from_json_value(json)?;
+
+        // Not marked as non-transferable: a JPT presentation is not signed by the holder
+        let credential: Credential = validity
+            .apply(
+                CredentialBuilder::default()
+                    .issuer(Url::parse(issuer_document.id().as_str())?)
+                    .type_(credential_type)
+                    .subject(subject),
+            )
+            .build()?;
+
+        let jpt: Jpt = issuer_document
+            .create_credential_jpt(
+                &credential,
+                &self.context.storage,
+                issuer_fragment,
+                &JwpCredentialOptions::default(),
+                None,
+            )
+            .await?;
+
+        let decoded_credential = VerifyVCCommand::verify_jpt(&jpt, issuer_document)?;
+
+        Ok((jpt, decoded_credential))
+    }
+}
@@ -1,29 +1,42 @@
 use crate::{
-    is_command_available, utils, AppContext, Command, Config, Did, Input, ListDIDsCommand, Output,
-    ScreenEvent, VariablesConfig, VerifyVCCommand,
+    is_command_available, revocation_service, revocation_status, status_list_status,
+    template_validity, utils, AppContext, Command, CommandRegistration, Config, Did, DidQuery,
+    ListDIDsCommand, Menu, Output, ScreenEvent, ScreenState, StatusList, Validity, VariablesConfig,
+    VerifyVCCommand, NO_EXPIRATION,
 };
+use async_trait::async_trait;
 
 use colored::*;
 use identity_iota::core::{FromJson, Url};
-use identity_iota::credential::{Credential, CredentialBuilder, Jwt, Subject};
+use identity_iota::credential::{
+    Credential, CredentialBuilder, DecodedJwtCredential, Jwt, Status, Subject,
+};
 use identity_iota::did::DID;
-use identity_iota::iota::IotaDocument;
+use identity_iota::document::CoreDocument;
 use identity_iota::storage::{JwkDocumentExt, JwsSignatureOptions};
 use serde_json::Value;
+use std::fs;
 use std::path::Path;
-use std::{fs, io};
+
+/// Where the status of an issued VC is kept, to revoke or suspend it later.
+pub struct StatusSlot {
+    /// The `credentialStatus` of the VC.
+    pub status: Status,
+    pub index: u32,
+    /// Status list the index is in, `None` for the revocation bitmap of the issuer.
+    pub status_list: Option<i64>,
+}
 
 pub struct CreateVCNormalCommand<'a> {
     context: &'a AppContext,
 }
 
+#[async_trait]
 impl Command for CreateVCNormalCommand<'_> {
-    fn execute(&mut self) -> ScreenEvent {
-        // Block on the async function using block_in_place
-        tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current().block_on(self.handle_vc_normal_creation())
-        })
-        .unwrap_or_else(|_| ScreenEvent::Cancel)
+    async fn execute(&mut self) -> ScreenEvent {
+        self.handle_vc_normal_creation()
+            .await
+            .unwrap_or_else(|e| ScreenEvent::Error(e.to_string()))
     }
 
     fn print_tile(&self) {
@@ -33,78 +46,255 @@ impl Command for CreateVCNormalCommand<'_> {
 }
 
 impl CreateVCNormalCommand<'_> {
-    pub fn new(context: &AppContext) -> CreateVCNormalCommand {
+    pub fn new(context: &AppContext) -> CreateVCNormalCommand<'_> {
         CreateVCNormalCommand { context }
     }
 
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::CreateNormalVCWorkflow, |context, _| {
+            Box::new(CreateVCNormalCommand::new(context))
+        })
+        .menu_entry(
+            ScreenState::CreateVCMenu,
+            "Create Verifiable Credential",
+            ScreenEvent::CreateNormalVC,
+        )
+    }
+
     async fn handle_vc_normal_creation(&self) -> anyhow::Result<ScreenEvent> {
-        let (issuer_document, issuer, holder_document, holder, ok) = self.choose_dids().await?;
-
-        match ok {
-            ScreenEvent::Success => {
-                self.print_information_status(
-                    &issuer_document,
-                    &issuer.name().to_string(),
-                    &holder_document,
-                    &holder.name().to_string(),
-                );
-                println!("Creating VC with theses credentials");
-                Output::cooldown().await;
-            }
-            _ => return Ok(ScreenEvent::Cancel),
-        }
+        let Some((issuer_document, issuer, holder_document, holder)) = self.choose_dids().await?
+        else {
+            return Ok(ScreenEvent::Cancel);
+        };
+
+        self.print_information_status(
+            &issuer_document,
+            &issuer.name().to_string(),
+            &holder_document,
+            &holder.name().to_string(),
+        );
+        println!("Creating VC with theses credentials");
+        Output::cooldown().await;
 
-        let (path, template): (String, String) = self.create_credential()?;
+        let (path, template): (String, String) = self.create_credential().await?;
 
         let credential_type = Output::snake_to_camel_case(&template);
 
-        let mut json: Value = utils::read_json_file(&path)?;
+        let json: Value = utils::read_json_file(&path)?;
+
+        let validity = self.choose_validity(&template).await?;
+        let slot = self.choose_status_slot(&issuer, &issuer_document).await?;
+
+        let (credential_jwt, decoded_credential) = self
+            .issue_credential(
+                &issuer_document,
+                issuer.fragment(),
+                &holder_document,
+                json,
+                &credential_type,
+                &validity,
+                slot.as_ref().map(|slot| slot.status.clone()),
+            )
+            .await?;
+
+        utils::pretty_print_json(
+            "VC Created",
+            decoded_credential.credential.to_string().as_str(),
+        );
+
+        // Save the credential to the database
+        let id = self.context.db.save_vc(
+            credential_jwt.as_str(),
+            issuer.id(),
+            holder.id(),
+            &credential_type,
+            false,
+        )?;
+        self.save_validity(id, &validity)?;
+        if let Some(slot) = &slot {
+            self.save_status_slot(id, slot)?;
+        }
+
+        Ok(ScreenEvent::Success)
+    }
 
+    /// Builds a credential for `holder_document` with the claims in `json` and signs it with the
+    /// `issuer_fragment` method, returning the JWT together with its decoded (and already
+    /// verified) form. The VC is valid within the `validity` window. With a `status`, it can be
+    /// revoked or suspended later, see [`StatusSlot`].
+    #[allow(clippy::too_many_arguments)]
+    pub async fn issue_credential(
+        &self,
+        issuer_document: &CoreDocument,
+        issuer_fragment: &str,
+        holder_document: &CoreDocument,
+        mut json: Value,
+        credential_type: &str,
+        validity: &Validity,
+        status: Option<Status>,
+    ) -> anyhow::Result<(Jwt, DecodedJwtCredential)> {
         json = utils::insert_holder_did(&mut json, holder_document.id().as_str())?;
 
         let subject: Subject = Subject::from_json_value(json)?;
 
-        let credential: Credential = CredentialBuilder::default()
-            .issuer(Url::parse(issuer_document.id().as_str())?)
-            .type_(&credential_type)
-            .non_transferable(true)
-            .subject(subject)
-            .build()?;
+        let mut builder = validity.apply(
+            CredentialBuilder::default()
+                .issuer(Url::parse(issuer_document.id().as_str())?)
+                .type_(credential_type)
+                .non_transferable(true)
+                .subject(subject),
+        );
+        if let Some(status) = status {
+            builder = builder.status(status);
+        }
+        let credential: Credential = builder.build()?;
 
         let credential_jwt: Jwt = issuer_document
             .create_credential_jwt(
                 &credential,
                 &self.context.storage,
-                utils::extract_kid(&issuer_document)?.as_str(),
+                issuer_fragment,
                 &JwsSignatureOptions::default(),
                 None,
             )
             .await?;
 
-        let _decoded_credential =
-            VerifyVCCommand::verify_normal_vc(&credential_jwt, &issuer_document)?;
+        let decoded_credential =
+            VerifyVCCommand::verify_normal_vc(&credential_jwt, issuer_document)?;
 
-        utils::pretty_print_json(
-            "VC Created",
-            _decoded_credential.credential.to_string().as_str(),
-        );
+        Ok((credential_jwt, decoded_credential))
+    }
 
-        // Save the credential to the database
-        self.context.db.save_vc(
-            &credential_jwt.as_str(),
-            issuer.id(),
-            holder.id(),
-            &credential_type,
-            false,
-        )?;
+    /// Slot of the next VC of `issuer` in its revocation bitmap, `None` when the issuer has no
+    /// bitmap to revoke it in.
+    pub fn bitmap_slot(
+        &self,
+        issuer: &Did,
+        issuer_document: &CoreDocument,
+    ) -> anyhow::Result<Option<StatusSlot>> {
+        if revocation_service(issuer_document).is_none() {
+            return Ok(None);
+        }
+        let index = self.context.db.next_status_index(issuer.id())?;
+        Ok(Some(StatusSlot {
+            status: revocation_status(issuer_document, index)?,
+            index,
+            status_list: None,
+        }))
+    }
 
-        Ok(ScreenEvent::Success)
+    /// Slot of the next VC in the status `list`.
+    pub fn status_list_slot(&self, list: &StatusList) -> anyhow::Result<StatusSlot> {
+        let index = self.context.db.next_status_list_index(list.id())?;
+        Ok(StatusSlot {
+            status: status_list_status(list.url(), list.purpose(), index)?,
+            index,
+            status_list: Some(list.id()),
+        })
+    }
+
+    /// Records where the status of the VC `vc_id` is kept.
+    pub fn save_status_slot(&self, vc_id: i64, slot: &StatusSlot) -> anyhow::Result<()> {
+        self.context
+            .db
+            .set_vc_status_index(vc_id, slot.index, slot.status_list)
+    }
+
+    /// Records when the VC `vc_id` expires, if it does.
+    pub fn save_validity(&self, vc_id: i64, validity: &Validity) -> anyhow::Result<()> {
+        match validity.expiration() {
+            Some(expires_at) => self.context.db.set_vc_expiration(vc_id, expires_at),
+            None => Ok(()),
+        }
+    }
+
+    /// Default end of the validity of the VCs issued from `template`, from the
+    /// `CREDENTIALS_VALIDITY_FILE` when it is set.
+    pub fn default_validity_end(template: &str) -> anyhow::Result<Option<String>> {
+        let file = VariablesConfig::get().get_value("credentials_validity_file");
+        if file.is_empty() {
+            return Ok(None);
+        }
+        template_validity(Path::new(file), template)
+    }
+
+    /// Asks when the VC is valid: from now on by default, until the default end of the
+    /// `template` if it has one.
+    pub(crate) async fn choose_validity(&self, template: &str) -> anyhow::Result<Validity> {
+        let default_end = Self::default_validity_end(template)?;
+        let until_prompt = match &default_end {
+            Some(end) => format!(
+                "Valid until (default {}, '{}' to never expire):",
+                end, NO_EXPIRATION
+            ),
+            None => "Valid until (empty to never expire):".to_string(),
+        };
+        println!("Dates are YYYY-MM-DD or RFC 3339, durations are like 90d, 6mo or 5y");
+
+        let input = self.context.input.as_ref();
+        loop {
+            let valid_from = input
+                .wait_for_user_input("Valid from (empty for now):")
+                .await;
+            let valid_until = match input.wait_for_user_input(&until_prompt).await {
+                end if end.is_empty() => default_end.clone(),
+                end => Some(end),
+            };
+            match Validity::parse(Some(&valid_from), valid_until.as_deref()) {
+                Ok(validity) => return Ok(validity),
+                Err(e) => println!("{}", e.to_string().red()),
+            }
+        }
+    }
+
+    /// Asks where the status of the VC is kept: in the revocation bitmap of the `issuer`, in one
+    /// of its status lists, or nowhere. `None` when the VC cannot be revoked.
+    async fn choose_status_slot(
+        &self,
+        issuer: &Did,
+        issuer_document: &CoreDocument,
+    ) -> anyhow::Result<Option<StatusSlot>> {
+        let bitmap = self.bitmap_slot(issuer, issuer_document)?;
+        let lists = self.context.db.get_status_lists(Some(issuer.id()))?;
+        if bitmap.is_none() && lists.is_empty() {
+            println!(
+                "{}",
+                "The issuer has no revocation bitmap nor status list, this VC cannot be revoked"
+                    .yellow()
+            );
+            return Ok(None);
+        }
+
+        let mut options = vec!["None, the VC cannot be revoked".to_string()];
+        if bitmap.is_some() {
+            options.push("Revocation bitmap of the issuer DID".to_string());
+        }
+        options.extend(
+            lists
+                .iter()
+                .map(|list| format!("Status list {}", list.label())),
+        );
+        let first_list = options.len() - lists.len();
+
+        match Menu::new("Credential Status", options)
+            .message("Where the VC can be revoked or suspended later")
+            .select(self.context.input.as_ref())
+            .await
+        {
+            Some(index) if index >= first_list => {
+                Ok(Some(self.status_list_slot(&lists[index - first_list])?))
+            }
+            Some(index) if index > 0 => Ok(bitmap),
+            _ => Ok(None),
+        }
     }
 
+    /// The issuer and the holder chosen by the user, `None` when the user went back.
     pub(crate) async fn choose_dids(
         &self,
-    ) -> anyhow::Result<(IotaDocument, Did, IotaDocument, Did, ScreenEvent)> {
-        let dids = self.context.db.get_stored_dids()?;
+    ) -> anyhow::Result<Option<(CoreDocument, Did, CoreDocument, Did)>> {
+        // Only our active DIDs can take part, the issuer signs and the holder will present the VC
+        let dids = self.context.db.search_dids(&DidQuery::active())?;
 
         // Check if there are any DIDs stored
         if dids.is_empty() {
@@ -115,27 +305,33 @@ impl CreateVCNormalCommand<'_> {
             return Err(anyhow::anyhow!("No DIDs found"));
         }
 
-        let (mut issuer_document, mut issuer): (IotaDocument, Did) =
-            self.get_issuer_did(&dids).await;
+        let Some((mut issuer_document, mut issuer)) = self.get_issuer_did().await? else {
+            return Ok(None);
+        };
 
-        let (mut holder_document, mut holder): (IotaDocument, Did) =
-            self.get_holder_did(&dids).await;
+        let Some((mut holder_document, mut holder)) = self.get_holder_did().await? else {
+            return Ok(None);
+        };
 
         let ok = self
             .confirm_user_selection(
-                &dids,
                 &mut issuer_document,
                 &mut issuer,
                 &mut holder_document,
                 &mut holder,
             )
             .await?;
-        Ok((issuer_document, issuer, holder_document, holder, ok))
+        Ok((ok == ScreenEvent::Success).then_some((
+            issuer_document,
+            issuer,
+            holder_document,
+            holder,
+        )))
     }
 
-    pub(crate) fn create_credential(&self) -> anyhow::Result<(String, String)> {
-        let template = self.choose_credential_template()?;
-        let editor = self.choose_editor()?;
+    pub(crate) async fn create_credential(&self) -> anyhow::Result<(String, String)> {
+        let template = self.choose_credential_template().await?;
+        let editor = self.choose_editor().await?;
         let path = self.copy_template_to_file(&template)?;
 
         utils::edit_file(editor, &path)?;
@@ -157,7 +353,7 @@ impl CreateVCNormalCommand<'_> {
         Ok(copy_file.to_str().unwrap().to_string())
     }
 
-    fn choose_credential_template(&self) -> anyhow::Result<String> {
+    async fn choose_credential_template(&self) -> anyhow::Result<String> {
         self.print_tile();
         let templates = self.get_available_templates();
 
@@ -174,7 +370,11 @@ impl CreateVCNormalCommand<'_> {
         // Prompt the user to choose a template
         println!("Please select a template:");
 
-        let input = Input::get_number_input(1, templates.len());
+        let input = self
+            .context
+            .input
+            .get_number_input(1, templates.len())
+            .await;
 
         Ok(templates[input - 1].to_string())
     }
@@ -190,7 +390,7 @@ impl CreateVCNormalCommand<'_> {
         }
     }
 
-    pub fn choose_editor(&self) -> anyhow::Result<String> {
+    pub async fn choose_editor(&self) -> anyhow::Result<String> {
         self.print_tile();
         let editors = ["nvim", "vim", "nano", "vi", "code"];
 
@@ -208,7 +408,7 @@ impl CreateVCNormalCommand<'_> {
         // Print unavailable editors to the user
         if !unavailable_editors.is_empty() {
             println!("Unavailable editors:");
-            for (_, editor) in unavailable_editors.iter().enumerate() {
+            for editor in unavailable_editors.iter() {
                 println!("- : {}", editor.red());
             }
         }
@@ -222,48 +422,52 @@ impl CreateVCNormalCommand<'_> {
         // Prompt the user to choose an editor
         println!("Please select an editor:");
 
-        let input = Input::get_number_input(1, available_editors.len());
+        let input = self
+            .context
+            .input
+            .get_number_input(1, available_editors.len())
+            .await;
 
         Ok(available_editors[input - 1].to_string())
     }
 
     async fn confirm_user_selection(
         &self,
-        dids: &Vec<Did>,
-        issuer_did: &mut IotaDocument,
+        issuer_did: &mut CoreDocument,
         issuer: &mut Did,
-        holder_did: &mut IotaDocument,
+        holder_did: &mut CoreDocument,
         holder: &mut Did,
     ) -> anyhow::Result<ScreenEvent> {
         loop {
             self.print_information_status(
-                &issuer_did,
+                issuer_did,
                 &issuer.name().to_string(),
-                &holder_did,
+                holder_did,
                 &holder.name().to_string(),
             );
             println!(
-                "{} {} {} {} {} {}",
-                "\nPress",
+                "\nPress {} or type {} to the main menu, or {}",
                 "enter to continue".green().bold(),
-                "or type",
                 "'back' to go back ".red().bold(),
-                "to the main menu, or",
                 "'issuer'/'holder' to open the selection".blue().bold()
             );
-            let mut input = String::new();
-            io::stdin().read_line(&mut input)?;
+            let input = self.context.input.read_line().await;
 
             // Trim input and make it lowercase
             let input = input.trim().to_lowercase();
 
             match input.as_str() {
-                "back" => return Err(anyhow::anyhow!("User cancelled operation")),
+                "back" => return Ok(ScreenEvent::Cancel),
+                // Going back from the selection keeps the DID chosen before
                 "issuer" => {
-                    (*issuer_did, *issuer) = self.get_issuer_did(&dids).await;
+                    if let Some(chosen) = self.get_issuer_did().await? {
+                        (*issuer_did, *issuer) = chosen;
+                    }
                 }
                 "holder" => {
-                    (*holder_did, *holder) = self.get_holder_did(&dids).await;
+                    if let Some(chosen) = self.get_holder_did().await? {
+                        (*holder_did, *holder) = chosen;
+                    }
                 }
                 "" => break Ok(ScreenEvent::Success),
                 _ => continue,
@@ -273,9 +477,9 @@ impl CreateVCNormalCommand<'_> {
 
     pub(crate) fn print_information_status(
         &self,
-        issuer_did: &IotaDocument,
+        issuer_did: &CoreDocument,
         issuer_name: &String,
-        holder_did: &IotaDocument,
+        holder_did: &CoreDocument,
         holder_name: &String,
     ) {
         self.print_tile();
@@ -283,51 +487,29 @@ impl CreateVCNormalCommand<'_> {
         println!("Holder DID: {} {}", holder_name, holder_did.id());
     }
 
-    async fn get_issuer_did(&self, dids: &Vec<Did>) -> (IotaDocument, Did) {
+    async fn get_issuer_did(&self) -> anyhow::Result<Option<(CoreDocument, Did)>> {
         self.print_tile();
-        let index = Output::display_with_pagination(
-            dids,
-            Self::choose_issuer_table,
-            VariablesConfig::get().did_table_size(),
-            true,
-            Some(Box::new(|| self.print_tile())),
-        );
-        let did: Did = self.get_did(dids, index);
-        (
-            did.resolve_to_iota_document(&self.context.resolver).await,
+        let Some(did) =
+            ListDIDsCommand::select_did(self.context, "Select the DID to use as the issuer").await
+        else {
+            return Ok(None);
+        };
+        Ok(Some((
+            did.resolve_document(&self.context.resolver).await?,
             did,
-        )
-    }
-
-    fn choose_issuer_table(dids: &Vec<Did>, first_row_index: usize) {
-        ListDIDsCommand::display_dids_table(dids, first_row_index);
-        println!("Select the DID row to use as the issuer:");
+        )))
     }
 
-    async fn get_holder_did(&self, dids: &Vec<Did>) -> (IotaDocument, Did) {
+    async fn get_holder_did(&self) -> anyhow::Result<Option<(CoreDocument, Did)>> {
         self.print_tile();
-        let index = Output::display_with_pagination(
-            dids,
-            Self::choose_holder_table,
-            VariablesConfig::get().did_table_size(),
-            true,
-            Some(Box::new(|| self.print_tile())),
-        );
-
-        let did: Did = self.get_did(dids, index);
-        (
-            did.resolve_to_iota_document(&self.context.resolver).await,
+        let Some(did) =
+            ListDIDsCommand::select_did(self.context, "Select the DID to use as the holder").await
+        else {
+            return Ok(None);
+        };
+        Ok(Some((
+            did.resolve_document(&self.context.resolver).await?,
             did,
-        )
-    }
-
-    fn choose_holder_table(dids: &Vec<Did>, first_row_index: usize) {
-        ListDIDsCommand::display_dids_table(dids, first_row_index);
-        println!("Select the DID row to use as the holder:");
-    }
-
-    fn get_did(&self, dids: &Vec<Did>, index: usize) -> Did {
-        let selected_did = dids.get(index - 1).unwrap();
-        selected_did.clone()
+        )))
     }
 }
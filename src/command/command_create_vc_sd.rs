@@ -1,13 +1,14 @@
 use crate::{
-    utils, AppContext, Command, Config, CreateVCNormalCommand, Did, Input, Output, ScreenEvent,
-    VariablesConfig,
+    utils, AppContext, Command, CommandRegistration, Config, CreateVCNormalCommand, Did, Output,
+    ScreenEvent, ScreenState, Validity, VariablesConfig,
 };
 use anyhow::Result;
+use async_trait::async_trait;
 use colored::Colorize;
 use identity_iota::core::{FromJson, Url};
 use identity_iota::credential::{Credential, CredentialBuilder, Jws, Subject};
 use identity_iota::did::DID;
-use identity_iota::iota::IotaDocument;
+use identity_iota::document::CoreDocument;
 use identity_iota::storage::{JwkDocumentExt, JwsSignatureOptions};
 use sd_jwt_payload::{Disclosure, SdJwt, SdObjectEncoder};
 use serde_json::Value;
@@ -18,17 +19,12 @@ pub struct CreateVCSDCommand<'a> {
     create_vc: CreateVCNormalCommand<'a>,
 }
 
+#[async_trait]
 impl<'a> Command for CreateVCSDCommand<'a> {
-    fn execute(&mut self) -> ScreenEvent {
-        // Block on the async function using block_in_place
-        tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current().block_on(self.handle_vc_sd_creation())
-        })
-        .unwrap_or_else(|e| {
-            println!("Error: {}", e);
-            Input::wait_for_user_input("Press enter to continue");
-            ScreenEvent::Cancel
-        })
+    async fn execute(&mut self) -> ScreenEvent {
+        self.handle_vc_sd_creation()
+            .await
+            .unwrap_or_else(|e| ScreenEvent::Error(e.to_string()))
     }
 
     fn print_tile(&self) {
@@ -44,26 +40,92 @@ impl<'a> CreateVCSDCommand<'a> {
         }
     }
 
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::CreateSDVCWorkflow, |context, _| {
+            Box::new(CreateVCSDCommand::new(context))
+        })
+        .menu_entry(
+            ScreenState::CreateVCMenu,
+            "Create Verifiable Credential with Selective Disclosure",
+            ScreenEvent::CreateSDVC,
+        )
+    }
+
     pub async fn handle_vc_sd_creation(&self) -> Result<ScreenEvent> {
-        let (issuer_document, issuer, holder_document, holder) = self.select_dids().await?;
+        let Some((issuer_document, issuer, holder_document, holder)) = self.select_dids().await?
+        else {
+            return Ok(ScreenEvent::Cancel);
+        };
 
-        let (path, template): (String, String) = self.create_vc.create_credential()?;
+        let (path, template): (String, String) = self.create_vc.create_credential().await?;
 
         let credential_type = Output::snake_to_camel_case(&template);
 
-        let mut json: Value = utils::read_json_file(&path)?;
+        let json: Value = utils::read_json_file(&path)?;
+
+        let validity = self.create_vc.choose_validity(&template).await?;
+
+        let json_paths = self.get_json_sd_paths(template, &json, &path).await;
+
+        let (sd_jwt_str, encoded_payload) = self
+            .issue_sd_credential(
+                &issuer_document,
+                issuer.fragment(),
+                &holder_document,
+                json,
+                &credential_type,
+                &validity,
+                json_paths,
+            )
+            .await?;
+
+        let id = self.context.db.save_vc(
+            &sd_jwt_str,
+            issuer.id(),
+            holder.id(),
+            &credential_type,
+            true,
+        )?;
+        self.create_vc.save_validity(id, &validity)?;
+
+        utils::pretty_print_json("VC-SD Created successfully!", &encoded_payload);
+
+        self.context
+            .input
+            .wait_for_user_input("Press enter to continue")
+            .await;
 
-        let json_paths = self.get_json_sd_paths(template, &json, &path);
+        Ok(ScreenEvent::Success)
+    }
 
+    /// Builds an SD-JWT for `holder_document`, valid within the `validity` window, concealing
+    /// every claim listed in `json_paths`. It is signed with the `issuer_fragment` method, the
+    /// current key of the issuer.
+    ///
+    /// Returns the SD-JWT (with all disclosures) and the encoded payload that was signed.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn issue_sd_credential(
+        &self,
+        issuer_document: &CoreDocument,
+        issuer_fragment: &str,
+        holder_document: &CoreDocument,
+        mut json: Value,
+        credential_type: &str,
+        validity: &Validity,
+        json_paths: Vec<String>,
+    ) -> Result<(String, String)> {
         json = utils::insert_holder_did(&mut json, holder_document.id().as_str())?;
 
         let subject: Subject = Subject::from_json_value(json)?;
 
-        let credential: Credential = CredentialBuilder::default()
-            .type_(&credential_type)
-            .issuer(Url::parse(issuer_document.id().as_str())?)
-            .non_transferable(true)
-            .subject(subject)
+        let credential: Credential = validity
+            .apply(
+                CredentialBuilder::default()
+                    .type_(credential_type)
+                    .issuer(Url::parse(issuer_document.id().as_str())?)
+                    .non_transferable(true)
+                    .subject(subject),
+            )
             .build()?;
 
         let payload = credential.serialize_jwt(None)?;
@@ -73,7 +135,7 @@ impl<'a> CreateVCSDCommand<'a> {
         let jwt: Jws = issuer_document
             .create_jws(
                 &self.context.storage,
-                utils::extract_kid(&issuer_document)?.as_str(),
+                issuer_fragment,
                 encoded_payload.as_bytes(),
                 &JwsSignatureOptions::default(),
             )
@@ -86,27 +148,15 @@ impl<'a> CreateVCSDCommand<'a> {
 
         let sd_jwt_str = SdJwt::new(jwt.into(), disclosures, None).presentation();
 
-        self.context.db.save_vc(
-            &sd_jwt_str,
-            issuer.id(),
-            holder.id(),
-            &credential_type,
-            true,
-        )?;
-
-        utils::pretty_print_json("VC-SD Created successfully!", &encoded_payload);
-
-        Input::wait_for_user_input("Press enter to continue");
-
-        Ok(ScreenEvent::Success)
+        Ok((sd_jwt_str, encoded_payload))
     }
 
     fn add_disclosures(
         &self,
-        payload: &String,
+        payload: &str,
         json_paths: Vec<String>,
     ) -> Result<(String, Vec<Disclosure>)> {
-        let mut encoder = SdObjectEncoder::new(&payload)?;
+        let mut encoder = SdObjectEncoder::new(payload)?;
 
         let disclosures: Vec<Disclosure> = json_paths
             .iter()
@@ -121,14 +171,14 @@ impl<'a> CreateVCSDCommand<'a> {
         Ok((encoded_payload, disclosures))
     }
 
-    fn get_json_sd_paths(
+    async fn get_json_sd_paths(
         &self,
         template: String,
         edited_json: &Value,
-        template_path: &String,
+        template_path: &str,
     ) -> Vec<String> {
         let path = Self::get_sd_file_path(&template);
-        let json_paths = utils::generate_json_paths(&edited_json, "/vc/credentialSubject");
+        let json_paths = utils::generate_json_paths(edited_json, "/vc/credentialSubject");
         let template_json = utils::read_json_file(template_path).unwrap();
 
         // check if the file exists
@@ -140,7 +190,7 @@ impl<'a> CreateVCSDCommand<'a> {
             utils::prepend_comment_to_file(&mut file).unwrap();
             utils::write_vec_to_file(&mut file, &json_paths).unwrap();
             println!("File created!");
-        } else if !utils::have_same_structure(&edited_json, &template_json) {
+        } else if !utils::have_same_structure(edited_json, &template_json) {
             println!("The edited JSON does not have the same structure as the template");
             println!("Creating a new file...");
             // Remove the file
@@ -151,49 +201,48 @@ impl<'a> CreateVCSDCommand<'a> {
             utils::write_vec_to_file(&mut file, &json_paths).unwrap();
             println!("File created!");
         }
-        self.edit_sd_paths_file(&path);
+        self.edit_sd_paths_file(&path).await;
         let sd_paths = self.get_sd_paths_from_file(&path);
         std::fs::remove_file(&path).unwrap();
         sd_paths
     }
 
-    fn edit_sd_paths_file(&self, path: &String) {
+    async fn edit_sd_paths_file(&self, path: &String) {
         // Ask the user if they want to edit the file (Default: No)
         println!(
-            "{} {} {} {}",
-            "You",
+            "You {} that you {}",
             "should comment the fields".bold().red(),
-            "that you",
             "don't want to selective disclose".bold().red()
         );
-        let user_input = Input::wait_for_user_input("Do you want to edit the file? (y/N)");
+        let user_input = self
+            .context
+            .input
+            .wait_for_user_input("Do you want to edit the file? (y/N)")
+            .await;
         if user_input == "y" {
-            let editor = self.create_vc.choose_editor().unwrap();
-            utils::edit_file(editor, &path).unwrap();
+            let editor = self.create_vc.choose_editor().await.unwrap();
+            utils::edit_file(editor, path).unwrap();
         }
     }
 
-    fn get_sd_paths_from_file(&self, path: &String) -> Vec<String> {
-        let json_paths = utils::read_file_ignoring_comments(&path).unwrap();
-        json_paths
+    fn get_sd_paths_from_file(&self, path: &str) -> Vec<String> {
+        utils::read_file_ignoring_comments(path).unwrap()
     }
 
-    fn get_sd_file_path(template: &String) -> String {
+    fn get_sd_file_path(template: &str) -> String {
         let path = VariablesConfig::get().get_value("credentials_sd_directory");
         // remove the extension of template
-        let template = utils::remove_file_extension(&template);
+        let template = utils::remove_file_extension(template);
         let path = format!("{}/{}.txt", path, template);
         path
     }
 
-    async fn select_dids(&self) -> Result<(IotaDocument, Did, IotaDocument, Did)> {
-        let (issuer_document, issuer, holder_document, holder, ok) =
-            self.create_vc.choose_dids().await?;
-
-        if ok != ScreenEvent::Success {
-            // Return an Error
-            return Err(anyhow::anyhow!("Error selecting DIDs"));
-        }
+    async fn select_dids(&self) -> Result<Option<(CoreDocument, Did, CoreDocument, Did)>> {
+        let Some((issuer_document, issuer, holder_document, holder)) =
+            self.create_vc.choose_dids().await?
+        else {
+            return Ok(None);
+        };
 
         self.create_vc.print_information_status(
             &issuer_document,
@@ -203,6 +252,6 @@ impl<'a> CreateVCSDCommand<'a> {
         );
         println!("Creating VC with theses credentials");
         Output::cooldown().await;
-        Ok((issuer_document, issuer, holder_document, holder))
+        Ok(Some((issuer_document, issuer, holder_document, holder)))
     }
 }
@@ -1,12 +1,13 @@
 use crate::{
-    utils, AppContext, Command, Did, Input, ListDIDsCommand, ListVCsCommand, Output, ScreenEvent,
-    VariablesConfig, Vc,
+    check_credential_status, check_validity, jwt_validation_options, utils, AppContext, Checklist,
+    Command, CommandRegistration, CompositeJwsVerifier, ContactRole, Did, DidQuery,
+    ListDIDsCommand, ListVCsCommand, Output, ScreenEvent, ScreenState, Vc, VerifyVCCommand,
 };
 use anyhow::Result;
+use async_trait::async_trait;
 use colored::Colorize;
 use crossterm::terminal::{Clear, ClearType};
 use crossterm::{cursor, ExecutableCommand};
-use identity_eddsa_verifier::EdDSAJwsVerifier;
 use identity_iota::core::{Duration as IotaDuration, Object};
 use identity_iota::core::{Timestamp, ToJson};
 use identity_iota::credential::{
@@ -15,20 +16,23 @@ use identity_iota::credential::{
     JwtPresentationValidatorUtils, KeyBindingJWTValidationOptions, SdJwtCredentialValidator,
     SubjectHolderRelationship,
 };
+use identity_iota::credential::{
+    Credential, Jpt, JptPresentationValidationOptions, JptPresentationValidator,
+    JptPresentationValidatorUtils, JwpPresentationOptions, SelectiveDisclosurePresentation,
+};
 use identity_iota::credential::{JwtCredentialValidatorUtils, Presentation};
 use identity_iota::did::{CoreDID, DID};
-use identity_iota::iota::{IotaDID, IotaDocument};
-use rand::Rng;
+use identity_iota::document::CoreDocument;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::collections::HashMap;
-use std::collections::HashSet;
 use std::io::{stdout, Write};
-use std::thread::sleep;
-use std::time::Duration;
+use tokio::time::{sleep, Duration};
 use uuid::Uuid;
 
 use identity_iota::credential::PresentationBuilder;
 use identity_iota::document::verifiable::JwsVerificationOptions;
-use identity_iota::storage::{JwkDocumentExt, JwsSignatureOptions};
+use identity_iota::storage::{JwkDocumentExt, JwpDocumentExt, JwsSignatureOptions};
 
 use identity_iota::credential::JwtCredentialValidationOptions;
 use sd_jwt_payload::{KeyBindingJwtClaims, SdJwt, SdObjectDecoder, Sha256Hasher};
@@ -39,16 +43,12 @@ pub struct CreateVPCommand<'a> {
     vc: Option<Vc>,
 }
 
+#[async_trait]
 impl Command for CreateVPCommand<'_> {
-    fn execute(&mut self) -> ScreenEvent {
-        tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current().block_on(self.handle_vp_creation())
-        })
-        .unwrap_or_else(|e| {
-            println!("Error: {}", e);
-            Input::wait_for_user_input("Press enter to continue");
-            ScreenEvent::Cancel
-        })
+    async fn execute(&mut self) -> ScreenEvent {
+        self.handle_vp_creation()
+            .await
+            .unwrap_or_else(|e| ScreenEvent::Error(e.to_string()))
     }
 
     fn print_tile(&self) {
@@ -84,7 +84,7 @@ impl Command for CreateVPCommand<'_> {
 }
 
 impl CreateVPCommand<'_> {
-    pub fn new(context: &AppContext) -> CreateVPCommand {
+    pub fn new(context: &AppContext) -> CreateVPCommand<'_> {
         CreateVPCommand {
             context,
             verifier: None,
@@ -92,44 +92,85 @@ impl CreateVPCommand<'_> {
         }
     }
 
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::CreateVPWorkflow, |context, _| {
+            Box::new(CreateVPCommand::new(context))
+        })
+        .menu_entry(
+            ScreenState::MainMenu,
+            "Create a new VP",
+            ScreenEvent::CreateVP,
+        )
+    }
+
     async fn handle_vp_creation(&mut self) -> Result<ScreenEvent> {
-        let mut vc = self.choose_vc()?;
+        let Some(mut vc) = self.choose_vc().await? else {
+            return Ok(ScreenEvent::Cancel);
+        };
         self.confirm_vc_selection(&mut vc).await;
         self.vc = Some(vc.clone());
 
-        if vc.sd() {
-            self.handle_sd_vp(&vc).await?;
+        if vc.jpt() {
+            self.handle_jpt_vp(&vc).await?;
+        } else if vc.sd() {
+            if !self.handle_sd_vp(&vc).await? {
+                return Ok(ScreenEvent::Cancel);
+            }
         } else {
             self.handle_normal_vp(&vc).await?;
         }
 
-        Input::wait_for_user_input("Press enter to continue");
+        self.context
+            .input
+            .wait_for_user_input("Press enter to continue")
+            .await;
 
         Ok(ScreenEvent::Success)
     }
 
-    async fn handle_sd_vp(&self, vc: &Vc) -> Result<()> {
-        let (verifier_document, _) = self.choose_did().await?;
+    // Returns whether the VP was created, rather than the user going back from the verifier
+    async fn handle_sd_vp(&self, vc: &Vc) -> Result<bool> {
+        let Some((verifier_document, _)) = self.choose_did().await? else {
+            return Ok(false);
+        };
 
-        let (sd_jwt, nonce) = self.create_vp_sd(&vc, &verifier_document).await?;
+        let (sd_jwt, nonce) = self.create_vp_sd(vc, &verifier_document).await?;
         self.verify_sd_jwt_presentation(&sd_jwt, &verifier_document, nonce.as_str())
             .await?;
 
-        Ok(())
+        Ok(true)
     }
 
     async fn create_vp_sd(
         &self,
         vc: &Vc,
-        verifier_document: &IotaDocument,
+        verifier_document: &CoreDocument,
     ) -> Result<(String, String)> {
         self.print_tile();
 
         let sd_jwt = SdJwt::parse(vc.vc())?;
-        let disclosures: Vec<String> = self.handle_disclosures_selection(&sd_jwt.disclosures);
-        let nonce = self.exchange_challenge();
+        let disclosures: Vec<String> = self.handle_disclosures_selection(&sd_jwt.disclosures).await;
+        let nonce = self.exchange_challenge().await;
+
+        let sd_jwt_presentation = self
+            .build_sd_presentation(vc, disclosures, &nonce, verifier_document)
+            .await?;
+
+        Ok((sd_jwt_presentation, nonce))
+    }
+
+    /// Creates the holder's SD-JWT presentation revealing only `disclosures`, bound to the
+    /// verifier through a KB-JWT carrying `nonce` and the verifier's DID as audience.
+    pub async fn build_sd_presentation(
+        &self,
+        vc: &Vc,
+        disclosures: Vec<String>,
+        nonce: &str,
+        verifier_document: &CoreDocument,
+    ) -> Result<String> {
+        let sd_jwt = SdJwt::parse(vc.vc())?;
 
-        print!("Holder is creating the KB-JWT...");
+        Output::print_step("Holder is creating the KB-JWT...");
         // Optionally, the holder can add a Key Binding JWT (KB-JWT). This is dependent on the verifier's policy.
         // Issuing the KB-JWT is done by creating the claims set and setting the header `typ` value
         // with the help of `KeyBindingJwtClaims`.
@@ -142,184 +183,210 @@ impl CreateVPCommand<'_> {
             Timestamp::now_utc().to_unix(),
         )
         .to_json()?;
-        println!("Ok!");
+        Output::print_step_ok();
 
-        print!("Holder is signing the JWT...");
+        Output::print_step("Holder is signing the JWT...");
 
         // Setting the `typ` in the header is required.
         let options = JwsSignatureOptions::new().typ(KeyBindingJwtClaims::KB_JWT_HEADER_TYP);
-        let holder_document = vc
-            .holder()
-            .resolve_to_iota_document(&self.context.resolver)
-            .await;
+        let holder_document = vc.holder().resolve_document(&self.context.resolver).await?;
         // Create the KB-JWT.
         let kb_jwt: Jws = holder_document
             .create_jws(
                 &self.context.storage,
-                &vc.holder().fragment(),
+                vc.holder().fragment(),
                 binding_claims.as_bytes(),
                 &options,
             )
             .await?;
         // Create the final SD-JWT.
         let sd_jwt_obj = SdJwt::new(sd_jwt.jwt, disclosures, Some(kb_jwt.into()));
-        println!("Ok!");
+        Output::print_step_ok();
 
-        print!("Sending presentation (as JWT) to the verifier...");
+        Output::print_step("Sending presentation (as JWT) to the verifier...");
         let sd_jwt_presentation: String = sd_jwt_obj.presentation();
-        println!("Ok!");
+        Output::print_step_ok();
 
-        Ok((sd_jwt_presentation, nonce.clone()))
+        Ok(sd_jwt_presentation)
     }
 
-    async fn verify_sd_jwt_presentation(
+    pub async fn verify_sd_jwt_presentation(
         &self,
-        sd_jwt_presentation: &String,
-        verifier_document: &IotaDocument,
+        sd_jwt_presentation: &str,
+        verifier_document: &CoreDocument,
         nonce: &str,
     ) -> Result<()> {
-        // //Print the SD-JWT-Presentation, The nonce, and the verifier's DID
-        // println!("SD-JWT-Presentation: {}", sd_jwt_presentation);
-        // println!("Nonce: {}", nonce);
-        // println!("Verifier's DID: {}", verifier_document.id());
-
-        print!("Verifier is parsing the JWT...");
-        let sd_jwt = SdJwt::parse(&sd_jwt_presentation)?;
+        Output::print_step("Verifier is parsing the JWT...");
+        let sd_jwt = SdJwt::parse(sd_jwt_presentation)?;
         let (issuer_document, holder_document) = self.get_issuer_and_holder(&sd_jwt.jwt).await?;
-        println!("Ok!");
+        Output::print_step_ok();
 
-        print!("Verifier is validating the JWT...");
+        Output::print_step("Verifier is validating the JWT...");
         let decoder = SdObjectDecoder::new_with_sha256();
         let validator =
-            SdJwtCredentialValidator::with_signature_verifier(EdDSAJwsVerifier::default(), decoder);
+            SdJwtCredentialValidator::with_signature_verifier(CompositeJwsVerifier, decoder);
         let validation = validator.validate_credential::<_, Object>(
             &sd_jwt,
             &issuer_document,
-            &JwtCredentialValidationOptions::default(),
+            &jwt_validation_options(),
             FailFast::FirstError,
         )?;
-        println!("Ok!");
+        check_validity(&validation.credential, Timestamp::now_utc())?;
+        check_credential_status(
+            &validation.credential,
+            &issuer_document,
+            VerifyVCCommand::status_list_directory(),
+        )?;
+        Output::print_step_ok();
 
-        print!("Verifier is validating the KB-JWT...");
+        Output::print_step("Verifier is validating the KB-JWT...");
         let options = KeyBindingJWTValidationOptions::new()
             .nonce(nonce)
-            .aud(&verifier_document.id().to_string());
+            .aud(verifier_document.id().to_string());
         let _kb_validation =
             validator.validate_key_binding_jwt(&sd_jwt, &holder_document, &options)?;
-        println!("Ok!");
+        Output::print_step_ok();
 
-        println!("JWT successfully validated");
+        Output::print_info("JWT successfully validated");
         utils::pretty_print_json("Decoded Credential", &validation.credential.to_string());
 
         Ok(())
     }
 
-    async fn get_issuer_and_holder(&self, jwt: &String) -> Result<(IotaDocument, IotaDocument)> {
+    async fn get_issuer_and_holder(&self, jwt: &str) -> Result<(CoreDocument, CoreDocument)> {
         let (issuer, holder) = utils::get_entities_from_jwt(jwt)?;
         let issuer_document = self
             .context
             .resolver
-            .resolve(&IotaDID::parse(&issuer)?)
+            .resolve(&CoreDID::parse(&issuer)?)
             .await?;
         let holder_document = self
             .context
             .resolver
-            .resolve(&IotaDID::parse(&holder)?)
+            .resolve(&CoreDID::parse(&holder)?)
             .await?;
 
         Ok((issuer_document, holder_document))
     }
 
-    pub fn handle_disclosures_selection(&self, disclosures: &Vec<String>) -> Vec<String> {
-        let mut selected_disclosures: HashSet<usize> = HashSet::new();
-        let mut error: String = String::new();
+    pub async fn handle_disclosures_selection(&self, disclosures: &Vec<String>) -> Vec<String> {
         let disclosures_options =
             utils::extract_disclosure_keys(disclosures).unwrap_or(disclosures.clone()); // If the disclosures are not base64 encoded, use the original disclosures
 
-        loop {
-            self.print_tile();
-            self.print_disclosures(&disclosures_options, &selected_disclosures);
+        Checklist::new("Select the disclosures to reveal", disclosures_options)
+            .select(self.context.input.as_ref())
+            .await
+            .into_iter()
+            .filter_map(|index| disclosures.get(index).cloned())
+            .collect()
+    }
 
-            if !error.is_empty() {
-                println!("{}", error.red());
-            }
+    async fn handle_jpt_vp(&self, vc: &Vc) -> Result<()> {
+        self.print_tile();
+        let claims = self.jpt_claims(vc).await?;
+        let revealed: Vec<String> = Checklist::new("Select the claims to reveal", claims.clone())
+            .select(self.context.input.as_ref())
+            .await
+            .into_iter()
+            .filter_map(|index| claims.get(index).cloned())
+            .collect();
+        let nonce = self.exchange_challenge().await;
+
+        let presentation = self.build_jpt_presentation(vc, &revealed, &nonce).await?;
+        self.verify_jpt_presentation(&presentation, &nonce).await?;
 
-            let user_input = Input::wait_for_user_input(
-                "Type the number to toggle a disclosure, or 'ok' to proceed:",
-            );
+        Ok(())
+    }
 
-            match self.handle_user_input(
-                user_input.trim(),
-                &disclosures_options,
-                &mut selected_disclosures,
-            ) {
-                Ok(_) => error.clear(),
-                Err(e) => error = e,
-            }
+    /// Paths of the subject claims of a JPT credential, see [`utils::jpt_claim_paths`].
+    pub async fn jpt_claims(&self, vc: &Vc) -> Result<Vec<String>> {
+        let issuer_document = vc.issuer().resolve_document(&self.context.resolver).await?;
+        let decoded =
+            VerifyVCCommand::verify_jpt(&Jpt::new(vc.vc().to_string()), &issuer_document)?;
 
-            if user_input.trim() == "ok" {
-                return selected_disclosures
-                    .iter()
-                    .filter_map(|&index| disclosures.get(index).cloned())
-                    .collect();
-            }
-        }
+        Self::subject_claims(&decoded.credential)
     }
 
-    fn print_disclosures<T: ToString>(
-        &self,
-        disclosures: &Vec<T>,
-        selected_disclosures: &HashSet<usize>,
-    ) {
-        println!("Available disclosures:");
-        Output::print_options_vec_generic(disclosures);
-
-        println!("\nSelected disclosures:");
-        if selected_disclosures.is_empty() {
-            println!("None");
-        } else {
-            for &index in selected_disclosures {
-                if let Some(disclosure) = disclosures.get(index) {
-                    println!("{}: {}", index + 1, disclosure.to_string());
-                }
-            }
+    fn subject_claims(credential: &Credential) -> Result<Vec<String>> {
+        let mut claims = Vec::new();
+        for subject in credential.credential_subject.iter() {
+            claims.extend(utils::jpt_claim_paths(&serde_json::to_value(subject)?, ""));
         }
+        Ok(claims)
     }
 
-    fn handle_user_input<T: ToString>(
+    /// Derives from a JPT credential a presentation revealing only the `revealed` claims and
+    /// bound to the `nonce` of the verifier. The BBS+ proof is derived anew every time, so two
+    /// presentations of the same credential cannot be linked.
+    pub async fn build_jpt_presentation(
         &self,
-        input: &str,
-        disclosures: &[T],
-        selected_disclosures: &mut HashSet<usize>,
-    ) -> Result<(), String> {
-        match input {
-            "ok" => Ok(()),
-            input => {
-                if let Ok(num) = input.parse::<usize>() {
-                    if num > 0 && num <= disclosures.len() {
-                        let index = num - 1;
-                        if selected_disclosures.contains(&index) {
-                            selected_disclosures.remove(&index);
-                        } else {
-                            selected_disclosures.insert(index);
-                        }
-                        Ok(())
-                    } else {
-                        Err(String::from(
-                            "Invalid number. Please select a valid option.",
-                        ))
-                    }
-                } else {
-                    Err(String::from(
-                        "Invalid input. Please enter a number or 'ok'.",
-                    ))
-                }
+        vc: &Vc,
+        revealed: &[String],
+        nonce: &str,
+    ) -> Result<String> {
+        Output::print_step("Holder is decoding the JPT...");
+        let issuer_document = vc.issuer().resolve_document(&self.context.resolver).await?;
+        let decoded =
+            VerifyVCCommand::verify_jpt(&Jpt::new(vc.vc().to_string()), &issuer_document)?;
+        let method_id = decoded
+            .decoded_jwp
+            .get_issuer_protected_header()
+            .kid()
+            .ok_or(anyhow::anyhow!("The JPT has no issuer method id"))?
+            .clone();
+        Output::print_step_ok();
+
+        Output::print_step("Holder is concealing the claims not to reveal...");
+        let mut presentation = SelectiveDisclosurePresentation::new(&decoded.decoded_jwp);
+        for claim in Self::subject_claims(&decoded.credential)? {
+            if !revealed.contains(&claim) {
+                presentation.conceal_in_subject(&claim)?;
             }
         }
+        Output::print_step_ok();
+
+        Output::print_step("Holder is deriving the presentation proof...");
+        let presentation_jpt: Jpt = issuer_document
+            .create_presentation_jpt(
+                &mut presentation,
+                &method_id,
+                &JwpPresentationOptions::default().nonce(nonce),
+            )
+            .await?;
+        Output::print_step_ok();
+
+        Output::print_step("Sending presentation (as JPT) to the verifier...");
+        Output::print_step_ok();
+
+        Ok(presentation_jpt.as_str().to_string())
+    }
+
+    pub async fn verify_jpt_presentation(&self, presentation: &str, nonce: &str) -> Result<()> {
+        Output::print_step("Verifier is resolving the issuer of the JPT...");
+        let presentation_jpt = Jpt::new(presentation.to_string());
+        let issuer: CoreDID =
+            JptPresentationValidatorUtils::extract_issuer_from_presented_jpt(&presentation_jpt)?;
+        let issuer_document: CoreDocument = self.context.resolver.resolve(&issuer).await?;
+        Output::print_step_ok();
+
+        Output::print_step("Verifier is validating the proof and the nonce...");
+        let decoded = JptPresentationValidator::validate::<_, Object>(
+            &presentation_jpt,
+            &issuer_document,
+            &JptPresentationValidationOptions::default().nonce(nonce),
+            FailFast::FirstError,
+        )?;
+        check_validity(&decoded.credential, Timestamp::now_utc())?;
+        Output::print_step_ok();
+
+        Output::print_info("JPT successfully validated");
+        utils::pretty_print_json("Revealed Credential", &decoded.credential.to_string());
+
+        Ok(())
     }
 
     async fn handle_normal_vp(&self, vc: &Vc) -> Result<()> {
-        let (vp_jwt, challenge) = self.create_vp_normal(&vc).await?;
+        let (vp_jwt, challenge) = self.create_vp_normal(vc).await?;
         self.verify_jwt_presentation_normal(challenge, &vp_jwt)
             .await?;
 
@@ -328,16 +395,28 @@ impl CreateVPCommand<'_> {
 
     async fn create_vp_normal(&self, vc: &Vc) -> Result<(Jwt, String)> {
         self.print_tile();
-        let expires = self.define_expiration();
-        let challenge = self.exchange_challenge();
+        let expires = self.define_expiration().await;
+        let challenge = self.exchange_challenge().await;
+
+        let presentation_jwt = self
+            .build_normal_presentation(vc, expires, &challenge)
+            .await?;
 
+        Ok((presentation_jwt, challenge))
+    }
+
+    /// Wraps the credential in a presentation signed by its holder, carrying the agreed
+    /// `challenge` as nonce and expiring at `expires`.
+    pub async fn build_normal_presentation(
+        &self,
+        vc: &Vc,
+        expires: Timestamp,
+        challenge: &str,
+    ) -> Result<Jwt> {
         let vc_jwt = Jwt::from(vc.vc().to_string());
 
-        print!("Holder is signing the VP...");
-        let holder_document = vc
-            .holder()
-            .resolve_to_iota_document(&self.context.resolver)
-            .await;
+        Output::print_step("Holder is signing the VP...");
+        let holder_document = vc.holder().resolve_document(&self.context.resolver).await?;
 
         let presentation: Presentation<Jwt> =
             PresentationBuilder::new(holder_document.id().to_url().into(), Default::default())
@@ -348,92 +427,100 @@ impl CreateVPCommand<'_> {
             .create_presentation_jwt(
                 &presentation,
                 &self.context.storage,
-                &vc.holder().fragment(),
+                vc.holder().fragment(),
                 &JwsSignatureOptions::default().nonce(challenge.to_owned()),
                 &JwtPresentationOptions::default().expiration_date(expires),
             )
             .await?;
-        println!("Ok!");
+        Output::print_step_ok();
 
-        print!("Sending presentation (as JWT) to the verifier...");
+        Output::print_step("Sending presentation (as JWT) to the verifier...");
 
-        println!("Ok!");
+        Output::print_step_ok();
 
-        Ok((presentation_jwt, challenge))
+        Ok(presentation_jwt)
     }
 
-    async fn verify_jwt_presentation_normal(
+    pub async fn verify_jwt_presentation_normal(
         &self,
         challenge: String,
         presentation_jwt: &Jwt,
     ) -> Result<()> {
         // Resolve the holder's document.
-        print!("Verifying the Holder of the VP...");
-        let holder_did: CoreDID = JwtPresentationValidatorUtils::extract_holder(&presentation_jwt)?;
-        let holder: IotaDocument = self.context.resolver.resolve(&holder_did).await?;
-        println!("Ok!");
+        Output::print_step("Verifying the Holder of the VP...");
+        let holder_did: CoreDID = JwtPresentationValidatorUtils::extract_holder(presentation_jwt)?;
+        let holder: CoreDocument = self.context.resolver.resolve(&holder_did).await?;
+        Output::print_step_ok();
 
-        print!("Verifying the VP Challenge and Expiration...");
+        Output::print_step("Verifying the VP Challenge and Expiration...");
         let presentation_verifier_options: JwsVerificationOptions =
             JwsVerificationOptions::default().nonce(challenge.to_owned());
         let presentation_validation_options = JwtPresentationValidationOptions::default()
             .presentation_verifier_options(presentation_verifier_options);
         let presentation: DecodedJwtPresentation<Jwt> =
-            JwtPresentationValidator::with_signature_verifier(EdDSAJwsVerifier::default())
-                .validate(&presentation_jwt, &holder, &presentation_validation_options)?;
-        println!("Ok!");
-
-        print!("Verifying the Issuer...");
+            JwtPresentationValidator::with_signature_verifier(CompositeJwsVerifier).validate(
+                presentation_jwt,
+                &holder,
+                &presentation_validation_options,
+            )?;
+        Output::print_step_ok();
+
+        Output::print_step("Verifying the Issuer...");
         let jwt_credentials: &Vec<Jwt> = &presentation.presentation.verifiable_credential;
         let issuers: Vec<CoreDID> = jwt_credentials
             .iter()
             .map(JwtCredentialValidatorUtils::extract_issuer_from_jwt)
             .collect::<Result<Vec<CoreDID>, _>>()?;
-        let issuers_documents: HashMap<CoreDID, IotaDocument> =
+        let issuers_documents: HashMap<CoreDID, CoreDocument> =
             self.context.resolver.resolve_multiple(&issuers).await?;
-        println!("Ok!");
+        Output::print_step_ok();
 
-        print!("Verifying the credentials and the relationship (Holder<>Subject)...");
-        let credential_validator: JwtCredentialValidator<EdDSAJwsVerifier> =
-            JwtCredentialValidator::with_signature_verifier(EdDSAJwsVerifier::default());
-        let validation_options: JwtCredentialValidationOptions =
-            JwtCredentialValidationOptions::default().subject_holder_relationship(
+        Output::print_step("Verifying the credentials and the relationship (Holder<>Subject)...");
+        let credential_validator: JwtCredentialValidator<CompositeJwsVerifier> =
+            JwtCredentialValidator::with_signature_verifier(CompositeJwsVerifier);
+        let validation_options: JwtCredentialValidationOptions = jwt_validation_options()
+            .subject_holder_relationship(
                 holder_did.to_url().into(),
                 SubjectHolderRelationship::AlwaysSubject,
             );
         for (index, jwt_vc) in jwt_credentials.iter().enumerate() {
             // SAFETY: Indexing should be fine since we extracted the DID from each credential and resolved it.
-            let issuer_document: &IotaDocument = &issuers_documents[&issuers[index]];
+            let issuer_document: &CoreDocument = &issuers_documents[&issuers[index]];
 
-            let _decoded_credential: DecodedJwtCredential<Object> = credential_validator
+            let decoded_credential: DecodedJwtCredential<Object> = credential_validator
                 .validate::<_, Object>(
                     jwt_vc,
                     issuer_document,
                     &validation_options,
                     FailFast::FirstError,
                 )?;
+            check_validity(&decoded_credential.credential, Timestamp::now_utc())?;
+            check_credential_status(
+                &decoded_credential.credential,
+                issuer_document,
+                VerifyVCCommand::status_list_directory(),
+            )?;
         }
-        println!("Ok!");
+        Output::print_step_ok();
         Ok(())
     }
 
-    fn exchange_challenge(&self) -> String {
+    async fn exchange_challenge(&self) -> String {
         self.print_tile();
         println!("Exchanging challenge with verifier and Holder...");
-        let challenge = self.generate_uuid4();
+        let challenge = self.generate_uuid4().await;
         challenge
     }
 
-    fn define_expiration(&self) -> Timestamp {
+    async fn define_expiration(&self) -> Timestamp {
         //Ask for the expiration time
         println!("Please enter the expiration time in minutes:");
-        let expiration_time = Input::get_number_input(0, 60);
+        let expiration_time = self.context.input.get_number_input(0, 60).await;
         let expiration = Timestamp::now_utc()
             .checked_add(IotaDuration::minutes(expiration_time as u32))
             .unwrap();
         println!(
-            "{} {} {}",
-            "Verifier and Hold have agreed upon",
+            "Verifier and Hold have agreed upon {} {}",
             expiration_time.to_string().green(),
             "minutes expiration".green()
         );
@@ -441,17 +528,18 @@ impl CreateVPCommand<'_> {
         expiration
     }
 
-    fn generate_uuid4(&self) -> String {
+    async fn generate_uuid4(&self) -> String {
         let uuid = Uuid::new_v4().to_string();
-        let mut rng = rand::thread_rng();
+        // Unlike the thread one, this generator can be kept across the pauses of the animation
+        let mut rng = StdRng::from_entropy();
         let mut stdout = stdout();
 
         println!("UUID:\n");
         for i in 0..uuid.len() {
-            for _ in 0..rand::thread_rng().gen_range(1..=10) {
+            for _ in 0..rng.gen_range(1..=10) {
                 let mut display_string: Vec<char> = uuid.chars().collect();
-                for j in i + 1..uuid.len() {
-                    display_string[j] = rng.gen_range(b'a'..=b'z') as char;
+                for c in display_string.iter_mut().skip(i + 1) {
+                    *c = rng.gen_range(b'a'..=b'z') as char;
                 }
                 // Clear the last 2 lines
 
@@ -460,7 +548,7 @@ impl CreateVPCommand<'_> {
 
                 println!("{}", display_string.iter().collect::<String>());
                 stdout.flush().unwrap();
-                sleep(Duration::from_millis(40));
+                sleep(Duration::from_millis(40)).await;
             }
         }
 
@@ -495,21 +583,21 @@ impl CreateVPCommand<'_> {
 
     async fn confirm_vc_selection(&self, vc: &mut Vc) {
         loop {
-            self.display_vc_selection(&vc);
+            self.display_vc_selection(vc);
             println!(
-                "{} {} {} {} ",
-                "\nPress",
+                "\nPress {} or type {} ",
                 "enter to continue".green().bold(),
-                "or type",
                 "'back' to open the selection ".red().bold()
             );
-            let mut input = String::new();
-            std::io::stdin().read_line(&mut input).unwrap();
+            let input = self.context.input.read_line().await;
 
             match input.trim() {
                 "" => return,
+                // Going back from the selection keeps the VC chosen before
                 "back" => {
-                    *vc = self.choose_vc().unwrap();
+                    if let Some(chosen) = self.choose_vc().await.unwrap() {
+                        *vc = chosen;
+                    }
                 }
                 _ => continue,
             }
@@ -519,32 +607,37 @@ impl CreateVPCommand<'_> {
     async fn _confirm_verifier_selection(
         &self,
         verifier_did: &mut Did,
-        verifier_document: &mut IotaDocument,
+        verifier_document: &mut CoreDocument,
     ) {
         loop {
-            self._display_verifier_selection(&verifier_did);
+            self._display_verifier_selection(verifier_did);
             println!(
-                "{} {} {} {} ",
-                "\nPress",
+                "\nPress {} or type {} ",
                 "enter to continue".green().bold(),
-                "or type",
                 "'back' to open the selection ".red().bold()
             );
-            let mut input = String::new();
-            std::io::stdin().read_line(&mut input).unwrap();
+            let input = self.context.input.read_line().await;
             match input.trim() {
                 "" => return,
                 "back" => {
-                    (*verifier_document, *verifier_did) = self.choose_did().await.unwrap();
+                    if let Some(chosen) = self.choose_did().await.unwrap() {
+                        (*verifier_document, *verifier_did) = chosen;
+                    }
                 }
                 _ => continue,
             }
         }
     }
 
-    async fn choose_did(&self) -> Result<(IotaDocument, Did)> {
+    /// `None` when the user went back.
+    async fn choose_did(&self) -> Result<Option<(CoreDocument, Did)>> {
         self.print_tile();
-        let dids = self.context.db.get_stored_dids().unwrap_or_default();
+        // The verifier can also be one of the contacts
+        let dids = self
+            .context
+            .db
+            .search_dids(&DidQuery::with_role(ContactRole::Verifier))
+            .unwrap_or_default();
 
         // Check if there are any DIDs stored
         if dids.is_empty() {
@@ -555,31 +648,28 @@ impl CreateVPCommand<'_> {
             return Err(anyhow::anyhow!("No DIDs found"));
         }
 
-        Ok(self.get_verifier_did(&dids).await)
+        self.get_verifier_did().await
     }
 
-    async fn get_verifier_did(&self, dids: &Vec<Did>) -> (IotaDocument, Did) {
+    async fn get_verifier_did(&self) -> Result<Option<(CoreDocument, Did)>> {
         self.print_tile();
-        let index = Output::display_with_pagination(
-            dids,
-            Self::choose_verifier_table,
-            VariablesConfig::get().did_table_size(),
-            true,
-            Some(Box::new(|| self.print_tile())),
-        );
-        let did: Did = dids.get(index - 1).unwrap().clone();
-        (
-            did.resolve_to_iota_document(&self.context.resolver).await,
-            did,
+        let Some(did) = ListDIDsCommand::select_did_among(
+            self.context,
+            "Select the DID to use as the verifier",
+            DidQuery::with_role(ContactRole::Verifier),
         )
+        .await
+        else {
+            return Ok(None);
+        };
+        Ok(Some((
+            did.resolve_document(&self.context.resolver).await?,
+            did,
+        )))
     }
 
-    fn choose_verifier_table(dids: &Vec<Did>, first_row_index: usize) {
-        ListDIDsCommand::display_dids_table(dids, first_row_index);
-        println!("Select the DID row to use as the verifier:");
-    }
-
-    fn choose_vc(&self) -> Result<Vc> {
+    /// `None` when the user went back.
+    async fn choose_vc(&self) -> Result<Option<Vc>> {
         self.print_tile();
         let vcs: Vec<Vc> = self.context.db.get_stored_vcs().unwrap_or_default();
         if vcs.is_empty() {
@@ -587,26 +677,6 @@ impl CreateVPCommand<'_> {
 
             return Err(anyhow::anyhow!("No VCs found"));
         }
-        let vc = self.get_vc(&vcs).unwrap_or_default();
-        Ok(vc)
-    }
-
-    fn get_vc(&self, vcs: &Vec<Vc>) -> Result<Vc> {
-        let index = Output::display_with_pagination(
-            &vcs,
-            Self::choose_vc_to_vp_table,
-            VariablesConfig::get().vc_table_size(),
-            true,
-            Some(Box::new(|| self.print_tile())),
-        );
-        let vc = vcs
-            .get(index - 1)
-            .map_or(Err(anyhow::anyhow!("Invalid index")), |vc| Ok(vc.clone()));
-        vc
-    }
-
-    fn choose_vc_to_vp_table(vcs: &Vec<Vc>, first_row_index: usize) {
-        ListVCsCommand::display_vcs_table(vcs, first_row_index);
-        println!("Choose a VC to create the VP by entering the row number:");
+        Ok(ListVCsCommand::select_vc(self.context, "Choose a VC to create the VP").await)
     }
 }
@@ -0,0 +1,90 @@
+use crate::{
+    utils, AppContext, Command, CommandRegistration, Did, DidQuery, DidStatus, ListDIDsCommand,
+    Modal, Output, Progress, ScreenEvent, ScreenState,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Deactivates a DID on the ledger, so verifiers reject the credentials it issued.
+pub struct DeactivateDIDCommand<'a> {
+    context: &'a AppContext,
+}
+
+#[async_trait]
+impl Command for DeactivateDIDCommand<'_> {
+    async fn execute(&mut self) -> ScreenEvent {
+        self.print_tile();
+        self.handle_deactivate_did()
+            .await
+            .unwrap_or_else(|e| ScreenEvent::Error(e.to_string()))
+    }
+
+    fn print_tile(&self) {
+        Output::clear_screen();
+        Output::print_screen_title("Deactivate DID")
+    }
+}
+
+impl DeactivateDIDCommand<'_> {
+    pub fn new(context: &AppContext) -> DeactivateDIDCommand<'_> {
+        DeactivateDIDCommand { context }
+    }
+
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::DeactivateDIDWorkflow, |context, _| {
+            Box::new(DeactivateDIDCommand::new(context))
+        })
+        .menu_entry(
+            ScreenState::UpdateDIDMenu,
+            "Deactivate a DID",
+            ScreenEvent::DeactivateDID,
+        )
+    }
+
+    async fn handle_deactivate_did(&self) -> Result<ScreenEvent> {
+        if self.context.db.search_dids(&DidQuery::active())?.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No active DIDs found. Please create a DID first."
+            ));
+        }
+        let Some(did) =
+            ListDIDsCommand::select_did(self.context, "Choose a DID to deactivate").await
+        else {
+            return Ok(ScreenEvent::Cancel);
+        };
+
+        let message = format!(
+            "Deactivate {}?\nVerifiers will reject the credentials it issued.",
+            did.name()
+        );
+        if !Modal::new("Deactivate DID", &message)
+            .confirm(self.context.input.as_ref())
+            .await
+        {
+            return Ok(ScreenEvent::Cancel);
+        }
+
+        Progress::run("Deactivating DID", self.deactivate_did(&did)).await?;
+        self.context
+            .db
+            .set_did_status(did.id(), DidStatus::Deactivated)?;
+        Output::print_during_loading(&format!("{} deactivated", did.name()));
+
+        self.context
+            .input
+            .wait_for_user_input("Press any key to continue...")
+            .await;
+        Ok(ScreenEvent::Success)
+    }
+
+    async fn deactivate_did(&self, did: &Did) -> Result<()> {
+        Output::print_during_loading("Publishing the deactivation...");
+        utils::publish_did_deactivation(
+            self.context.client().await?,
+            self.context.stronghold_storage.as_secret_manager(),
+            &self.context.ledger_did(did)?,
+        )
+        .await?;
+        Ok(())
+    }
+}
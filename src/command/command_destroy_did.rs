@@ -0,0 +1,102 @@
+use crate::{
+    AppContext, Command, CommandRegistration, Did, DidQuery, DidStatus, ListDIDsCommand, Modal,
+    Output, Progress, ScreenEvent, ScreenState,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use identity_iota::iota::IotaClientExt;
+
+/// Destroys the Alias Output of a DID, returning its storage deposit to our address.
+pub struct DestroyDIDCommand<'a> {
+    context: &'a AppContext,
+}
+
+#[async_trait]
+impl Command for DestroyDIDCommand<'_> {
+    async fn execute(&mut self) -> ScreenEvent {
+        self.print_tile();
+        self.handle_destroy_did()
+            .await
+            .unwrap_or_else(|e| ScreenEvent::Error(e.to_string()))
+    }
+
+    fn print_tile(&self) {
+        Output::clear_screen();
+        Output::print_screen_title("Destroy DID")
+    }
+}
+
+impl DestroyDIDCommand<'_> {
+    pub fn new(context: &AppContext) -> DestroyDIDCommand<'_> {
+        DestroyDIDCommand { context }
+    }
+
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::DestroyDIDWorkflow, |context, _| {
+            Box::new(DestroyDIDCommand::new(context))
+        })
+        .menu_entry(
+            ScreenState::UpdateDIDMenu,
+            "Destroy a DID",
+            ScreenEvent::DestroyDID,
+        )
+    }
+
+    async fn handle_destroy_did(&self) -> Result<ScreenEvent> {
+        if self.context.db.search_dids(&DidQuery::owned())?.is_empty() {
+            return Err(anyhow::anyhow!("No DIDs found. Please create a DID first."));
+        }
+        // A deactivated DID can still be destroyed, to get its storage deposit back
+        let Some(did) = ListDIDsCommand::select_did_among(
+            self.context,
+            "Choose a DID to destroy",
+            DidQuery::owned(),
+        )
+        .await
+        else {
+            return Ok(ScreenEvent::Cancel);
+        };
+        if did.status() == DidStatus::Destroyed {
+            return Err(anyhow::anyhow!("{} is already destroyed", did.name()));
+        }
+
+        let message = format!(
+            "Destroy {}?\nIt can no longer be resolved and its storage deposit\n\
+            returns to our address. This cannot be undone.",
+            did.name()
+        );
+        if !Modal::new("Destroy DID", &message)
+            .confirm(self.context.input.as_ref())
+            .await
+        {
+            return Ok(ScreenEvent::Cancel);
+        }
+
+        Progress::run("Destroying DID", self.destroy_did(&did)).await?;
+        // The row stays, so the credentials issued by the DID still show who issued them
+        self.context
+            .db
+            .set_did_status(did.id(), DidStatus::Destroyed)?;
+        Output::print_during_loading(&format!("{} destroyed", did.name()));
+
+        self.context
+            .input
+            .wait_for_user_input("Press any key to continue...")
+            .await;
+        Ok(ScreenEvent::Success)
+    }
+
+    async fn destroy_did(&self, did: &Did) -> Result<()> {
+        Output::print_during_loading("Destroying the Alias Output...");
+        self.context
+            .client()
+            .await?
+            .delete_did_output(
+                self.context.stronghold_storage.as_secret_manager(),
+                self.context.address().await?,
+                &self.context.ledger_did(did)?,
+            )
+            .await?;
+        Ok(())
+    }
+}
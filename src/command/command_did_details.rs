@@ -0,0 +1,240 @@
+use crate::{
+    method_alg, AppContext, Command, CommandRegistration, DetailSection, DetailsView, Did,
+    DidQuery, DidStatus, ListDIDsCommand, Output, Progress, ScreenEvent, ScreenState,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use identity_iota::did::DIDUrl;
+use identity_iota::document::CoreDocument;
+use identity_iota::iota::{IotaDocument, IotaIdentityClientExt};
+use identity_iota::storage::{JwkStorage, KeyIdStorage, MethodDigest};
+use identity_iota::verification::{MethodRef, VerificationMethod};
+use iota_sdk::types::block::output::AliasOutput;
+
+/// Shows a stored DID as it is published on the ledger, or as derived from the DID itself for
+/// the offline methods.
+pub struct DIDDetailsCommand<'a> {
+    context: &'a AppContext,
+}
+
+#[async_trait]
+impl Command for DIDDetailsCommand<'_> {
+    async fn execute(&mut self) -> ScreenEvent {
+        self.print_tile();
+        self.handle_did_details()
+            .await
+            .unwrap_or_else(|e| ScreenEvent::Error(e.to_string()))
+    }
+
+    fn print_tile(&self) {
+        Output::clear_screen();
+        Output::print_screen_title("DID Details")
+    }
+}
+
+impl DIDDetailsCommand<'_> {
+    pub fn new(context: &AppContext) -> DIDDetailsCommand<'_> {
+        DIDDetailsCommand { context }
+    }
+
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::DIDDetailsWorkflow, |context, _| {
+            Box::new(DIDDetailsCommand::new(context))
+        })
+        .menu_entry(
+            ScreenState::ListItemsMenu,
+            "DID details",
+            ScreenEvent::ShowDIDDetails,
+        )
+    }
+
+    async fn handle_did_details(&self) -> Result<ScreenEvent> {
+        if self.context.db.get_stored_dids()?.is_empty() {
+            return Err(anyhow::anyhow!("No DIDs found. Please create a DID first."));
+        }
+
+        let Some(did) = ListDIDsCommand::select_did_among(
+            self.context,
+            "Choose a DID to show",
+            DidQuery::default(),
+        )
+        .await
+        else {
+            return Ok(ScreenEvent::Cancel);
+        };
+        if did.status() == DidStatus::Destroyed {
+            return Err(anyhow::anyhow!(
+                "{} was destroyed, it can no longer be resolved",
+                did.name()
+            ));
+        }
+        let sections = Progress::run("Resolving DID", self.load_details(&did)).await?;
+
+        let title = format!("DID Details: {}", did.name());
+        DetailsView::new(&title, sections)
+            .show(self.context.input.as_ref())
+            .await;
+        Ok(ScreenEvent::Success)
+    }
+
+    async fn load_details(&self, did: &Did) -> Result<Vec<DetailSection>> {
+        Output::print_during_loading("Resolving the DID document");
+        // The ledger details are only read from the nodes of the active network
+        let Ok(iota_did) = self.context.ledger_did(did) else {
+            let document = did.resolve_document(&self.context.resolver).await?;
+            return Ok(vec![
+                Self::document_section(did, &document),
+                self.methods_section(&document).await,
+                Self::relationships_section(&document),
+                Self::services_section(&document),
+            ]);
+        };
+
+        let client = self.context.client().await?;
+        let document: IotaDocument = client.resolve_did(&iota_did).await?;
+
+        Output::print_during_loading("Fetching the Alias Output");
+        let alias_output: AliasOutput = client.resolve_did_output(&iota_did).await?;
+
+        let core = document.core_document();
+        Ok(vec![
+            Self::document_section(did, core),
+            Self::ledger_section(&document, &alias_output),
+            self.methods_section(core).await,
+            Self::relationships_section(core),
+            Self::services_section(core),
+        ])
+    }
+
+    fn document_section(did: &Did, document: &CoreDocument) -> DetailSection {
+        let controllers: Vec<String> = document
+            .controller()
+            .into_iter()
+            .flat_map(|controllers| controllers.iter())
+            .map(|controller| controller.to_string())
+            .collect();
+
+        DetailSection::new("Document")
+            .field("Name", did.name())
+            .field("DID", document.id())
+            .field("Method", format!("did:{}", did.method()))
+            .field("Network", did.network().unwrap_or("None"))
+            .field("Owner", ListDIDsCommand::owner(did))
+            .field("Status", did.status())
+            .field("Stored at", did.created_at())
+            .field("Controllers", Self::join_or_none(controllers))
+    }
+
+    fn ledger_section(document: &IotaDocument, alias_output: &AliasOutput) -> DetailSection {
+        let metadata = &document.metadata;
+
+        DetailSection::new("Ledger")
+            .field("Created", Self::or_unknown(metadata.created.as_ref()))
+            .field("Updated", Self::or_unknown(metadata.updated.as_ref()))
+            .field("Deactivated", metadata.deactivated.unwrap_or(false))
+            .field(
+                "State controller",
+                Self::or_unknown(metadata.state_controller_address.as_ref()),
+            )
+            .field(
+                "Governor",
+                Self::or_unknown(metadata.governor_address.as_ref()),
+            )
+            .field("Alias ID", alias_output.alias_id())
+            .field("State index", alias_output.state_index())
+            .field("Storage deposit", alias_output.amount())
+    }
+
+    async fn methods_section(&self, document: &CoreDocument) -> DetailSection {
+        let mut section = DetailSection::new("Verification methods");
+        for method in document.methods(None) {
+            let key = if self.has_key(method).await {
+                "key in Stronghold"
+            } else {
+                "no key in Stronghold"
+            };
+            section = section.field(
+                &Self::fragment(method.id()),
+                format!("{} {} ({})", method.type_(), method_alg(method), key),
+            );
+        }
+        section
+    }
+
+    fn relationships_section(document: &CoreDocument) -> DetailSection {
+        let relationships = [
+            ("Authentication", document.authentication()),
+            ("Assertion method", document.assertion_method()),
+            ("Key agreement", document.key_agreement()),
+            ("Capability delegation", document.capability_delegation()),
+            ("Capability invocation", document.capability_invocation()),
+        ];
+
+        let mut section = DetailSection::new("Verification relationships");
+        for (name, methods) in relationships {
+            let fragments = methods
+                .iter()
+                .map(|method: &MethodRef| Self::fragment(method.id()))
+                .collect();
+            section = section.field(name, Self::join_or_none(fragments));
+        }
+        section
+    }
+
+    fn services_section(document: &CoreDocument) -> DetailSection {
+        let mut section = DetailSection::new("Services");
+        for service in document.service().iter() {
+            let types = serde_json::to_string(service.type_()).unwrap_or_default();
+            let endpoint = serde_json::to_string(service.service_endpoint()).unwrap_or_default();
+            section = section.field(
+                &Self::fragment(service.id()),
+                format!("{} {}", types, endpoint),
+            );
+        }
+        section
+    }
+
+    /// Whether the private key of `method` is in our Stronghold.
+    async fn has_key(&self, method: &VerificationMethod) -> bool {
+        let Ok(digest) = MethodDigest::new(method) else {
+            return false;
+        };
+        match self
+            .context
+            .storage
+            .key_id_storage()
+            .get_key_id(&digest)
+            .await
+        {
+            Ok(key_id) => self
+                .context
+                .storage
+                .key_storage()
+                .exists(&key_id)
+                .await
+                .unwrap_or(false),
+            Err(_) => false,
+        }
+    }
+
+    fn fragment(url: &DIDUrl) -> String {
+        match url.fragment() {
+            Some(fragment) => format!("#{}", fragment),
+            None => url.did().to_string(),
+        }
+    }
+
+    fn or_unknown(value: Option<impl ToString>) -> String {
+        value
+            .map(|value| value.to_string())
+            .unwrap_or_else(|| "Unknown".to_string())
+    }
+
+    fn join_or_none(values: Vec<String>) -> String {
+        if values.is_empty() {
+            "None".to_string()
+        } else {
+            values.join(", ")
+        }
+    }
+}
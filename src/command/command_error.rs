@@ -0,0 +1,55 @@
+use crate::{AppContext, Command, CommandRegistration, Menu, Output, ScreenEvent, ScreenState};
+use async_trait::async_trait;
+use colored::Colorize;
+
+/// Shows the failure of the previous screen and lets the user run it again or go back.
+pub struct ErrorCommand<'a> {
+    context: &'a AppContext,
+    message: String,
+    options: Vec<(String, ScreenEvent)>,
+}
+
+#[async_trait]
+impl Command for ErrorCommand<'_> {
+    async fn execute(&mut self) -> ScreenEvent {
+        self.print_tile();
+        println!("{} {}\n", "Error:".red().bold(), self.message);
+
+        let labels = self
+            .options
+            .iter()
+            .map(|(label, _)| label.clone())
+            .collect();
+        let menu = Menu::new("Error", labels).message(&format!("Error: {}", self.message));
+        match menu.select(self.context.input.as_ref()).await {
+            Some(index) => self.options[index].1.clone(),
+            None => ScreenEvent::Cancel,
+        }
+    }
+
+    fn print_tile(&self) {
+        Output::clear_screen();
+        Output::print_screen_title("Error")
+    }
+}
+
+impl ErrorCommand<'_> {
+    pub fn new(context: &AppContext, message: String) -> ErrorCommand<'_> {
+        ErrorCommand {
+            context,
+            message,
+            options: vec![
+                ("Retry".to_string(), ScreenEvent::Retry),
+                ("Back".to_string(), ScreenEvent::Cancel),
+            ],
+        }
+    }
+
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::Error, |context, fsm| {
+            let message = fsm.last_error().unwrap_or("Unknown error").to_string();
+            Box::new(ErrorCommand::new(context, message))
+        })
+        .title("Error")
+    }
+}
@@ -1,16 +1,18 @@
-use crate::{Command, Output, ScreenEvent};
-use std::io;
-use std::io::stdin;
-use termion::event::Key;
-use termion::input::TermRead;
-use termion::raw::IntoRawMode;
+use crate::{AppContext, Command, CommandRegistration, Modal, Output, ScreenEvent, ScreenState};
+use async_trait::async_trait;
 
-pub struct ExitAppCommand;
+const EXIT_MESSAGE: &str = "It is a shame that we have to part ways. Goodbye!\n\
+                            Press any key to exit (ESC to cancel)";
 
-impl Command for ExitAppCommand {
-    fn execute(&mut self) -> ScreenEvent {
+pub struct ExitAppCommand<'a> {
+    context: &'a AppContext,
+}
+
+#[async_trait]
+impl Command for ExitAppCommand<'_> {
+    async fn execute(&mut self) -> ScreenEvent {
         self.print_tile();
-        ExitAppCommand::show_exit_message()
+        self.show_exit_message().await
     }
 
     fn print_tile(&self) {
@@ -19,30 +21,26 @@ impl Command for ExitAppCommand {
     }
 }
 
-impl ExitAppCommand {
-    fn show_exit_message() -> ScreenEvent {
-        println!("It is a shame that we have to part ways. Goodbye!");
-        println!("Press any key to exit (ESC to cancel):");
-
-        let stdin = stdin();
-        let mut keys = stdin.keys();
-
-        // Enable raw mode
-        let _stdout = io::stdout().into_raw_mode().unwrap();
-
-        while let Some(Ok(key)) = keys.next() {
-            return match key {
-                Key::Esc => {
-                    ScreenEvent::Cancel // Cancel exit if ESC is pressed
-                }
-                _ => {
-                    println!("Exiting...");
-                    ScreenEvent::Exit // Exit if any other key is pressed
-                }
-            };
-        }
+impl ExitAppCommand<'_> {
+    pub fn new(context: &AppContext) -> ExitAppCommand<'_> {
+        ExitAppCommand { context }
+    }
 
-        // Default case
-        ScreenEvent::Exit
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::ExitAppWorkflow, |context, _| {
+            Box::new(ExitAppCommand::new(context))
+        })
+        .menu_entry(ScreenState::MainMenu, "Exit", ScreenEvent::Cancel)
+    }
+
+    async fn show_exit_message(&self) -> ScreenEvent {
+        let modal = Modal::new("Exit App", EXIT_MESSAGE);
+
+        if modal.acknowledge(self.context.input.as_ref()).await {
+            println!("Exiting...");
+            ScreenEvent::Exit // Exit if any other key is pressed
+        } else {
+            ScreenEvent::Cancel // Cancel exit if ESC is pressed
+        }
     }
 }
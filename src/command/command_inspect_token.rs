@@ -0,0 +1,179 @@
+use crate::{
+    inspect_token, AppContext, Command, CommandRegistration, DetailSection, DetailsView,
+    InspectedToken, ListVCsCommand, Menu, Output, ScreenEvent, ScreenState, TokenKind,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::Value;
+
+/// Decodes a stored VC or a pasted token: JWT, SD-JWT or SD-JWT with a key-binding JWT.
+pub struct InspectTokenCommand<'a> {
+    context: &'a AppContext,
+}
+
+#[async_trait]
+impl Command for InspectTokenCommand<'_> {
+    async fn execute(&mut self) -> ScreenEvent {
+        self.print_tile();
+        self.handle_inspect_token()
+            .await
+            .unwrap_or_else(|e| ScreenEvent::Error(e.to_string()))
+    }
+
+    fn print_tile(&self) {
+        Output::clear_screen();
+        Output::print_screen_title("Inspect a Token")
+    }
+}
+
+impl InspectTokenCommand<'_> {
+    pub fn new(context: &AppContext) -> InspectTokenCommand<'_> {
+        InspectTokenCommand { context }
+    }
+
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::InspectTokenWorkflow, |context, _| {
+            Box::new(InspectTokenCommand::new(context))
+        })
+        .menu_entry(
+            ScreenState::MainMenu,
+            "Inspect a token",
+            ScreenEvent::InspectToken,
+        )
+    }
+
+    async fn handle_inspect_token(&self) -> Result<ScreenEvent> {
+        let input = self.context.input.as_ref();
+        let options = vec!["A stored VC".to_string(), "A pasted token".to_string()];
+        let token = match Menu::new("Inspect a Token", options)
+            .message("Which token do you want to inspect?")
+            .select(input)
+            .await
+        {
+            Some(0) => match self.choose_vc().await? {
+                Some(vc) => vc,
+                None => return Ok(ScreenEvent::Cancel),
+            },
+            Some(_) => input.wait_for_user_input("Paste the token:").await,
+            None => return Ok(ScreenEvent::Cancel),
+        };
+
+        // The key-binding JWT follows the last `~` of an SD-JWT presentation
+        let (nonce, aud) = match token.trim().rsplit_once('~') {
+            Some((_, kb_jwt)) if !kb_jwt.is_empty() => (
+                input
+                    .wait_for_user_input("Expected nonce (empty to skip):")
+                    .await,
+                input
+                    .wait_for_user_input("Expected audience (empty to skip):")
+                    .await,
+            ),
+            _ => (String::new(), String::new()),
+        };
+        let inspected = inspect_token(
+            &token,
+            (!nonce.is_empty()).then_some(nonce.as_str()),
+            (!aud.is_empty()).then_some(aud.as_str()),
+        )?;
+        DetailsView::new("Inspect a Token", Self::sections(&inspected))
+            .show(input)
+            .await;
+        Ok(ScreenEvent::Success)
+    }
+
+    async fn choose_vc(&self) -> Result<Option<String>> {
+        if self.context.db.get_stored_vcs()?.is_empty() {
+            return Err(anyhow::anyhow!("No VCs found. Please create a VC first."));
+        }
+        let vc = ListVCsCommand::select_vc(self.context, "Choose a VC to inspect").await;
+        Ok(vc.map(|vc| vc.vc().to_string()))
+    }
+
+    fn sections(inspected: &InspectedToken) -> Vec<DetailSection> {
+        let kind = match inspected.kind {
+            TokenKind::Jwt => "JWT",
+            TokenKind::SdJwt => "SD-JWT",
+            TokenKind::SdJwtWithKeyBinding => "SD-JWT with key-binding JWT",
+        };
+        let mut sections = vec![
+            DetailSection::new("Token").field("Kind", kind),
+            Self::claims_section("Header", &inspected.header),
+            Self::claims_section("Payload", &inspected.payload),
+        ];
+        if inspected.kind == TokenKind::Jwt {
+            return sections;
+        }
+
+        let mut disclosures = DetailSection::new("Disclosures");
+        for disclosure in &inspected.disclosures {
+            let locations = if disclosure.locations.is_empty() {
+                "not referenced by any digest".to_string()
+            } else {
+                disclosure.locations.join(", ")
+            };
+            disclosures = disclosures.field(
+                disclosure.key.as_deref().unwrap_or("[array element]"),
+                format!(
+                    "{} · salt {} · digest {} · in {}",
+                    Self::display_value(&disclosure.value),
+                    disclosure.salt,
+                    disclosure.digest,
+                    locations
+                ),
+            );
+        }
+        sections.push(disclosures);
+
+        let mut undisclosed = DetailSection::new("Digests without a disclosure");
+        for digest in &inspected.undisclosed_digests {
+            undisclosed = undisclosed.field("Digest", digest);
+        }
+        sections.push(undisclosed);
+
+        if let Some(key_binding) = &inspected.key_binding {
+            let check = |matches: Option<bool>| match matches {
+                Some(true) => "matches",
+                Some(false) => "does not match",
+                None => "not checked",
+            };
+            let sd_hash = if key_binding.sd_hash_matches {
+                "matches".to_string()
+            } else {
+                format!("does not match, expected {}", key_binding.expected_sd_hash)
+            };
+
+            sections.push(Self::claims_section(
+                "Key-binding JWT header",
+                &key_binding.header,
+            ));
+            sections.push(Self::claims_section(
+                "Key-binding JWT payload",
+                &key_binding.payload,
+            ));
+            sections.push(
+                DetailSection::new("Key-binding checks")
+                    .field("sd_hash", sd_hash)
+                    .field("nonce", check(key_binding.nonce_matches))
+                    .field("aud", check(key_binding.aud_matches)),
+            );
+        }
+        sections
+    }
+
+    fn claims_section(title: &str, claims: &Value) -> DetailSection {
+        let mut section = DetailSection::new(title);
+        if let Some(claims) = claims.as_object() {
+            for (name, value) in claims {
+                section = section.field(name, Self::display_value(value));
+            }
+        }
+        section
+    }
+
+    fn display_value(value: &Value) -> String {
+        match value {
+            Value::String(value) => value.clone(),
+            value => value.to_string(),
+        }
+    }
+}
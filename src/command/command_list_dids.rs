@@ -1,31 +1,27 @@
-use crate::{AppContext, Command, Did, Output, ScreenEvent, VariablesConfig};
+use crate::{
+    AppContext, Command, CommandRegistration, Did, DidQuery, Output, ScreenEvent, ScreenState,
+    TableView, VariablesConfig,
+};
+use async_trait::async_trait;
 use comfy_table::modifiers::UTF8_ROUND_CORNERS;
 use comfy_table::{Cell, Table};
+use ratatui::layout::Constraint;
 
 pub struct ListDIDsCommand<'a> {
     context: &'a AppContext,
 }
 
+#[async_trait]
 impl Command for ListDIDsCommand<'_> {
-    fn execute(&mut self) -> ScreenEvent {
-
-        let dids = self.context.db.get_stored_dids();
-        match dids {
-            Ok(dids) => {
-                Output::display_with_pagination(
-                    &dids,
-                    Self::display_dids_table,
-                    VariablesConfig::get().did_table_size(),
-                    false,
-                    Some(Box::new(|| self.print_tile())),
-                );
-                ScreenEvent::Success
-            }
-            Err(e) => {
-                println!("Error: {}", e);
-                ScreenEvent::Cancel
-            }
-        }
+    async fn execute(&mut self) -> ScreenEvent {
+        self.print_tile();
+        let db = &self.context.db;
+        Self::table_view("List DIDs")
+            .show(self.context.input.as_ref(), |query: &DidQuery| {
+                db.search_dids(query)
+            })
+            .await;
+        ScreenEvent::Success
     }
 
     fn print_tile(&self) {
@@ -35,30 +31,131 @@ impl Command for ListDIDsCommand<'_> {
 }
 
 impl ListDIDsCommand<'_> {
-    pub fn new(context: &AppContext) -> ListDIDsCommand {
+    pub fn new(context: &AppContext) -> ListDIDsCommand<'_> {
         ListDIDsCommand { context }
     }
 
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::ListDIDsWorkflow, |context, _| {
+            Box::new(ListDIDsCommand::new(context))
+        })
+        .menu_entry(
+            ScreenState::ListItemsMenu,
+            "List DIDs",
+            ScreenEvent::ListDIDs,
+        )
+    }
+
+    /// Asks the user to choose one of our active DIDs, the ones that can sign, which can be
+    /// searched and sorted. Returns `None` when the user goes back.
+    pub async fn select_did(context: &AppContext, title: &str) -> Option<Did> {
+        Self::select_did_among(context, title, DidQuery::active()).await
+    }
+
+    /// Asks the user to choose one of the DIDs of `among`, e.g. [`DidQuery::contacts`].
+    /// Returns `None` when the user goes back.
+    pub async fn select_did_among(
+        context: &AppContext,
+        title: &str,
+        among: DidQuery,
+    ) -> Option<Did> {
+        Self::table_view(title)
+            .select(context.input.as_ref(), |query: &DidQuery| {
+                context.db.search_dids(&DidQuery {
+                    text: query.text.clone(),
+                    sort: query.sort,
+                    descending: query.descending,
+                    ..among.clone()
+                })
+            })
+            .await
+    }
+
+    /// Full-screen table of DIDs, with every field of the highlighted DID in the side panel.
+    pub fn table_view(title: &str) -> TableView<'_, Did> {
+        TableView::new(
+            title,
+            vec!["Name", "Created", "DID", "Status", "Id"],
+            vec![
+                Constraint::Length(16),
+                Constraint::Length(19),
+                Constraint::Fill(1),
+                Constraint::Length(11),
+                Constraint::Length(5),
+            ],
+            |did: &Did| {
+                vec![
+                    did.name().to_string(),
+                    did.created_at().to_string(),
+                    did.did().to_string(),
+                    did.status().to_string(),
+                    did.id().to_string(),
+                ]
+            },
+            |did: &Did| {
+                let mut details = vec![
+                    ("Name", did.name().to_string()),
+                    ("DID", did.did().to_string()),
+                    ("Owner", Self::owner(did).to_string()),
+                    ("Status", did.status().to_string()),
+                    ("Fragment", did.fragment().to_string()),
+                    ("Created", did.created_at().to_string()),
+                    ("Id", did.id().to_string()),
+                ];
+                if !did.owned() {
+                    details.push(("Roles", Self::roles(did)));
+                }
+                if did.services().is_empty() {
+                    details.push(("Services", "None".to_string()));
+                }
+                for service in did.services() {
+                    details.push(("Service", service.clone()));
+                }
+                details
+            },
+        )
+        .page_size(VariablesConfig::get().did_table_size())
+    }
+
+    /// Whether the DID is ours or a contact.
+    pub fn owner(did: &Did) -> &'static str {
+        if did.owned() {
+            "Us"
+        } else {
+            "Contact"
+        }
+    }
+
+    /// Roles of a contact, separated by commas.
+    pub fn roles(did: &Did) -> String {
+        if did.roles().is_empty() {
+            return "None".to_string();
+        }
+        let roles: Vec<&str> = did.roles().iter().map(|role| role.as_str()).collect();
+        roles.join(", ")
+    }
+
     pub fn display_dids_table(dids: &Vec<Did>, first_row_index: usize) {
         let mut table = Table::new();
         table.apply_modifier(UTF8_ROUND_CORNERS);
         table.set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
 
         // Add a header row
-        table.set_header(vec!["Row", "Name", "Created", "DID", "Id"]);
+        table.set_header(vec![
+            "Row", "Name", "Created", "DID", "Status", "Services", "Id",
+        ]);
 
         // Add rows for each DID, selecting only `id` and `name`
-        let mut row_number = first_row_index;
-        for did in dids {
+        for (row_number, did) in (first_row_index..).zip(dids) {
             table.add_row(vec![
                 Cell::new(row_number),
                 Cell::new(did.name()),
                 Cell::new(did.created_at()),
                 Cell::new(did.did()),
+                Cell::new(did.status()),
+                Cell::new(did.services().join("\n")),
                 Cell::new(did.id()),
             ]);
-
-            row_number += 1;
         }
 
         // Print the table to the terminal
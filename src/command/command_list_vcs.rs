@@ -1,31 +1,27 @@
-use crate::{AppContext, Command, Output, ScreenEvent, VariablesConfig, Vc};
+use crate::{
+    AppContext, Command, CommandRegistration, Output, ScreenEvent, ScreenState, TableView,
+    VariablesConfig, Vc, VcQuery,
+};
+use async_trait::async_trait;
 use comfy_table::modifiers::UTF8_ROUND_CORNERS;
 use comfy_table::{Cell, Table};
+use ratatui::layout::Constraint;
 
 pub struct ListVCsCommand<'a> {
     context: &'a AppContext,
 }
 
+#[async_trait]
 impl Command for ListVCsCommand<'_> {
-    fn execute(&mut self) -> ScreenEvent {
-
-        let vcs = self.context.db.get_stored_vcs();
-        match vcs {
-            Ok(vcs) => {
-                Output::display_with_pagination(
-                    &vcs,
-                    Self::display_vcs_table,
-                    VariablesConfig::get().vc_table_size(),
-                    false,
-                    Some(Box::new(|| self.print_tile())),
-                );
-                ScreenEvent::Success
-            }
-            Err(e) => {
-                println!("Error: {}", e);
-                ScreenEvent::Cancel
-            }
-        }
+    async fn execute(&mut self) -> ScreenEvent {
+        self.print_tile();
+        let db = &self.context.db;
+        Self::table_view("List VCs")
+            .show(self.context.input.as_ref(), |query: &VcQuery| {
+                db.search_vcs(query)
+            })
+            .await;
+        ScreenEvent::Success
     }
 
     fn print_tile(&self) {
@@ -35,22 +31,89 @@ impl Command for ListVCsCommand<'_> {
 }
 
 impl ListVCsCommand<'_> {
-    pub fn new(context: &AppContext) -> ListVCsCommand {
+    pub fn new(context: &AppContext) -> ListVCsCommand<'_> {
         ListVCsCommand { context }
     }
 
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::ListVCsWorkflow, |context, _| {
+            Box::new(ListVCsCommand::new(context))
+        })
+        .menu_entry(ScreenState::ListItemsMenu, "List VCs", ScreenEvent::ListVCs)
+    }
+
+    /// Asks the user to choose one of the stored VCs, which can be searched and sorted.
+    /// Returns `None` when the user goes back.
+    pub async fn select_vc(context: &AppContext, title: &str) -> Option<Vc> {
+        Self::table_view(title)
+            .select(context.input.as_ref(), |query: &VcQuery| {
+                context.db.search_vcs(query)
+            })
+            .await
+    }
+
+    /// Full-screen table of VCs, with the whole token of the highlighted VC in the side panel.
+    pub fn table_view(title: &str) -> TableView<'_, Vc> {
+        TableView::new(
+            title,
+            vec![
+                "Holder", "Issuer", "Type", "Format", "Expires", "Created", "Id",
+            ],
+            vec![
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(2),
+                Constraint::Length(6),
+                Constraint::Length(19),
+                Constraint::Length(19),
+                Constraint::Length(5),
+            ],
+            |vc: &Vc| {
+                vec![
+                    vc.holder().name().to_string(),
+                    vc.issuer().name().to_string(),
+                    vc.tp().to_string(),
+                    vc.format().to_string(),
+                    vc.expires_at()
+                        .map_or("never".to_string(), |expires_at| expires_at.to_string()),
+                    vc.created_at().to_string(),
+                    vc.id().to_string(),
+                ]
+            },
+            |vc: &Vc| {
+                vec![
+                    ("Type", vc.tp().to_string()),
+                    (
+                        "Holder",
+                        format!("{} {}", vc.holder().name(), vc.holder().did()),
+                    ),
+                    (
+                        "Issuer",
+                        format!("{} {}", vc.issuer().name(), vc.issuer().did()),
+                    ),
+                    ("Format", vc.format().to_string()),
+                    ("Revocation", vc.revocation()),
+                    ("Expires", vc.expiry()),
+                    ("Created", vc.created_at().to_string()),
+                    ("Id", vc.id().to_string()),
+                    (vc.format(), vc.vc().to_string()),
+                ]
+            },
+        )
+        .page_size(VariablesConfig::get().vc_table_size())
+    }
+
     pub fn display_vcs_table(vcs: &Vec<Vc>, fist_row_index: usize) {
         let mut table = Table::new();
         table.apply_modifier(UTF8_ROUND_CORNERS);
         table.set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
         // Add header row
         table.set_header(vec![
-            "Row", "Holder", "Issuer", "Type", "SD", "JWT", "Created", "Id",
+            "Row", "Holder", "Issuer", "Type", "Format", "Token", "Expires", "Created", "Id",
         ]);
 
         // Add rows for each DID
-        let mut row_number = fist_row_index;
-        for vc in vcs {
+        for (row_number, vc) in (fist_row_index..).zip(vcs) {
             let vc_jwt = vc.vc();
 
             // Extract the first 10 and last 10 characters
@@ -65,12 +128,12 @@ impl ListVCsCommand<'_> {
                 Cell::new(vc.holder().name()),
                 Cell::new(vc.issuer().name()),
                 Cell::new(vc.tp()),
-                Cell::new(vc.sd().to_string()),
+                Cell::new(vc.format()),
                 Cell::new(short_vc_text),
+                Cell::new(vc.expiry()),
                 Cell::new(vc.created_at()),
                 Cell::new(vc.id()),
             ]);
-            row_number += 1;
         }
 
         println!("{table}");
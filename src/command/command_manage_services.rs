@@ -0,0 +1,225 @@
+use crate::{
+    parse_service_endpoint, service_endpoint_string, service_types, utils, AppContext, Command,
+    CommandRegistration, Did, DidQuery, ListDIDsCommand, Menu, Output, Progress, ScreenEvent,
+    ScreenState,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use identity_iota::core::Object;
+use identity_iota::did::{DIDUrl, DID};
+use identity_iota::document::{Service, ServiceEndpoint};
+use identity_iota::iota::{IotaDocument, IotaIdentityClientExt};
+
+/// Change of a service, made to the document and then recorded in the database.
+enum ServiceChange {
+    Save {
+        fragment: String,
+        service_type: String,
+        endpoint: ServiceEndpoint,
+    },
+    Remove {
+        fragment: String,
+    },
+}
+
+/// Adds, edits and removes the service endpoints of a published DID.
+pub struct ManageServicesCommand<'a> {
+    context: &'a AppContext,
+}
+
+#[async_trait]
+impl Command for ManageServicesCommand<'_> {
+    async fn execute(&mut self) -> ScreenEvent {
+        self.print_tile();
+        self.handle_manage_services()
+            .await
+            .unwrap_or_else(|e| ScreenEvent::Error(e.to_string()))
+    }
+
+    fn print_tile(&self) {
+        Output::clear_screen();
+        Output::print_screen_title("Service Endpoints")
+    }
+}
+
+impl ManageServicesCommand<'_> {
+    pub fn new(context: &AppContext) -> ManageServicesCommand<'_> {
+        ManageServicesCommand { context }
+    }
+
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::ManageServicesWorkflow, |context, _| {
+            Box::new(ManageServicesCommand::new(context))
+        })
+        .menu_entry(
+            ScreenState::UpdateDIDMenu,
+            "Manage service endpoints",
+            ScreenEvent::ManageServices,
+        )
+    }
+
+    async fn handle_manage_services(&self) -> Result<ScreenEvent> {
+        if self.context.db.search_dids(&DidQuery::active())?.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No active DIDs found. Please create a DID first."
+            ));
+        }
+        let Some(did) = ListDIDsCommand::select_did(self.context, "Choose a DID to update").await
+        else {
+            return Ok(ScreenEvent::Cancel);
+        };
+
+        let mut document = Progress::run("Resolving DID", self.resolve(&did)).await?;
+        let Some(change) = self.choose_change(&document).await? else {
+            return Ok(ScreenEvent::Cancel);
+        };
+        Self::apply_change(&mut document, &change)?;
+
+        Progress::run("Updating DID", self.publish(document)).await?;
+
+        Output::print_during_loading("Saving the services to database");
+        match &change {
+            ServiceChange::Save {
+                fragment,
+                service_type,
+                endpoint,
+            } => {
+                self.context.db.save_did_service(
+                    did.id(),
+                    fragment,
+                    service_type,
+                    &service_endpoint_string(endpoint),
+                )?;
+                Output::print_during_loading(&format!("Saved #{}", fragment));
+            }
+            ServiceChange::Remove { fragment } => {
+                self.context.db.remove_did_service(did.id(), fragment)?;
+                Output::print_during_loading(&format!("Removed #{}", fragment));
+            }
+        }
+
+        self.context
+            .input
+            .wait_for_user_input("Press any key to continue...")
+            .await;
+        Ok(ScreenEvent::Success)
+    }
+
+    /// Asks which service to add, edit or remove. `None` when the user went back.
+    async fn choose_change(&self, document: &IotaDocument) -> Result<Option<ServiceChange>> {
+        let input = self.context.input.as_ref();
+        let services: Vec<&Service> = document.service().iter().collect();
+
+        let mut options: Vec<String> = services
+            .iter()
+            .map(|service| {
+                format!(
+                    "#{} {} {}",
+                    service.id().fragment().unwrap_or_default(),
+                    service_types(service),
+                    service_endpoint_string(service.service_endpoint())
+                )
+            })
+            .collect();
+        options.push("Add a service".to_string());
+        let Some(index) = Menu::new("Service Endpoints", options)
+            .message("Choose a service to edit or remove, or add a new one")
+            .select(input)
+            .await
+        else {
+            return Ok(None);
+        };
+
+        let Some(service) = services.get(index) else {
+            let fragment = input
+                .wait_for_user_input("Service id, without the # (e.g. linked-domain):")
+                .await;
+            let service_type = input
+                .wait_for_user_input("Service type (e.g. LinkedDomains):")
+                .await;
+            let endpoint = input
+                .wait_for_user_input("Endpoint, a URL or a JSON map of URLs:")
+                .await;
+            return Ok(Some(ServiceChange::Save {
+                fragment: fragment.trim().trim_start_matches('#').to_string(),
+                service_type: service_type.trim().to_string(),
+                endpoint: parse_service_endpoint(&endpoint)?,
+            }));
+        };
+
+        let fragment = service.id().fragment().unwrap_or_default().to_string();
+        let actions = vec!["Edit".to_string(), "Remove".to_string()];
+        let title = format!("Service #{}", fragment);
+        match Menu::new(&title, actions).select(input).await {
+            Some(0) => {
+                // Empty answers keep the current values
+                let service_type = input.wait_for_user_input("New type (empty to keep):").await;
+                let endpoint = input
+                    .wait_for_user_input("New endpoint (empty to keep):")
+                    .await;
+                Ok(Some(ServiceChange::Save {
+                    fragment,
+                    service_type: match service_type.trim() {
+                        "" => service_types(service),
+                        service_type => service_type.to_string(),
+                    },
+                    endpoint: match endpoint.trim() {
+                        "" => service.service_endpoint().clone(),
+                        endpoint => parse_service_endpoint(endpoint)?,
+                    },
+                }))
+            }
+            Some(_) => Ok(Some(ServiceChange::Remove { fragment })),
+            None => Ok(None),
+        }
+    }
+
+    fn apply_change(document: &mut IotaDocument, change: &ServiceChange) -> Result<()> {
+        match change {
+            ServiceChange::Save {
+                fragment,
+                service_type,
+                endpoint,
+            } => {
+                if fragment.is_empty() || service_type.is_empty() {
+                    return Err(anyhow::anyhow!("The service id and type are required"));
+                }
+                let id: DIDUrl = document.id().to_url().join(format!("#{}", fragment))?;
+                // An edited service replaces the previous one with the same id
+                document.remove_service(&id);
+
+                let mut builder = Service::builder(Object::new()).id(id);
+                for service_type in service_type.split(',') {
+                    builder = builder.type_(service_type.trim());
+                }
+                document.insert_service(builder.service_endpoint(endpoint.clone()).build()?)?;
+            }
+            ServiceChange::Remove { fragment } => {
+                let id: DIDUrl = document.id().to_url().join(format!("#{}", fragment))?;
+                document.remove_service(&id);
+            }
+        }
+        Ok(())
+    }
+
+    async fn resolve(&self, did: &Did) -> Result<IotaDocument> {
+        Output::print_during_loading("Resolving DID...");
+        Ok(self
+            .context
+            .client()
+            .await?
+            .resolve_did(&self.context.ledger_did(did)?)
+            .await?)
+    }
+
+    async fn publish(&self, document: IotaDocument) -> Result<()> {
+        Output::print_during_loading("Publishing DID...");
+        utils::publish_did_update(
+            self.context.client().await?,
+            self.context.stronghold_storage.as_secret_manager(),
+            document,
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,198 @@
+use crate::{
+    AddMethodsCommand, AppContext, Command, ContactsCommand, CreateDIDCommand, CreateVCJptCommand,
+    CreateVCMenu, CreateVCNormalCommand, CreateVCSDCommand, CreateVPCommand, DIDDetailsCommand,
+    DeactivateDIDCommand, DestroyDIDCommand, ErrorCommand, ExitAppCommand, InspectTokenCommand,
+    ListCreatedItems, ListDIDsCommand, ListVCsCommand, MainMenuCommand, ManageServicesCommand,
+    RemoveRetiredKeysCommand, RevocationCommand, RotateKeyCommand, ScreenEvent, ScreenFSM,
+    ScreenState, StatusListsCommand, SwitchNetworkCommand, UpdateDIDMenu, VerifyVCCommand,
+};
+
+/// Builds the command of a screen. It also receives the [`ScreenFSM`], so menus can list their
+/// entries from its registry and the error screen can show the last failure.
+pub type CommandFactory = for<'a> fn(&'a AppContext, &ScreenFSM) -> Box<dyn Command + 'a>;
+
+/// Entry of a screen in the menu of its parent.
+#[derive(Clone, Debug)]
+pub struct MenuEntry {
+    pub parent: ScreenState,
+    pub label: String,
+    pub event: ScreenEvent,
+}
+
+/// Everything the app needs to know about a screen: the state it is shown in,
+/// how it is reached from a menu and where its events lead to.
+pub struct CommandRegistration {
+    state: ScreenState,
+    factory: CommandFactory,
+    title: Option<String>,
+    menu_entry: Option<MenuEntry>,
+    transitions: Vec<(ScreenEvent, ScreenState)>,
+}
+
+impl CommandRegistration {
+    pub fn new(state: ScreenState, factory: CommandFactory) -> Self {
+        CommandRegistration {
+            state,
+            factory,
+            title: None,
+            menu_entry: None,
+            transitions: Vec::new(),
+        }
+    }
+
+    /// Name of the screen in the breadcrumbs. Defaults to the label of its menu entry.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Lists the screen in the `parent` menu as `label`; selecting it emits `event`.
+    pub fn menu_entry(mut self, parent: ScreenState, label: &str, event: ScreenEvent) -> Self {
+        self.menu_entry = Some(MenuEntry {
+            parent,
+            label: label.to_string(),
+            event,
+        });
+        self
+    }
+
+    /// Declares that `event`, emitted by this screen, leads to `target`.
+    pub fn transition(mut self, event: ScreenEvent, target: ScreenState) -> Self {
+        self.transitions.push((event, target));
+        self
+    }
+
+    pub fn state(&self) -> &ScreenState {
+        &self.state
+    }
+
+    pub(crate) fn build<'a>(
+        &self,
+        context: &'a AppContext,
+        fsm: &ScreenFSM,
+    ) -> Box<dyn Command + 'a> {
+        (self.factory)(context, fsm)
+    }
+}
+
+/// Registry of every screen of the app. The menus and the [`ScreenFSM`](crate::ScreenFSM)
+/// transitions are built from it, so a new workflow only needs to register itself.
+pub struct CommandRegistry {
+    registrations: Vec<CommandRegistration>,
+}
+
+impl CommandRegistry {
+    pub fn new() -> Self {
+        CommandRegistry {
+            registrations: Vec::new(),
+        }
+    }
+
+    /// Registry with the screens shipped with Petrus. Menu entries are shown in registration order.
+    pub fn with_default_commands() -> Self {
+        let mut registry = CommandRegistry::new();
+        registry
+            .register(MainMenuCommand::registration())
+            .register(ListCreatedItems::registration())
+            .register(ListDIDsCommand::registration())
+            .register(ListVCsCommand::registration())
+            .register(DIDDetailsCommand::registration())
+            .register(CreateDIDCommand::registration())
+            .register(UpdateDIDMenu::registration())
+            .register(AddMethodsCommand::registration())
+            .register(ManageServicesCommand::registration())
+            .register(RotateKeyCommand::registration())
+            .register(RemoveRetiredKeysCommand::registration())
+            .register(DeactivateDIDCommand::registration())
+            .register(DestroyDIDCommand::registration())
+            .register(RevocationCommand::registration())
+            .register(CreateVCMenu::registration())
+            .register(CreateVCNormalCommand::registration())
+            .register(CreateVCSDCommand::registration())
+            .register(CreateVCJptCommand::registration())
+            .register(StatusListsCommand::registration())
+            .register(CreateVPCommand::registration())
+            .register(VerifyVCCommand::registration())
+            .register(InspectTokenCommand::registration())
+            .register(ContactsCommand::registration())
+            .register(SwitchNetworkCommand::registration())
+            .register(ExitAppCommand::registration())
+            .register(ErrorCommand::registration());
+        registry
+    }
+
+    /// Adds a screen, replacing any screen previously registered for the same state.
+    pub fn register(&mut self, registration: CommandRegistration) -> &mut Self {
+        self.registrations
+            .retain(|existing| existing.state != registration.state);
+        self.registrations.push(registration);
+        self
+    }
+
+    /// Options of the `menu` screen. Every menu but the main one ends with a "Back" option.
+    pub fn menu_options(&self, menu: &ScreenState) -> Vec<(String, ScreenEvent)> {
+        let mut options: Vec<(String, ScreenEvent)> = self
+            .registrations
+            .iter()
+            .filter_map(|registration| registration.menu_entry.as_ref())
+            .filter(|entry| &entry.parent == menu)
+            .map(|entry| (entry.label.clone(), entry.event.clone()))
+            .collect();
+
+        if *menu != ScreenState::MainMenu {
+            options.push(("Back".to_string(), ScreenEvent::Cancel));
+        }
+
+        options
+    }
+
+    /// Title of `state` in the breadcrumbs.
+    pub fn title(&self, state: &ScreenState) -> String {
+        let registration = self.find(state);
+        registration
+            .and_then(|registration| registration.title.clone())
+            .or_else(|| {
+                registration
+                    .and_then(|registration| registration.menu_entry.as_ref())
+                    .map(|entry| entry.label.clone())
+            })
+            .unwrap_or_else(|| format!("{:?}", state))
+    }
+
+    /// Screen opened by `event` from `state`, if the screen declared it or `event` selects an
+    /// entry of the `state` menu. Going back is handled by the [`ScreenFSM`].
+    pub fn transition(&self, state: &ScreenState, event: &ScreenEvent) -> Option<ScreenState> {
+        // Transitions declared by the screen itself take precedence
+        let declared = self.find(state).and_then(|registration| {
+            registration
+                .transitions
+                .iter()
+                .find(|(declared_event, _)| declared_event == event)
+                .map(|(_, target)| target.clone())
+        });
+        if declared.is_some() {
+            return declared;
+        }
+
+        // Selecting an entry of a menu opens its screen
+        let menu_entry = self.registrations.iter().find(|registration| {
+            registration
+                .menu_entry
+                .as_ref()
+                .is_some_and(|entry| &entry.parent == state && &entry.event == event)
+        });
+        menu_entry.map(|registration| registration.state.clone())
+    }
+
+    pub(crate) fn find(&self, state: &ScreenState) -> Option<&CommandRegistration> {
+        self.registrations
+            .iter()
+            .find(|registration| &registration.state == state)
+    }
+}
+
+impl Default for CommandRegistry {
+    fn default() -> Self {
+        CommandRegistry::with_default_commands()
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{
+    utils, AppContext, Command, CommandRegistration, Did, DidMethod, Modal, Output, Progress,
+    ScreenEvent, ScreenState,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use identity_iota::did::DID;
+use identity_iota::iota::{IotaDocument, IotaIdentityClientExt};
+use identity_iota::storage::JwkDocumentExt;
+
+/// Removes from their documents the methods whose key was rotated and whose grace period is
+/// over, along with their keys.
+pub struct RemoveRetiredKeysCommand<'a> {
+    context: &'a AppContext,
+}
+
+#[async_trait]
+impl Command for RemoveRetiredKeysCommand<'_> {
+    async fn execute(&mut self) -> ScreenEvent {
+        self.print_tile();
+        self.handle_remove_retired_keys()
+            .await
+            .unwrap_or_else(|e| ScreenEvent::Error(e.to_string()))
+    }
+
+    fn print_tile(&self) {
+        Output::clear_screen();
+        Output::print_screen_title("Remove Retired Keys")
+    }
+}
+
+impl RemoveRetiredKeysCommand<'_> {
+    pub fn new(context: &AppContext) -> RemoveRetiredKeysCommand<'_> {
+        RemoveRetiredKeysCommand { context }
+    }
+
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::RemoveRetiredKeysWorkflow, |context, _| {
+            Box::new(RemoveRetiredKeysCommand::new(context))
+        })
+        .menu_entry(
+            ScreenState::UpdateDIDMenu,
+            "Remove retired keys",
+            ScreenEvent::RemoveRetiredKeys,
+        )
+    }
+
+    async fn handle_remove_retired_keys(&self) -> Result<ScreenEvent> {
+        let due = self.context.db.get_due_did_methods()?;
+        if due.is_empty() {
+            Modal::new("Remove Retired Keys", "No retired key is due for removal.")
+                .acknowledge(self.context.input.as_ref())
+                .await;
+            return Ok(ScreenEvent::Success);
+        }
+
+        let mut did_ids: Vec<i64> = due.iter().map(|method| method.did_id()).collect();
+        did_ids.sort();
+        did_ids.dedup();
+
+        for did_id in did_ids {
+            let did = self.context.db.get_did_from_id(did_id)?;
+            let methods: Vec<&DidMethod> = due
+                .iter()
+                .filter(|method| method.did_id() == did_id)
+                .collect();
+
+            let title = format!("Updating {}", did.name());
+            Progress::run(&title, self.remove_methods(&did, &methods)).await?;
+
+            for method in methods {
+                self.context.db.remove_did_method(method.id())?;
+                Output::print_during_loading(&format!(
+                    "Removed #{} from {}",
+                    method.fragment(),
+                    did.name()
+                ));
+            }
+        }
+
+        self.context
+            .input
+            .wait_for_user_input("Press any key to continue...")
+            .await;
+        Ok(ScreenEvent::Success)
+    }
+
+    async fn remove_methods(&self, did: &Did, methods: &[&DidMethod]) -> Result<()> {
+        Output::print_during_loading("Resolving DID...");
+        let mut document: IotaDocument = self
+            .context
+            .client()
+            .await?
+            .resolve_did(&self.context.ledger_did(did)?)
+            .await?;
+
+        let mut method_ids = Vec::new();
+        let mut updated = document.clone();
+        for method in methods {
+            let method_id = document
+                .id()
+                .to_url()
+                .join(format!("#{}", method.fragment()))?;
+            updated.remove_method(&method_id);
+            method_ids.push(method_id);
+        }
+
+        Output::print_during_loading("Publishing DID...");
+        utils::publish_did_update(
+            self.context.client().await?,
+            self.context.stronghold_storage.as_secret_manager(),
+            updated,
+        )
+        .await?;
+
+        // The keys are deleted only once the document without them is published
+        for (method, method_id) in methods.iter().zip(&method_ids) {
+            Output::print_during_loading(&format!("Removing #{}...", method.fragment()));
+            document
+                .purge_method(&self.context.storage, method_id)
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{
+    new_revocation_service, revocation_service, service_endpoint_string, service_types,
+    update_revocation_service, utils, AppContext, Command, CommandRegistration, Did, DidQuery,
+    ListDIDsCommand, Menu, Output, Progress, ScreenEvent, ScreenState, Vc, VcQuery,
+};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use identity_iota::document::Service;
+use identity_iota::iota::{IotaDocument, IotaIdentityClientExt};
+
+/// Revocation of the VCs we issued. An issuer first attaches a RevocationBitmap2022 service to
+/// its DID, the VCs issued afterwards get an index in it and are revoked by publishing the
+/// updated bitmap.
+pub struct RevocationCommand<'a> {
+    context: &'a AppContext,
+}
+
+#[async_trait]
+impl Command for RevocationCommand<'_> {
+    async fn execute(&mut self) -> ScreenEvent {
+        self.print_tile();
+        self.handle_revocation()
+            .await
+            .unwrap_or_else(|e| ScreenEvent::Error(e.to_string()))
+    }
+
+    fn print_tile(&self) {
+        Output::clear_screen();
+        Output::print_screen_title("Revocation")
+    }
+}
+
+impl RevocationCommand<'_> {
+    pub fn new(context: &AppContext) -> RevocationCommand<'_> {
+        RevocationCommand { context }
+    }
+
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::RevocationWorkflow, |context, _| {
+            Box::new(RevocationCommand::new(context))
+        })
+        .menu_entry(
+            ScreenState::UpdateDIDMenu,
+            "Revoke issued VCs",
+            ScreenEvent::Revocation,
+        )
+    }
+
+    async fn handle_revocation(&self) -> Result<ScreenEvent> {
+        let options = vec![
+            "Attach a revocation bitmap".to_string(),
+            "Revoke a VC".to_string(),
+            "Unrevoke a VC".to_string(),
+        ];
+        match Menu::new("Revocation", options)
+            .message("Only the VCs issued after the bitmap was attached can be revoked")
+            .select(self.context.input.as_ref())
+            .await
+        {
+            Some(0) => self.attach_bitmap().await,
+            Some(1) => self.set_revoked(true).await,
+            Some(_) => self.set_revoked(false).await,
+            None => Ok(ScreenEvent::Cancel),
+        }
+    }
+
+    async fn attach_bitmap(&self) -> Result<ScreenEvent> {
+        if self.context.db.search_dids(&DidQuery::active())?.is_empty() {
+            anyhow::bail!("No active DIDs found. Please create a DID first.");
+        }
+        let Some(did) = ListDIDsCommand::select_did(self.context, "Choose the issuer DID").await
+        else {
+            return Ok(ScreenEvent::Cancel);
+        };
+
+        let mut document = Progress::run("Resolving DID", self.resolve(&did)).await?;
+        if let Some(service) = revocation_service(document.core_document()) {
+            anyhow::bail!(
+                "{} already has the revocation bitmap {}",
+                did.name(),
+                service.id()
+            );
+        }
+        let service = new_revocation_service(document.core_document())?;
+        document.insert_service(service.clone())?;
+
+        Progress::run("Updating DID", self.publish(document)).await?;
+        self.save_service(&did, &service)?;
+
+        Output::print_during_loading(&format!("{} can now revoke the VCs it issues", did.name()));
+        self.context
+            .input
+            .wait_for_user_input("Press any key to continue...")
+            .await;
+        Ok(ScreenEvent::Success)
+    }
+
+    /// Revokes a valid VC, or unrevokes a revoked one, in the bitmap of its issuer.
+    async fn set_revoked(&self, revoked: bool) -> Result<ScreenEvent> {
+        let Some(vc) = self.choose_vc(revoked).await? else {
+            return Ok(ScreenEvent::Cancel);
+        };
+        let index = vc.status_index().context("The VC cannot be revoked")?;
+
+        let mut document = Progress::run("Resolving DID", self.resolve(vc.issuer())).await?;
+        let service = revocation_service(document.core_document())
+            .with_context(|| format!("{} has no revocation bitmap", vc.issuer().name()))?;
+        let service = update_revocation_service(service, index, revoked)?;
+        document.remove_service(service.id());
+        document.insert_service(service.clone())?;
+
+        Progress::run("Updating DID", self.publish(document)).await?;
+        self.save_service(vc.issuer(), &service)?;
+        self.context.db.set_vc_revoked(vc.id(), revoked)?;
+
+        let verb = if revoked { "revoked" } else { "unrevoked" };
+        Output::print_during_loading(&format!("{} {} is {}", vc.tp(), vc.id(), verb));
+        self.context
+            .input
+            .wait_for_user_input("Press any key to continue...")
+            .await;
+        Ok(ScreenEvent::Success)
+    }
+
+    /// Asks for one of the revocable VCs we issued that can be `revoked`, or unrevoked.
+    /// `None` when the user went back.
+    async fn choose_vc(&self, revoked: bool) -> Result<Option<Vc>> {
+        let vcs = Self::revocable_vcs(self.context.db.search_vcs(&VcQuery::default())?, revoked);
+        if vcs.is_empty() {
+            let state = if revoked { "valid" } else { "revoked" };
+            anyhow::bail!("None of the revocable VCs we issued is {}", state);
+        }
+
+        let labels = vcs
+            .iter()
+            .map(|vc| {
+                format!(
+                    "{} {} issued by {} to {} ({})",
+                    vc.id(),
+                    vc.tp(),
+                    vc.issuer().name(),
+                    vc.holder().name(),
+                    vc.created_at()
+                )
+            })
+            .collect();
+        let title = if revoked {
+            "Revoke a VC"
+        } else {
+            "Unrevoke a VC"
+        };
+        Ok(Menu::new(title, labels)
+            .select(self.context.input.as_ref())
+            .await
+            .map(|index| vcs[index].clone()))
+    }
+
+    /// The `vcs` we issued with an index in the revocation bitmap of their issuer that can be
+    /// `revoked`, or unrevoked. The VCs of a status list have an index in that list instead.
+    pub fn revocable_vcs(vcs: Vec<Vc>, revoked: bool) -> Vec<Vc> {
+        vcs.into_iter()
+            .filter(|vc| {
+                vc.issuer().owned()
+                    && vc.status_index().is_some()
+                    && vc.status_list().is_none()
+                    && vc.revoked() != revoked
+            })
+            .collect()
+    }
+
+    // Records the revocation service as published, replacing the previous bitmap
+    fn save_service(&self, did: &Did, service: &Service) -> Result<()> {
+        self.context.db.save_did_service(
+            did.id(),
+            service.id().fragment().unwrap_or_default(),
+            &service_types(service),
+            &service_endpoint_string(service.service_endpoint()),
+        )
+    }
+
+    async fn resolve(&self, did: &Did) -> Result<IotaDocument> {
+        Output::print_during_loading("Resolving DID...");
+        Ok(self
+            .context
+            .client()
+            .await?
+            .resolve_did(&self.context.ledger_did(did)?)
+            .await?)
+    }
+
+    async fn publish(&self, document: IotaDocument) -> Result<()> {
+        Output::print_during_loading("Publishing DID...");
+        utils::publish_did_update(
+            self.context.client().await?,
+            self.context.stronghold_storage.as_secret_manager(),
+            document,
+        )
+        .await?;
+        Ok(())
+    }
+}
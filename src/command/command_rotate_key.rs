@@ -0,0 +1,175 @@
+use crate::{
+    scope_name, utils, AppContext, Command, CommandRegistration, Did, DidQuery, KeyAlgorithm,
+    ListDIDsCommand, Output, Progress, ScreenEvent, ScreenState,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use identity_iota::did::DID;
+use identity_iota::iota::{IotaDocument, IotaIdentityClientExt};
+use identity_iota::storage::JwkDocumentExt;
+use identity_iota::verification::MethodScope;
+
+// Longest grace period, in days, before the old key is removed
+const MAX_GRACE_DAYS: usize = 365;
+
+/// Replaces the key a DID signs with. The old method stays in the document for a grace period,
+/// so the credentials it signed can still be verified meanwhile.
+pub struct RotateKeyCommand<'a> {
+    context: &'a AppContext,
+}
+
+#[async_trait]
+impl Command for RotateKeyCommand<'_> {
+    async fn execute(&mut self) -> ScreenEvent {
+        self.print_tile();
+        self.handle_rotate_key()
+            .await
+            .unwrap_or_else(|e| ScreenEvent::Error(e.to_string()))
+    }
+
+    fn print_tile(&self) {
+        Output::clear_screen();
+        Output::print_screen_title("Rotate Key")
+    }
+}
+
+impl RotateKeyCommand<'_> {
+    pub fn new(context: &AppContext) -> RotateKeyCommand<'_> {
+        RotateKeyCommand { context }
+    }
+
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::RotateKeyWorkflow, |context, _| {
+            Box::new(RotateKeyCommand::new(context))
+        })
+        .menu_entry(
+            ScreenState::UpdateDIDMenu,
+            "Rotate the signing key",
+            ScreenEvent::RotateKey,
+        )
+    }
+
+    async fn handle_rotate_key(&self) -> Result<ScreenEvent> {
+        if self.context.db.search_dids(&DidQuery::active())?.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No active DIDs found. Please create a DID first."
+            ));
+        }
+        let Some(did) =
+            ListDIDsCommand::select_did(self.context, "Choose the DID whose key to rotate").await
+        else {
+            return Ok(ScreenEvent::Cancel);
+        };
+
+        self.print_tile();
+        println!(
+            "Days before the old key #{} is removed from the document (0 removes it now):",
+            did.fragment()
+        );
+        let grace_days = self.context.input.get_number_input(0, MAX_GRACE_DAYS).await as u32;
+
+        // The new key has the same type as the one it replaces
+        let old_method = self
+            .context
+            .db
+            .get_did_methods(did.id())?
+            .into_iter()
+            .find(|method| method.fragment() == did.fragment() && !method.removed());
+        let algorithm = match &old_method {
+            Some(old_method) => KeyAlgorithm::from_jws_name(old_method.alg())?,
+            None => KeyAlgorithm::default(),
+        };
+
+        let fragment =
+            Progress::run("Rotating key", self.rotate_key(&did, grace_days, algorithm)).await?;
+
+        Output::print_during_loading("Saving the new key to database");
+        self.context.db.set_did_fragment(did.id(), &fragment)?;
+        self.context.db.save_did_method(
+            did.id(),
+            &fragment,
+            scope_name(&MethodScope::VerificationMethod),
+            algorithm.jws_algorithm().name(),
+        )?;
+        if let Some(old_method) = old_method {
+            if grace_days == 0 {
+                self.context.db.remove_did_method(old_method.id())?;
+            } else {
+                self.context
+                    .db
+                    .retire_did_method(old_method.id(), grace_days)?;
+            }
+        }
+
+        Output::print_during_loading(&format!("New signing key: #{}", fragment));
+        if grace_days > 0 {
+            Output::print_during_loading(&format!(
+                "#{} can be removed with \"Remove retired keys\" in {} days",
+                did.fragment(),
+                grace_days
+            ));
+        }
+
+        self.context
+            .input
+            .wait_for_user_input("Press any key to continue...")
+            .await;
+        Ok(ScreenEvent::Success)
+    }
+
+    /// Adds a new signing method to the document of `did`, removes the current one unless
+    /// there is a grace period, and publishes the document. Returns the new fragment.
+    async fn rotate_key(
+        &self,
+        did: &Did,
+        grace_days: u32,
+        algorithm: KeyAlgorithm,
+    ) -> Result<String> {
+        Output::print_during_loading("Resolving DID...");
+        let mut document: IotaDocument = self
+            .context
+            .client()
+            .await?
+            .resolve_did(&self.context.ledger_did(did)?)
+            .await?;
+
+        Output::print_during_loading("Generating the new key...");
+        let fragment = document
+            .generate_method(
+                &self.context.storage,
+                algorithm.key_type(),
+                algorithm.jws_algorithm(),
+                None,
+                MethodScope::VerificationMethod,
+            )
+            .await?;
+
+        // The old key is deleted from the storage only once the document without it is
+        // published, a failed publish must leave the DID with a key it can sign with
+        let old_method = document
+            .id()
+            .to_url()
+            .join(format!("#{}", did.fragment()))?;
+        let mut updated = document.clone();
+        if grace_days == 0 {
+            updated.remove_method(&old_method);
+        }
+
+        Output::print_during_loading("Publishing DID...");
+        utils::publish_did_update(
+            self.context.client().await?,
+            self.context.stronghold_storage.as_secret_manager(),
+            updated,
+        )
+        .await?;
+
+        if grace_days == 0 {
+            Output::print_during_loading("Removing the old key...");
+            document
+                .purge_method(&self.context.storage, &old_method)
+                .await?;
+        }
+
+        Ok(fragment)
+    }
+}
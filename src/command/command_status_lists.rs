@@ -0,0 +1,265 @@
+use crate::{
+    export_status_list, new_status_list_credential, parse_status_list_credential,
+    set_status_list_entry, AppContext, Command, CommandRegistration, Did, DidQuery, DidStatus,
+    ListDIDsCommand, Menu, Output, ScreenEvent, ScreenState, StatusList, StatusListPurpose, Vc,
+    VcQuery, VerifyVCCommand,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use identity_iota::core::Url;
+use identity_iota::credential::status_list_2021::StatusList2021Credential;
+use identity_iota::storage::{JwkDocumentExt, JwsSignatureOptions};
+
+/// StatusList2021 credentials of our issuers, which any verifier can fetch to check whether a VC
+/// was revoked or suspended. The lists are signed again on every change, and exported to be
+/// hosted at their URL.
+pub struct StatusListsCommand<'a> {
+    context: &'a AppContext,
+}
+
+#[async_trait]
+impl Command for StatusListsCommand<'_> {
+    async fn execute(&mut self) -> ScreenEvent {
+        self.print_tile();
+        self.handle_status_lists()
+            .await
+            .unwrap_or_else(|e| ScreenEvent::Error(e.to_string()))
+    }
+
+    fn print_tile(&self) {
+        Output::clear_screen();
+        Output::print_screen_title("Status Lists")
+    }
+}
+
+impl StatusListsCommand<'_> {
+    pub fn new(context: &AppContext) -> StatusListsCommand<'_> {
+        StatusListsCommand { context }
+    }
+
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::StatusListsWorkflow, |context, _| {
+            Box::new(StatusListsCommand::new(context))
+        })
+        .menu_entry(
+            ScreenState::CreateVCMenu,
+            "Manage status lists",
+            ScreenEvent::StatusLists,
+        )
+    }
+
+    async fn handle_status_lists(&self) -> Result<ScreenEvent> {
+        let options = vec![
+            "Create a status list".to_string(),
+            "Revoke, suspend or reinstate a VC".to_string(),
+            "Re-sign a status list".to_string(),
+            "Export a status list".to_string(),
+        ];
+        match Menu::new("Status Lists", options)
+            .message("VCs are put in a status list of their issuer when they are issued")
+            .select(self.context.input.as_ref())
+            .await
+        {
+            Some(0) => self.create_list().await,
+            Some(1) => self.change_status().await,
+            Some(2) => self.resign_list().await,
+            Some(_) => self.export_list().await,
+            None => Ok(ScreenEvent::Cancel),
+        }
+    }
+
+    async fn create_list(&self) -> Result<ScreenEvent> {
+        if self.context.db.search_dids(&DidQuery::active())?.is_empty() {
+            anyhow::bail!("No active DIDs found. Please create a DID first.");
+        }
+        let Some(issuer) =
+            ListDIDsCommand::select_did(self.context, "Choose the issuer of the list").await
+        else {
+            return Ok(ScreenEvent::Cancel);
+        };
+        let input = self.context.input.as_ref();
+        let url = input
+            .wait_for_user_input(
+                "URL the list will be hosted at (e.g. https://issuer.example/status/1):",
+            )
+            .await;
+        let url = Url::parse(url.trim())?;
+
+        let purposes = StatusListPurpose::all();
+        let labels = purposes
+            .iter()
+            .map(|purpose| format!("{}, the VCs can be {}", purpose, purpose.set_state()))
+            .collect();
+        let Some(index) = Menu::new("Purpose of the list", labels).select(input).await else {
+            return Ok(ScreenEvent::Cancel);
+        };
+        let purpose = purposes[index];
+
+        let issuer_document = issuer.resolve_document(&self.context.resolver).await?;
+        let list = new_status_list_credential(&issuer_document, url.clone(), purpose)?;
+        let jwt = self.sign(&list, &issuer).await?;
+        let id = self.context.db.save_status_list(
+            issuer.id(),
+            purpose,
+            url.as_str(),
+            &serde_json::to_string(&list)?,
+            &jwt,
+        )?;
+
+        Output::print_during_loading(&format!(
+            "Status list {} created, export it to host it at {}",
+            id, url
+        ));
+        input
+            .wait_for_user_input("Press any key to continue...")
+            .await;
+        Ok(ScreenEvent::Success)
+    }
+
+    /// Sets the entry of a valid VC, which revokes or suspends it, or clears the entry of a
+    /// suspended one. Revocations are final.
+    async fn change_status(&self) -> Result<ScreenEvent> {
+        let vcs: Vec<Vc> = self
+            .context
+            .db
+            .search_vcs(&VcQuery::default())?
+            .into_iter()
+            .filter(|vc| {
+                vc.issuer().owned()
+                    && vc.status_list().is_some()
+                    && !(vc.revoked() && vc.status_purpose() == Some(StatusListPurpose::Revocation))
+            })
+            .collect();
+        if vcs.is_empty() {
+            anyhow::bail!("None of the VCs we issued in a status list can change status");
+        }
+
+        let labels = vcs
+            .iter()
+            .map(|vc| {
+                format!(
+                    "{} {} issued to {}: {}",
+                    vc.id(),
+                    vc.tp(),
+                    vc.holder().name(),
+                    vc.revocation()
+                )
+            })
+            .collect();
+        let Some(index) = Menu::new("Choose a VC", labels)
+            .select(self.context.input.as_ref())
+            .await
+        else {
+            return Ok(ScreenEvent::Cancel);
+        };
+        let vc = &vcs[index];
+        let (Some(list_id), Some(status_index)) = (vc.status_list(), vc.status_index()) else {
+            anyhow::bail!("The VC {} is not in a status list", vc.id());
+        };
+
+        let list = self.context.db.get_status_list(list_id)?;
+        let mut credential = parse_status_list_credential(list.credential())?;
+        set_status_list_entry(&mut credential, status_index, !vc.revoked())?;
+        self.save_list(&list, &credential).await?;
+        self.context.db.set_vc_revoked(vc.id(), !vc.revoked())?;
+
+        let state = if vc.revoked() {
+            "reinstated"
+        } else {
+            list.purpose().set_state()
+        };
+        Output::print_during_loading(&format!(
+            "{} {} is {}, export the status list {} to publish it",
+            vc.tp(),
+            vc.id(),
+            state,
+            list.id()
+        ));
+        self.context
+            .input
+            .wait_for_user_input("Press any key to continue...")
+            .await;
+        Ok(ScreenEvent::Success)
+    }
+
+    /// Signs the list again without changing it, e.g. after the key of the issuer was rotated.
+    async fn resign_list(&self) -> Result<ScreenEvent> {
+        let Some(list) = self
+            .choose_list("Choose the status list to sign again")
+            .await?
+        else {
+            return Ok(ScreenEvent::Cancel);
+        };
+        let credential = parse_status_list_credential(list.credential())?;
+        self.save_list(&list, &credential).await?;
+
+        Output::print_during_loading(&format!("Status list {} signed again", list.id()));
+        self.context
+            .input
+            .wait_for_user_input("Press any key to continue...")
+            .await;
+        Ok(ScreenEvent::Success)
+    }
+
+    async fn export_list(&self) -> Result<ScreenEvent> {
+        let Some(list) = self.choose_list("Choose the status list to export").await? else {
+            return Ok(ScreenEvent::Cancel);
+        };
+        let file = export_status_list(
+            list.jwt(),
+            &Url::parse(list.url())?,
+            VerifyVCCommand::status_list_directory(),
+        )?;
+
+        println!("Status list written to {}", file.display());
+        println!("Host it at {}", list.url());
+        self.context
+            .input
+            .wait_for_user_input("Press any key to continue...")
+            .await;
+        Ok(ScreenEvent::Success)
+    }
+
+    async fn choose_list(&self, title: &str) -> Result<Option<StatusList>> {
+        let lists = self.context.db.get_status_lists(None)?;
+        if lists.is_empty() {
+            anyhow::bail!("No status lists found. Please create one first.");
+        }
+        let labels = lists.iter().map(|list| list.label()).collect();
+        Ok(Menu::new(title, labels)
+            .select(self.context.input.as_ref())
+            .await
+            .map(|index| lists[index].clone()))
+    }
+
+    // Signs the `credential` of the `list` with its issuer and stores both
+    async fn save_list(
+        &self,
+        list: &StatusList,
+        credential: &StatusList2021Credential,
+    ) -> Result<()> {
+        let issuer = self.context.db.get_did_from_id(list.issuer())?;
+        let jwt = self.sign(credential, &issuer).await?;
+        self.context
+            .db
+            .update_status_list(list.id(), &serde_json::to_string(credential)?, &jwt)
+    }
+
+    async fn sign(&self, list: &StatusList2021Credential, issuer: &Did) -> Result<String> {
+        if issuer.status() != DidStatus::Active {
+            anyhow::bail!("{} is {}, it cannot sign", issuer.name(), issuer.status());
+        }
+        let issuer_document = issuer.resolve_document(&self.context.resolver).await?;
+        // The subject holding the list is only put back in the credential by `into_inner`
+        let jwt = issuer_document
+            .create_credential_jwt(
+                &list.clone().into_inner(),
+                &self.context.storage,
+                issuer.fragment(),
+                &JwsSignatureOptions::default(),
+                None,
+            )
+            .await?;
+        Ok(jwt.as_str().to_string())
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{
+    AppContext, Command, CommandRegistration, Menu, Output, ScreenEvent, ScreenState,
+    VariablesConfig,
+};
+use async_trait::async_trait;
+
+/// Chooses the network profile new DIDs are published on. The app connects to it once the
+/// screen returns [`ScreenEvent::NetworkSelected`], as the screens only borrow the context.
+pub struct SwitchNetworkCommand<'a> {
+    context: &'a AppContext,
+}
+
+#[async_trait]
+impl Command for SwitchNetworkCommand<'_> {
+    async fn execute(&mut self) -> ScreenEvent {
+        self.print_tile();
+        self.choose_network().await
+    }
+
+    fn print_tile(&self) {
+        Output::clear_screen();
+        Output::print_screen_title("Switch Network")
+    }
+}
+
+impl SwitchNetworkCommand<'_> {
+    pub fn new(context: &AppContext) -> SwitchNetworkCommand<'_> {
+        SwitchNetworkCommand { context }
+    }
+
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::SwitchNetworkWorkflow, |context, _| {
+            Box::new(SwitchNetworkCommand::new(context))
+        })
+        .menu_entry(
+            ScreenState::MainMenu,
+            "Switch network",
+            ScreenEvent::SwitchNetwork,
+        )
+    }
+
+    async fn choose_network(&self) -> ScreenEvent {
+        let profiles = match VariablesConfig::get().network_profiles() {
+            Ok(profiles) => profiles,
+            Err(e) => return ScreenEvent::Error(e.to_string()),
+        };
+        let labels = profiles.iter().map(|profile| profile.label()).collect();
+        let message = format!("Connected to {}", self.context.network.label());
+
+        match Menu::new("Network", labels)
+            .message(&message)
+            .select(self.context.input.as_ref())
+            .await
+        {
+            Some(index) => ScreenEvent::NetworkSelected(profiles[index].name.clone()),
+            None => ScreenEvent::Cancel,
+        }
+    }
+}
@@ -1,32 +1,38 @@
 use crate::{
-    AppContext, Command, Did, Input, ListDIDsCommand, ListVCsCommand, Output, ScreenEvent,
-    VariablesConfig, Vc,
+    check_credential_status, check_validity, inspect_token, jpt_validation_options,
+    jwt_validation_options, utils, AppContext, Command, CommandRegistration, CompositeJwsVerifier,
+    Config, ContactRole, ContactsCommand, Did, InspectedToken, ListVCsCommand, Menu, Output,
+    Progress, ScreenEvent, ScreenState, TokenKind, Validity, VariablesConfig, Vc,
+    VerificationCheck, VerificationReport,
 };
-use anyhow::Result;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
 use colored::Colorize;
-use identity_eddsa_verifier::EdDSAJwsVerifier;
-use identity_iota::core::Object;
-use identity_iota::credential::SdJwtCredentialValidator;
+use identity_iota::core::{Object, Timestamp, Url};
 use identity_iota::credential::{
-    DecodedJwtCredential, FailFast, Jwt, JwtCredentialValidationOptions, JwtCredentialValidator,
+    CompoundCredentialValidationError, Credential, DecodedJptCredential, Jpt,
+    JptCredentialValidator, JwtCredentialValidatorUtils, SdJwtCredentialValidator,
+    SubjectHolderRelationship,
 };
-use identity_iota::iota::IotaDocument;
+use identity_iota::credential::{DecodedJwtCredential, FailFast, Jwt, JwtCredentialValidator};
+use identity_iota::did::{CoreDID, DIDUrl, DID};
+use identity_iota::document::CoreDocument;
+use identity_iota::prelude::Resolver;
 use sd_jwt_payload::{SdJwt, SdObjectDecoder};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::Path;
 
 pub struct VerifyVCCommand<'a> {
     context: &'a AppContext,
 }
 
+#[async_trait]
 impl Command for VerifyVCCommand<'_> {
-    fn execute(&mut self) -> ScreenEvent {
-        // Block on the async function using block_in_place
-        tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current().block_on(self.handle_verify_vc())
-        })
-        .unwrap_or_else(|e| {
-            println!("Error: {}", e);
-            ScreenEvent::Cancel
-        })
+    async fn execute(&mut self) -> ScreenEvent {
+        self.handle_verify_vc()
+            .await
+            .unwrap_or_else(|e| ScreenEvent::Error(e.to_string()))
     }
 
     fn print_tile(&self) {
@@ -36,148 +42,521 @@ impl Command for VerifyVCCommand<'_> {
 }
 
 impl VerifyVCCommand<'_> {
-    pub fn new(context: &AppContext) -> VerifyVCCommand {
+    pub fn new(context: &AppContext) -> VerifyVCCommand<'_> {
         VerifyVCCommand { context }
     }
 
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::VerifyVCWorkflow, |context, _| {
+            Box::new(VerifyVCCommand::new(context))
+        })
+        .menu_entry(ScreenState::MainMenu, "Verify a VC", ScreenEvent::VerifyVC)
+    }
+
     pub async fn handle_verify_vc(&self) -> Result<ScreenEvent> {
-        let vc: Vc = self.choose_vc()?;
-
-        // if vc.sd() {
-        //     println!(
-        //         "{}",
-        //         "You choose a Selective Disclosure VC.".yellow().bold()
-        //     );
-        //     println!(
-        //         "{}",
-        //         "Please use the Create VP option to verify this Selective Disclosure VC."
-        //             .yellow()
-        //             .bold()
-        //     );
-        //     Input::wait_for_user_input("Press any key to continue...");
-        //     return Ok(ScreenEvent::Success);
-        // }
-
-        // Print the VC to be verified
-        println!("Verifying the following VC:");
-        println!("{:?}", vc);
-
-        let issuer_document = self.choose_did_document().await?;
-
-        let decoded_vc = Self::verify_credential(&vc, &issuer_document);
-
-        match decoded_vc {
-            Ok(decoded_vc) => {
-                println!("{}", "VC verified successfully:".green().bold());
-                println!("{:?}", decoded_vc);
+        let input = self.context.input.as_ref();
+        let options = vec![
+            "A stored VC".to_string(),
+            "A pasted JWT or SD-JWT".to_string(),
+            "A JWT or SD-JWT file".to_string(),
+        ];
+        let (report, token) = match Menu::new("Verify VC", options)
+            .message("Which credential do you want to verify?")
+            .select(input)
+            .await
+        {
+            Some(0) => match self.verify_stored_vc().await? {
+                Some(report) => (report, None),
+                None => return Ok(ScreenEvent::Cancel),
+            },
+            Some(1) => {
+                let token = input.wait_for_user_input("Paste the token:").await;
+                (self.verify_external_token(&token).await?, Some(token))
             }
-            Err(e) => {
-                println!("Error: {}", e);
+            Some(_) => {
+                let file = input.wait_for_user_input("File with the token:").await;
+                let token = fs::read_to_string(&file)
+                    .with_context(|| format!("Cannot read the token from {}", file))?;
+                (self.verify_external_token(&token).await?, Some(token))
+            }
+            None => return Ok(ScreenEvent::Cancel),
+        };
+
+        report.display();
+        match &report.credential {
+            Some(credential) if report.valid => {
+                utils::pretty_print_json("VC verified successfully", &credential.to_string())
             }
+            _ => println!("{}", "VC verification failed".red().bold()),
         }
 
-        Input::wait_for_user_input("Press any key to continue...");
+        if let (Some(token), true) = (token, report.valid) {
+            if input
+                .wait_for_user_input("Save the VC to the wallet? (y/N)")
+                .await
+                == "y"
+            {
+                let id = self.save_token(&token, &report).await?;
+                println!("VC saved with id {}", id);
+            }
+        }
+
+        let file = input
+            .wait_for_user_input("File to export the report to as JSON (empty to skip):")
+            .await;
+        if !file.is_empty() {
+            report.export(Path::new(&file))?;
+            println!("Report written to {}", file);
+        }
+
+        input
+            .wait_for_user_input("Press any key to continue...")
+            .await;
         Ok(ScreenEvent::Success)
     }
 
-    fn verify_credential(vc: &Vc, issuer_document: &IotaDocument) -> Result<DecodedJwtCredential> {
-        let decoded_vc: DecodedJwtCredential<Object>;
-        if vc.sd() {
-            decoded_vc = Self::verify_sd_vc(vc, &issuer_document)?;
-        } else {
-            let credential_jwt = Jwt::from(vc.vc().to_string());
-            decoded_vc = Self::verify_normal_vc(&credential_jwt, &issuer_document)?;
+    async fn verify_stored_vc(&self) -> Result<Option<VerificationReport>> {
+        let Some(vc) = self.choose_vc().await? else {
+            return Ok(None);
+        };
+        let issuer = Self::stored_vc_issuer(&vc)?;
+        let resolution = Self::resolve_did(&self.context.resolver, &issuer);
+        let issuer_document = Progress::run("Resolving the issuer DID", resolution).await;
+        Ok(Some(Self::verification_report(
+            &vc,
+            &issuer,
+            issuer_document,
+            Self::status_list_directory(),
+        )))
+    }
+
+    /// The issuer DID of a stored VC, named by the `iss` claim of a JWT or SD-JWT. A JPT has no
+    /// such claim, its issuer is the one it was stored with.
+    pub fn stored_vc_issuer(vc: &Vc) -> Result<String> {
+        if vc.jpt() {
+            return Ok(vc.issuer().did().to_string());
         }
+        Self::token_issuer(&inspect_token(vc.vc(), None, None)?)
+    }
 
-        Ok(decoded_vc)
+    async fn verify_external_token(&self, token: &str) -> Result<VerificationReport> {
+        let verification =
+            Self::verify_token(&self.context.resolver, token, Self::status_list_directory());
+        Progress::run("Resolving the issuer DID", verification).await
+    }
+
+    /// Verifies a JWT or SD-JWT credential that is not stored against the DID document of the
+    /// issuer named by its `iss` claim, resolved with the `resolver`.
+    pub async fn verify_token(
+        resolver: &Resolver<CoreDocument>,
+        token: &str,
+        status_list_directory: &Path,
+    ) -> Result<VerificationReport> {
+        let issuer = Self::token_issuer(&inspect_token(token, None, None)?)?;
+        let issuer_document = Self::resolve_did(resolver, &issuer).await;
+        Self::token_verification_report(token, issuer_document, status_list_directory)
+    }
+
+    /// Verifies `vc`, whatever its format, against the `issuer_document` resolved from the
+    /// `issuer` DID. Every check runs even after one failed, see [`VerificationReport`]; the
+    /// status lists are read from the `status_list_directory`.
+    pub fn verification_report(
+        vc: &Vc,
+        issuer: &str,
+        issuer_document: Result<CoreDocument>,
+        status_list_directory: &Path,
+    ) -> VerificationReport {
+        let mut report = VerificationReport::new(Some(vc.id()), issuer, vc.format());
+        let token = CheckedToken {
+            token: vc.vc(),
+            sd: vc.sd(),
+            jpt: vc.jpt(),
+            holder: Some(vc.holder()),
+        };
+        Self::run_checks(&mut report, &token, issuer_document, status_list_directory);
+        report
+    }
+
+    /// Verifies a JWT or SD-JWT credential that is not stored, pasted or read from a file,
+    /// against the `issuer_document` resolved from its `iss` claim. Its holder is not known, so
+    /// the subject/holder relationship is skipped.
+    pub fn token_verification_report(
+        token: &str,
+        issuer_document: Result<CoreDocument>,
+        status_list_directory: &Path,
+    ) -> Result<VerificationReport> {
+        let token = token.trim();
+        let inspected = inspect_token(token, None, None)?;
+        let issuer = Self::token_issuer(&inspected)?;
+        let sd = inspected.kind != TokenKind::Jwt;
+
+        let format = if sd { "SD-JWT" } else { "JWT" };
+        let mut report = VerificationReport::new(None, &issuer, format);
+        if let Ok(issuer_document) = &issuer_document {
+            if let Some(warning) = Self::check_kid(&inspected.header, issuer_document) {
+                report.warn(warning);
+            }
+        }
+
+        let token = CheckedToken {
+            token,
+            sd,
+            jpt: false,
+            holder: None,
+        };
+        Self::run_checks(&mut report, &token, issuer_document, status_list_directory);
+        Ok(report)
+    }
+
+    fn run_checks(
+        report: &mut VerificationReport,
+        token: &CheckedToken,
+        issuer_document: Result<CoreDocument>,
+        status_list_directory: &Path,
+    ) {
+        let issuer = report.issuer.clone();
+        // A deactivated DID resolves with no methods left, say so rather than failing on the
+        // signature
+        let issuer_document = match issuer_document {
+            Ok(document) if document.verification_method().is_empty() => Err(anyhow::anyhow!(
+                "The issuer DID {} is deactivated or has no methods",
+                issuer
+            )),
+            result => result,
+        };
+        let issuer_document = match issuer_document {
+            Ok(document) => {
+                report.pass(VerificationCheck::IssuerResolution, "Resolved");
+                document
+            }
+            Err(e) => {
+                report.fail(VerificationCheck::IssuerResolution, format!("{:#}", e));
+                report.skip_all(
+                    &[
+                        VerificationCheck::SdDigests,
+                        VerificationCheck::Signature,
+                        VerificationCheck::Expiry,
+                        VerificationCheck::Status,
+                        VerificationCheck::SubjectHolder,
+                    ],
+                    "The issuer DID could not be resolved",
+                );
+                return;
+            }
+        };
+
+        if !token.sd {
+            report.skip(
+                VerificationCheck::SdDigests,
+                "Only SD-JWT VCs have disclosures",
+            );
+        } else if !report.record(
+            VerificationCheck::SdDigests,
+            Self::check_sd_digests(token.token),
+            |count| format!("The {} disclosures match the signed digests", count),
+        ) {
+            report.skip_all(
+                &[
+                    VerificationCheck::Signature,
+                    VerificationCheck::Expiry,
+                    VerificationCheck::Status,
+                    VerificationCheck::SubjectHolder,
+                ],
+                "The credential cannot be decoded with these disclosures",
+            );
+            return;
+        }
+
+        let credential = match Self::decode_credential(token, &issuer_document) {
+            Ok(credential) => {
+                report.pass(VerificationCheck::Signature, "Signed by the issuer DID");
+                credential
+            }
+            Err(e) => {
+                report.fail(VerificationCheck::Signature, validation_errors(&e));
+                report.skip_all(
+                    &[
+                        VerificationCheck::Expiry,
+                        VerificationCheck::Status,
+                        VerificationCheck::SubjectHolder,
+                    ],
+                    "The signature of the credential could not be verified",
+                );
+                return;
+            }
+        };
+
+        report.record(
+            VerificationCheck::Expiry,
+            check_validity(&credential, Timestamp::now_utc()),
+            |_| match credential.expiration_date {
+                Some(expiration_date) => format!(
+                    "Valid from {} until {}",
+                    credential.issuance_date, expiration_date
+                ),
+                None => format!("Valid from {}, never expires", credential.issuance_date),
+            },
+        );
+
+        match &credential.credential_status {
+            None => report.skip(
+                VerificationCheck::Status,
+                "The VC has no status, it cannot be revoked",
+            ),
+            Some(status) => {
+                report.record(
+                    VerificationCheck::Status,
+                    check_credential_status(&credential, &issuer_document, status_list_directory),
+                    |_| format!("Neither revoked nor suspended ({})", status.type_),
+                );
+            }
+        }
+
+        match token.holder {
+            None => report.skip(
+                VerificationCheck::SubjectHolder,
+                "The holder of a credential that is not stored is not known",
+            ),
+            Some(holder) => {
+                report.record(
+                    VerificationCheck::SubjectHolder,
+                    Self::check_subject_holder(&credential, holder),
+                    |_| format!("The subject is the holder {}", holder.name()),
+                );
+            }
+        }
+
+        report.credential = serde_json::to_value(&credential).ok();
+    }
+
+    /// Checks that every disclosure of the `sd_jwt` matches a digest of its signed payload, and
+    /// returns how many there are.
+    pub fn check_sd_digests(sd_jwt: &str) -> Result<usize> {
+        let sd_jwt = SdJwt::parse(sd_jwt)?;
+        let payload = sd_jwt
+            .jwt
+            .split('.')
+            .nth(1)
+            .ok_or_else(|| anyhow::anyhow!("The SD-JWT has no payload"))?;
+        let claims: Map<String, Value> = serde_json::from_str(&utils::decode_base64(payload)?)?;
+        SdObjectDecoder::new_with_sha256().decode(&claims, &sd_jwt.disclosures)?;
+        Ok(sd_jwt.disclosures.len())
+    }
+
+    // The subject of the VC has to be its holder, who is the one to present it
+    fn check_subject_holder(credential: &Credential, holder: &Did) -> Result<()> {
+        let holder_url = Url::parse(holder.did())?;
+        JwtCredentialValidatorUtils::check_subject_holder_relationship(
+            credential,
+            &holder_url,
+            SubjectHolderRelationship::AlwaysSubject,
+        )?;
+        Ok(())
+    }
+
+    // Checks the signature of the token, whatever its format, and decodes it
+    fn decode_credential(
+        token: &CheckedToken,
+        issuer_document: &CoreDocument,
+    ) -> Result<Credential> {
+        let credential = if token.jpt {
+            Self::verify_jpt(&Jpt::new(token.token.to_string()), issuer_document)?.credential
+        } else if token.sd {
+            Self::verify_sd_vc(token.token, &issuer_document)?.credential
+        } else {
+            let credential_jwt = Jwt::from(token.token.to_string());
+            Self::verify_normal_vc(&credential_jwt, issuer_document)?.credential
+        };
+        Ok(credential)
+    }
+
+    /// Local copies of the status lists the VCs are checked against, see [`read_status_list`].
+    ///
+    /// [`read_status_list`]: crate::read_status_list
+    pub fn status_list_directory() -> &'static Path {
+        Path::new(VariablesConfig::get().get_value("status_list_directory"))
     }
 
-    fn verify_sd_vc(vc: &Vc, issuer_document: &&IotaDocument) -> Result<DecodedJwtCredential> {
-        let sd_jwt = SdJwt::parse(&vc.vc())?;
+    fn verify_sd_vc(sd_jwt: &str, issuer_document: &&CoreDocument) -> Result<DecodedJwtCredential> {
+        let sd_jwt = SdJwt::parse(sd_jwt)?;
         let decoder = SdObjectDecoder::new_with_sha256();
         let validator =
-            SdJwtCredentialValidator::with_signature_verifier(EdDSAJwsVerifier::default(), decoder);
+            SdJwtCredentialValidator::with_signature_verifier(CompositeJwsVerifier, decoder);
         let validation = validator.validate_credential::<_, Object>(
             &sd_jwt,
             &issuer_document,
-            &JwtCredentialValidationOptions::default(),
-            FailFast::FirstError,
+            &jwt_validation_options(),
+            FailFast::AllErrors,
         )?;
         Ok(validation)
     }
 
     pub fn verify_normal_vc(
         credential_jwt: &Jwt,
-        issuer_document: &IotaDocument,
+        issuer_document: &CoreDocument,
     ) -> Result<DecodedJwtCredential> {
         let decoded_vc: DecodedJwtCredential<Object> =
-            JwtCredentialValidator::with_signature_verifier(EdDSAJwsVerifier::default())
+            JwtCredentialValidator::with_signature_verifier(CompositeJwsVerifier)
                 .validate::<_, Object>(
-                    &credential_jwt,
+                    credential_jwt,
                     &issuer_document,
-                    &JwtCredentialValidationOptions::default(),
-                    FailFast::FirstError,
+                    &jwt_validation_options(),
+                    FailFast::AllErrors,
                 )?;
         Ok(decoded_vc)
     }
 
-    pub fn choose_vc(&self) -> Result<Vc> {
+    /// Checks the BBS+ proof of a JPT credential against the `issuer_document`.
+    pub fn verify_jpt(jpt: &Jpt, issuer_document: &CoreDocument) -> Result<DecodedJptCredential> {
+        let decoded_vc = JptCredentialValidator::validate::<_, Object>(
+            jpt,
+            issuer_document,
+            &jpt_validation_options(),
+            FailFast::AllErrors,
+        )?;
+        Ok(decoded_vc)
+    }
+
+    /// `None` when the user went back.
+    pub async fn choose_vc(&self) -> Result<Option<Vc>> {
         let vcs: Vec<Vc> = self.context.db.get_stored_vcs()?;
 
         if vcs.is_empty() {
             println!("{}", "No VCs found. Please create a VC first.".red().bold());
-            Input::wait_for_user_input("Press enter to continue");
+            self.context
+                .input
+                .wait_for_user_input("Press enter to continue")
+                .await;
             return Err(anyhow::anyhow!("No VCs found"));
         }
-        let vc = self.get_vc(&vcs)?;
-        Ok(vc)
+        Ok(ListVCsCommand::select_vc(self.context, "Choose a VC to be verified").await)
     }
 
-    fn get_vc(&self, vcs: &Vec<Vc>) -> Result<Vc> {
-        let index = Output::display_with_pagination(
-            &vcs,
-            Self::choose_vc_table,
-            VariablesConfig::get().vc_table_size(),
-            true,
-            Some(Box::new(|| self.print_tile())),
-        );
-        let vc = vcs.get(index - 1);
-        match vc {
-            Some(vc) => Ok(vc.clone()),
-            None => Err(anyhow::anyhow!("Invalid index")),
-        }
+    /// The issuer DID of a JWT or SD-JWT credential, named by its `iss` claim.
+    pub fn token_issuer(inspected: &InspectedToken) -> Result<String> {
+        inspected
+            .payload
+            .get("iss")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| anyhow::anyhow!("The token has no iss claim naming its issuer"))
     }
 
-    fn choose_vc_table(vcs: &Vec<Vc>, first_row_index: usize) {
-        ListVCsCommand::display_vcs_table(vcs, first_row_index);
-        println!("Choose a VC to be verified by entering the row number:");
+    /// Tells when the `kid` in the `header` of a token is not a method of the `issuer_document`,
+    /// the token then claims to be signed with a key its issuer does not have.
+    pub fn check_kid(header: &Value, issuer_document: &CoreDocument) -> Option<String> {
+        let issuer = issuer_document.id();
+        let kid = match header.get("kid").and_then(Value::as_str) {
+            Some(kid) => kid,
+            None => return Some("The token header has no kid naming its key".to_string()),
+        };
+        // A relative kid is a method of the issuer
+        let method = if kid.starts_with('#') {
+            issuer.to_url().join(kid)
+        } else {
+            DIDUrl::parse(kid)
+        };
+        match method {
+            Err(_) => Some(format!("The kid {} is not a DID URL", kid)),
+            Ok(method) if method.did() != issuer => Some(format!(
+                "The kid {} belongs to {}, not to the issuer {}",
+                kid,
+                method.did(),
+                issuer
+            )),
+            Ok(method) if issuer_document.resolve_method(&method, None).is_none() => Some(format!(
+                "The kid {} is not a method of the issuer {}",
+                kid, issuer
+            )),
+            Ok(_) => None,
+        }
     }
 
-    async fn choose_did_document(&self) -> Result<IotaDocument> {
-        let dids: Vec<Did> = self.context.db.get_stored_dids()?;
-        let did = self.get_did_document(&dids).await?;
-        Ok(did)
+    /// Resolves the DID named by a credential.
+    pub async fn resolve_did(resolver: &Resolver<CoreDocument>, did: &str) -> Result<CoreDocument> {
+        let did = CoreDID::parse(did)?;
+        Ok(resolver.resolve(&did).await?)
     }
 
-    async fn get_did_document(&self, dids: &Vec<Did>) -> Result<IotaDocument> {
-        let index = Output::display_with_pagination(
-            &dids,
-            Self::choose_did_table,
-            VariablesConfig::get().did_table_size(),
-            true,
-            Some(Box::new(|| self.print_tile())),
-        );
-        let did = dids.get(index - 1);
-        match did {
-            Some(did) => Ok(did.resolve_to_iota_document(&self.context.resolver).await),
-            None => Err(anyhow::anyhow!("Invalid index")),
+    // Stores a verified token as a VC of the wallet, along with its issuer as a contact when it
+    // is not stored yet. The VC has to be issued to one of our DIDs, its holder.
+    async fn save_token(&self, token: &str, report: &VerificationReport) -> Result<i64> {
+        let credential: Credential = match &report.credential {
+            Some(credential) => serde_json::from_value(credential.clone())?,
+            None => anyhow::bail!("Only a verified VC can be saved"),
+        };
+
+        let subject = credential
+            .credential_subject
+            .iter()
+            .find_map(|subject| subject.id.as_ref())
+            .ok_or_else(|| anyhow::anyhow!("The VC has no subject to be the holder"))?;
+        let holder = match self.context.db.find_did(subject.as_str())? {
+            Some(holder) if holder.owned() => holder,
+            _ => anyhow::bail!(
+                "The VC is issued to {}, which is not one of your DIDs",
+                subject
+            ),
+        };
+
+        let issuer = match self.context.db.find_did(&report.issuer)? {
+            Some(issuer) => issuer.id(),
+            None => {
+                let document = Self::resolve_did(&self.context.resolver, &report.issuer).await?;
+                println!("The issuer {} is saved as a contact", report.issuer);
+                let label = ContactsCommand::read_label(self.context.input.as_ref(), None).await;
+                self.context
+                    .db
+                    .save_contact(&document, &label, &[ContactRole::Issuer])?
+            }
+        };
+
+        // A presentation is stored without the key-binding JWT of its holder
+        let token = token.trim();
+        let (token, sd) = match token.rsplit_once('~') {
+            Some((sd_jwt, _)) => (format!("{}~", sd_jwt), true),
+            None => (token.to_string(), false),
+        };
+        let credential_type = credential
+            .types
+            .iter()
+            .find(|credential_type| *credential_type != Credential::<Object>::base_type())
+            .cloned()
+            .unwrap_or_else(|| Credential::<Object>::base_type().to_string());
+
+        let id = self
+            .context
+            .db
+            .save_vc(&token, issuer, holder.id(), &credential_type, sd)?;
+        let validity = Validity {
+            valid_from: credential.issuance_date,
+            expires_at: credential.expiration_date,
+        };
+        if let Some(expires_at) = validity.expiration() {
+            self.context.db.set_vc_expiration(id, expires_at)?;
         }
+        Ok(id)
     }
+}
 
-    fn choose_did_table(dids: &Vec<Did>, first_row_index: usize) {
-        ListDIDsCommand::display_dids_table(dids, first_row_index);
-        println!(
-            "Choose a DID to verify as the issuer of the credential by entering the row number:"
-        );
+// Every error the validator collected, one after the other
+fn validation_errors(error: &anyhow::Error) -> String {
+    match error.downcast_ref::<CompoundCredentialValidationError>() {
+        Some(compound) => compound
+            .validation_errors
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<String>>()
+            .join("; "),
+        None => format!("{:#}", error),
     }
 }
+
+/// What the checks need of a credential, stored or not.
+struct CheckedToken<'a> {
+    token: &'a str,
+    sd: bool,
+    jpt: bool,
+    /// Who the VC was stored for, unknown for a token that is not stored.
+    holder: Option<&'a Did>,
+}
@@ -1,16 +1,27 @@
-use crate::{Command, Input, Output, ScreenEvent};
+use crate::{AppContext, Command, CommandRegistration, Menu, Output, ScreenEvent, ScreenState};
+use async_trait::async_trait;
 
-pub struct CreateVCMenu{
+pub struct CreateVCMenu<'a> {
+    context: &'a AppContext,
     options: Vec<(String, ScreenEvent)>,
 }
 
-impl Command for CreateVCMenu {
-    fn execute(&mut self) -> ScreenEvent {
+#[async_trait]
+impl Command for CreateVCMenu<'_> {
+    async fn execute(&mut self) -> ScreenEvent {
         self.print_tile();
-        Output::print_options_vec(&self.options);
-        println!("\nPlease select an option:");
-        let user_input = Input::get_number_input(1, self.options.len());
-        self.options[user_input - 1].1.to_owned()
+        let labels = self
+            .options
+            .iter()
+            .map(|(label, _)| label.clone())
+            .collect();
+        match Menu::new("Create Verifiable Credential", labels)
+            .select(self.context.input.as_ref())
+            .await
+        {
+            Some(index) => self.options[index].1.clone(),
+            None => ScreenEvent::Cancel,
+        }
     }
 
     fn print_tile(&self) {
@@ -19,14 +30,23 @@ impl Command for CreateVCMenu {
     }
 }
 
-impl CreateVCMenu {
-    
-    pub fn new() -> CreateVCMenu {
-        let mut options = Vec::new();
-        options.push(("Create Verifiable Credential".to_string(), ScreenEvent::CreateNormalVC));
-        options.push(("Create Verifiable Credential with Selective Disclosure".to_string(), ScreenEvent::CreateSDVC));
-        options.push(("Back".to_string(), ScreenEvent::Cancel));
-        CreateVCMenu { options }
+impl CreateVCMenu<'_> {
+    pub fn new(context: &AppContext, options: Vec<(String, ScreenEvent)>) -> CreateVCMenu<'_> {
+        CreateVCMenu { context, options }
     }
-    
-}
\ No newline at end of file
+
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::CreateVCMenu, |context, fsm| {
+            Box::new(CreateVCMenu::new(
+                context,
+                fsm.registry().menu_options(&ScreenState::CreateVCMenu),
+            ))
+        })
+        .title("Create Verifiable Credential")
+        .menu_entry(
+            ScreenState::MainMenu,
+            "Create a new VC",
+            ScreenEvent::CreateVC,
+        )
+    }
+}
@@ -1,18 +1,28 @@
-use crate::{Command, Input, Output, ScreenEvent};
+use crate::{AppContext, Command, CommandRegistration, Menu, Output, ScreenEvent, ScreenState};
+use async_trait::async_trait;
 
-pub struct ListCreatedItems {
+pub struct ListCreatedItems<'a> {
+    context: &'a AppContext,
     // a vector of options that the user can select (String, ScreenEvent)
     options: Vec<(String, ScreenEvent)>,
 }
 
-impl Command for ListCreatedItems {
-    fn execute(&mut self) -> ScreenEvent {
+#[async_trait]
+impl Command for ListCreatedItems<'_> {
+    async fn execute(&mut self) -> ScreenEvent {
         self.print_tile();
-        Output::print_options_vec(&self.options);
-        println!("\nPlease select an option:");
-
-        let user_input = Input::get_number_input(1, self.options.len());
-        self.options[user_input - 1].1.clone()
+        let labels = self
+            .options
+            .iter()
+            .map(|(label, _)| label.clone())
+            .collect();
+        match Menu::new("List Items", labels)
+            .select(self.context.input.as_ref())
+            .await
+        {
+            Some(index) => self.options[index].1.clone(),
+            None => ScreenEvent::Cancel,
+        }
     }
 
     fn print_tile(&self) {
@@ -21,13 +31,23 @@ impl Command for ListCreatedItems {
     }
 }
 
-impl ListCreatedItems {
-    pub fn new() -> ListCreatedItems {
-        let mut options = Vec::new();
-        options.push(("List DIDs".to_string(), ScreenEvent::ListDIDs));
-        options.push(("List VCs".to_string(), ScreenEvent::ListVCs));
-        options.push(("Back".to_string(), ScreenEvent::Cancel));
-        ListCreatedItems { options }
+impl ListCreatedItems<'_> {
+    pub fn new(context: &AppContext, options: Vec<(String, ScreenEvent)>) -> ListCreatedItems<'_> {
+        ListCreatedItems { context, options }
+    }
+
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::ListItemsMenu, |context, fsm| {
+            Box::new(ListCreatedItems::new(
+                context,
+                fsm.registry().menu_options(&ScreenState::ListItemsMenu),
+            ))
+        })
+        .title("List Items")
+        .menu_entry(
+            ScreenState::MainMenu,
+            "List Created Items",
+            ScreenEvent::ListItems,
+        )
     }
-    
 }
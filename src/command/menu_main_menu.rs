@@ -1,19 +1,28 @@
-use crate::{Command, Input, Output, ScreenEvent};
+use crate::{AppContext, Command, CommandRegistration, Menu, Output, ScreenEvent, ScreenState};
+use async_trait::async_trait;
 
-pub struct MainMenuCommand {
+pub struct MainMenuCommand<'a> {
+    context: &'a AppContext,
     // a vector of options that the user can select (String, ScreenEvent)
     options: Vec<(String, ScreenEvent)>,
 }
 
-impl Command for MainMenuCommand {
-    fn execute(&mut self) -> ScreenEvent {
+#[async_trait]
+impl Command for MainMenuCommand<'_> {
+    async fn execute(&mut self) -> ScreenEvent {
         self.print_tile();
-        Output::print_options_vec(&self.options);
-        println!("\nPlease select an option:");
-
-        let user_input = Input::get_number_input(1, self.options.len());
-        println!("User input: {}", user_input);
-        self.options[user_input - 1].1.clone()
+        let labels = self
+            .options
+            .iter()
+            .map(|(label, _)| label.clone())
+            .collect();
+        match Menu::new("Main Menu", labels)
+            .select(self.context.input.as_ref())
+            .await
+        {
+            Some(index) => self.options[index].1.clone(),
+            None => ScreenEvent::Cancel,
+        }
     }
 
     fn print_tile(&self) {
@@ -22,17 +31,18 @@ impl Command for MainMenuCommand {
     }
 }
 
-impl MainMenuCommand {
-    pub fn new() -> MainMenuCommand {
-        let options = vec![
-            ("List Created Items".to_string(), ScreenEvent::ListItems),
-            ("Create a new DID".to_string(), ScreenEvent::CreateDID),
-            ("Create a new VC".to_string(), ScreenEvent::CreateVC),
-            ("Create a new VP".to_string(), ScreenEvent::CreateVP),
-            ("Verify a VC".to_string(), ScreenEvent::VerifyVC),
-            ("Exit".to_string(), ScreenEvent::Cancel),
-        ];
+impl MainMenuCommand<'_> {
+    pub fn new(context: &AppContext, options: Vec<(String, ScreenEvent)>) -> MainMenuCommand<'_> {
+        MainMenuCommand { context, options }
+    }
 
-        MainMenuCommand { options }
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::MainMenu, |context, fsm| {
+            Box::new(MainMenuCommand::new(
+                context,
+                fsm.registry().menu_options(&ScreenState::MainMenu),
+            ))
+        })
+        .title("Main Menu")
     }
 }
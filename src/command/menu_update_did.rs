@@ -0,0 +1,52 @@
+use crate::{AppContext, Command, CommandRegistration, Menu, Output, ScreenEvent, ScreenState};
+use async_trait::async_trait;
+
+pub struct UpdateDIDMenu<'a> {
+    context: &'a AppContext,
+    options: Vec<(String, ScreenEvent)>,
+}
+
+#[async_trait]
+impl Command for UpdateDIDMenu<'_> {
+    async fn execute(&mut self) -> ScreenEvent {
+        self.print_tile();
+        let labels = self
+            .options
+            .iter()
+            .map(|(label, _)| label.clone())
+            .collect();
+        match Menu::new("Update DID", labels)
+            .select(self.context.input.as_ref())
+            .await
+        {
+            Some(index) => self.options[index].1.clone(),
+            None => ScreenEvent::Cancel,
+        }
+    }
+
+    fn print_tile(&self) {
+        Output::clear_screen();
+        Output::print_screen_title("Update DID")
+    }
+}
+
+impl UpdateDIDMenu<'_> {
+    pub fn new(context: &AppContext, options: Vec<(String, ScreenEvent)>) -> UpdateDIDMenu<'_> {
+        UpdateDIDMenu { context, options }
+    }
+
+    pub fn registration() -> CommandRegistration {
+        CommandRegistration::new(ScreenState::UpdateDIDMenu, |context, fsm| {
+            Box::new(UpdateDIDMenu::new(
+                context,
+                fsm.registry().menu_options(&ScreenState::UpdateDIDMenu),
+            ))
+        })
+        .title("Update DID")
+        .menu_entry(
+            ScreenState::MainMenu,
+            "Update a DID",
+            ScreenEvent::UpdateDID,
+        )
+    }
+}
@@ -1,25 +1,57 @@
 pub use command::*;
+pub use command_add_methods::*;
+pub use command_contacts::*;
 pub use command_create_did::*;
+pub use command_create_vc_jpt::*;
 pub use command_create_vc_normal::*;
 pub use command_create_vc_sd::*;
 pub use command_create_vp::*;
+pub use command_deactivate_did::*;
+pub use command_destroy_did::*;
+pub use command_did_details::*;
+pub use command_error::*;
 pub use command_exit_app::*;
+pub use command_inspect_token::*;
 pub use command_list_dids::*;
 pub use command_list_vcs::*;
+pub use command_manage_services::*;
+pub use command_registry::*;
+pub use command_remove_retired_keys::*;
+pub use command_revocation::*;
+pub use command_rotate_key::*;
+pub use command_status_lists::*;
+pub use command_switch_network::*;
 pub use command_verify_vc::*;
 pub use menu_create_vc::*;
 pub use menu_list_created_items::*;
 pub use menu_main_menu::*;
+pub use menu_update_did::*;
 
 mod command;
+mod command_add_methods;
+mod command_contacts;
 mod command_create_did;
+mod command_create_vc_jpt;
 mod command_create_vc_normal;
 mod command_create_vc_sd;
 mod command_create_vp;
+mod command_deactivate_did;
+mod command_destroy_did;
+mod command_did_details;
+mod command_error;
 mod command_exit_app;
+mod command_inspect_token;
 mod command_list_dids;
 mod command_list_vcs;
+mod command_manage_services;
+mod command_registry;
+mod command_remove_retired_keys;
+mod command_revocation;
+mod command_rotate_key;
+mod command_status_lists;
+mod command_switch_network;
 mod command_verify_vc;
 mod menu_list_created_items;
 mod menu_main_menu;
 mod menu_create_vc;
+mod menu_update_did;
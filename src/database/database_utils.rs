@@ -1,4 +1,4 @@
-use crate::SQLiteConnector;
+use crate::{SQLiteConnector, DEFAULT_NETWORK_PROFILE};
 use anyhow::Result;
 
 pub fn create_did_table(sqlite: &SQLiteConnector) -> Result<usize> {
@@ -8,7 +8,12 @@ pub fn create_did_table(sqlite: &SQLiteConnector) -> Result<usize> {
                 created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP, 
                 did TEXT NOT NULL,                                      
                 fragment TEXT,
-                name TEXT                                            
+                name TEXT,
+                status TEXT NOT NULL DEFAULT 'active',
+                method TEXT NOT NULL DEFAULT 'iota',
+                network TEXT,
+                owned BOOLEAN NOT NULL DEFAULT TRUE,
+                roles TEXT
         )"#;
     
     
@@ -25,16 +30,179 @@ pub fn create_vc_table(sqlite: &SQLiteConnector) -> Result<usize> {
                 issuer INTEGER NOT NULL,
                 holder INTEGER NOT NULL,
                 sd BOOLEAN DEFAULT FALSE,
+                jpt BOOLEAN NOT NULL DEFAULT FALSE,
+                status_index INTEGER,
+                status_list INTEGER,
+                revoked BOOLEAN NOT NULL DEFAULT FALSE,
+                expires_at TIMESTAMP,
                 FOREIGN KEY (issuer) REFERENCES dids(id),
-                FOREIGN KEY (holder) REFERENCES dids(id)                                            
+                FOREIGN KEY (holder) REFERENCES dids(id),
+                FOREIGN KEY (status_list) REFERENCES status_lists(id)                                            
         )"#;
     
     sqlite.execute(sql_query, [])
 }
 
+pub fn create_status_list_table(sqlite: &SQLiteConnector) -> Result<usize> {
+    let sql_query = r#"
+        CREATE TABLE IF NOT EXISTS status_lists (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                issuer INTEGER NOT NULL,
+                purpose TEXT NOT NULL,
+                url TEXT NOT NULL UNIQUE,
+                credential TEXT NOT NULL,
+                jwt TEXT NOT NULL,
+                updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (issuer) REFERENCES dids(id)
+        )"#;
+
+    sqlite.execute(sql_query, [])
+}
+
+pub fn create_did_method_table(sqlite: &SQLiteConnector) -> Result<usize> {
+    let sql_query = r#"
+        CREATE TABLE IF NOT EXISTS did_methods (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                did_id INTEGER NOT NULL,
+                fragment TEXT NOT NULL,
+                scope TEXT NOT NULL,
+                alg TEXT NOT NULL DEFAULT 'EdDSA',
+                retire_at TIMESTAMP,
+                removed BOOLEAN NOT NULL DEFAULT FALSE,
+                FOREIGN KEY (did_id) REFERENCES dids(id)
+        )"#;
+
+    sqlite.execute(sql_query, [])
+}
+
+pub fn create_did_service_table(sqlite: &SQLiteConnector) -> Result<usize> {
+    let sql_query = r#"
+        CREATE TABLE IF NOT EXISTS did_services (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                did_id INTEGER NOT NULL,
+                fragment TEXT NOT NULL,
+                type TEXT NOT NULL,
+                endpoint TEXT NOT NULL,
+                UNIQUE (did_id, fragment),
+                FOREIGN KEY (did_id) REFERENCES dids(id)
+        )"#;
+
+    sqlite.execute(sql_query, [])
+}
+
+/// Adds `column` to `table`, declared with `definition`, unless the table already has it.
+/// Returns whether the column was added, e.g. to fill it for the existing rows.
+pub fn add_column_if_missing(
+    sqlite: &SQLiteConnector,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<bool> {
+    if sqlite.has_column(table, column)? {
+        return Ok(false);
+    }
+
+    let sql_query = format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition);
+    sqlite.execute(&sql_query, [])?;
+    Ok(true)
+}
+
+/// Adds the `status` column to the `dids` tables created before DIDs could be deactivated.
+pub fn migrate_did_status(sqlite: &SQLiteConnector) -> Result<()> {
+    add_column_if_missing(sqlite, "dids", "status", "TEXT NOT NULL DEFAULT 'active'")?;
+    Ok(())
+}
+
+/// Adds the `method` column to the `dids` tables created when every DID was an IOTA one.
+pub fn migrate_did_method_name(sqlite: &SQLiteConnector) -> Result<()> {
+    add_column_if_missing(sqlite, "dids", "method", "TEXT NOT NULL DEFAULT 'iota'")?;
+    Ok(())
+}
+
+/// Adds the `network` column to the `dids` tables created before the network profiles. Their
+/// IOTA DIDs were published with the nodes of the environment, the `default` profile.
+pub fn migrate_did_network(sqlite: &SQLiteConnector) -> Result<()> {
+    if add_column_if_missing(sqlite, "dids", "network", "TEXT")? {
+        sqlite.execute(
+            "UPDATE dids SET network = ?1 WHERE method = 'iota'",
+            [DEFAULT_NETWORK_PROFILE],
+        )?;
+    }
+    Ok(())
+}
+
+/// Adds the `owned` and `roles` columns to the `dids` tables created before the contacts, when
+/// every stored DID was one of ours.
+pub fn migrate_did_contacts(sqlite: &SQLiteConnector) -> Result<()> {
+    add_column_if_missing(sqlite, "dids", "owned", "BOOLEAN NOT NULL DEFAULT TRUE")?;
+    add_column_if_missing(sqlite, "dids", "roles", "TEXT")?;
+    Ok(())
+}
+
+/// Adds the `alg` column to the `did_methods` tables created when every key was an Ed25519 one.
+pub fn migrate_did_method_alg(sqlite: &SQLiteConnector) -> Result<()> {
+    add_column_if_missing(sqlite, "did_methods", "alg", "TEXT NOT NULL DEFAULT 'EdDSA'")?;
+    Ok(())
+}
+
+/// Adds the `jpt` column to the `vcs` tables created before JPT credentials could be issued.
+pub fn migrate_vc_jpt(sqlite: &SQLiteConnector) -> Result<()> {
+    add_column_if_missing(sqlite, "vcs", "jpt", "BOOLEAN NOT NULL DEFAULT FALSE")?;
+    Ok(())
+}
+
+/// Adds the `status_index` and `revoked` columns to the `vcs` tables created before VCs could
+/// be revoked. None of their VCs can be.
+pub fn migrate_vc_revocation(sqlite: &SQLiteConnector) -> Result<()> {
+    add_column_if_missing(sqlite, "vcs", "status_index", "INTEGER")?;
+    add_column_if_missing(sqlite, "vcs", "revoked", "BOOLEAN NOT NULL DEFAULT FALSE")?;
+    Ok(())
+}
+
+/// Adds the `status_list` column to the `vcs` tables created when the revocation bitmap was the
+/// only place to revoke a VC in.
+pub fn migrate_vc_status_list(sqlite: &SQLiteConnector) -> Result<()> {
+    add_column_if_missing(sqlite, "vcs", "status_list", "INTEGER REFERENCES status_lists(id)")?;
+    Ok(())
+}
+
+/// Adds the `expires_at` column to the `vcs` tables created before VCs could expire. None of
+/// their VCs does.
+pub fn migrate_vc_expiration(sqlite: &SQLiteConnector) -> Result<()> {
+    add_column_if_missing(sqlite, "vcs", "expires_at", "TIMESTAMP")?;
+    Ok(())
+}
+
+/// Records the single fragment of the DIDs saved before the `did_methods` table existed.
+pub fn migrate_did_fragments(sqlite: &SQLiteConnector) -> Result<usize> {
+    let sql_query = r#"
+        INSERT INTO did_methods (did_id, fragment, scope, created_at)
+        SELECT id, fragment, 'VerificationMethod', created_at FROM dids
+        WHERE fragment IS NOT NULL AND id NOT IN (SELECT did_id FROM did_methods)
+        "#;
+
+    sqlite.execute(sql_query, [])
+}
+
 pub fn create_database_tables(sqlite: &SQLiteConnector) -> Result<()> {
     create_did_table(sqlite)?;
+    migrate_did_status(sqlite)?;
+    migrate_did_method_name(sqlite)?;
+    migrate_did_network(sqlite)?;
+    migrate_did_contacts(sqlite)?;
     create_vc_table(sqlite)?;
+    migrate_vc_jpt(sqlite)?;
+    migrate_vc_revocation(sqlite)?;
+    create_status_list_table(sqlite)?;
+    migrate_vc_status_list(sqlite)?;
+    migrate_vc_expiration(sqlite)?;
+    create_did_method_table(sqlite)?;
+    migrate_did_method_alg(sqlite)?;
+    migrate_did_fragments(sqlite)?;
+    create_did_service_table(sqlite)?;
     Ok(())
 }
     
\ No newline at end of file
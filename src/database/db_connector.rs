@@ -1,17 +1,136 @@
-use crate::{Did, Vc};
+use crate::{
+    ContactRole, Did, DidMethod, DidQuery, DidStatus, StatusList, StatusListPurpose, Vc, VcQuery,
+};
 use anyhow::Result;
+use chrono::NaiveDateTime;
+use identity_iota::document::CoreDocument;
 use identity_iota::iota::IotaDocument;
 
-pub trait DBConnector {
-    fn save_did_document(&self, did: &IotaDocument, owner: &String) -> Result<usize>;
+// The `save_*` methods return the id of the inserted row
+pub trait DBConnector: Send + Sync {
+    fn save_did_document(&self, did: &IotaDocument, owner: &str) -> Result<i64> {
+        self.save_did(did.core_document(), owner)
+    }
+
+    /// Stores a DID of any method, along with its methods and services.
+    fn save_did(&self, did: &CoreDocument, owner: &str) -> Result<i64>;
+
+    /// Stores a DID controlled by someone else under `label`, along with its methods and
+    /// services. Contacts can verify or be verified, but never sign.
+    fn save_contact(&self, did: &CoreDocument, label: &str, roles: &[ContactRole]) -> Result<i64>;
+
+    /// Changes the label and the roles of the contact `did_id`.
+    fn update_contact(&self, did_id: i64, label: &str, roles: &[ContactRole]) -> Result<()>;
+
+    /// Replaces the methods and services recorded for the contact `did_id` with the ones of its
+    /// newly resolved document.
+    fn refresh_contact(&self, did_id: i64, did: &CoreDocument) -> Result<()>;
 
     fn get_did_from_id(&self, id: i64) -> Result<Did>;
 
-    fn get_stored_dids(&self) -> Result<Vec<Did>>;
+    fn get_stored_dids(&self) -> Result<Vec<Did>> {
+        self.search_dids(&DidQuery::default())
+    }
+
+    fn search_dids(&self, query: &DidQuery) -> Result<Vec<Did>>;
+
+    /// The stored DID, ours or a contact, whose DID string is `did`.
+    fn find_did(&self, did: &str) -> Result<Option<Did>> {
+        let query = DidQuery {
+            text: did.to_string(),
+            ..DidQuery::default()
+        };
+        let stored = self.search_dids(&query)?;
+        Ok(stored.into_iter().find(|stored| stored.did() == did))
+    }
+
+    /// Records that the stored DID `did_id` was deactivated or destroyed. The row is kept.
+    fn set_did_status(&self, did_id: i64, status: DidStatus) -> Result<()>;
+
+    /// Records the network profile the stored IOTA DID `did_id` was published with.
+    fn set_did_network(&self, did_id: i64, network: &str) -> Result<()>;
+
+    /// Records a method added to the stored DID `did_id` after it was saved, with the JWS
+    /// algorithm of its key.
+    fn save_did_method(&self, did_id: i64, fragment: &str, scope: &str, alg: &str) -> Result<i64>;
+
+    /// Every method recorded for the stored DID `did_id`, oldest first.
+    fn get_did_methods(&self, did_id: i64) -> Result<Vec<DidMethod>>;
+
+    /// Changes the fragment of the method the stored DID `did_id` signs with.
+    fn set_did_fragment(&self, did_id: i64, fragment: &str) -> Result<()>;
+
+    /// Schedules the removal of a method from its document in `grace_days` days.
+    fn retire_did_method(&self, method_id: i64, grace_days: u32) -> Result<()>;
+
+    /// Marks a method as removed from its document.
+    fn remove_did_method(&self, method_id: i64) -> Result<()>;
+
+    /// Methods of every stored DID whose removal is due and that were not removed yet.
+    fn get_due_did_methods(&self) -> Result<Vec<DidMethod>>;
 
-    fn save_vc(&self, vc: &str, issuer: i64, holder: i64, tp: &String, sd: bool) -> Result<usize>;
+    /// Records a service of the stored DID `did_id`, replacing the one with the same fragment.
+    fn save_did_service(
+        &self,
+        did_id: i64,
+        fragment: &str,
+        service_type: &str,
+        endpoint: &str,
+    ) -> Result<()>;
+
+    /// Forgets the service `fragment` of the stored DID `did_id`.
+    fn remove_did_service(&self, did_id: i64, fragment: &str) -> Result<()>;
+
+    fn save_vc(&self, vc: &str, issuer: i64, holder: i64, tp: &str, sd: bool) -> Result<i64>;
+
+    /// Stores a JSON Proof Token credential, signed with a BBS+ key of the issuer.
+    fn save_jpt_vc(&self, jpt: &str, issuer: i64, holder: i64, tp: &str) -> Result<i64>;
+
+    /// First index of the revocation bitmap of `issuer` not given to any of its VCs yet.
+    fn next_status_index(&self, issuer: i64) -> Result<u32>;
+
+    /// Records the index of the VC `vc_id` in the `status_list`, or in the revocation bitmap of
+    /// its issuer without a list.
+    fn set_vc_status_index(
+        &self,
+        vc_id: i64,
+        status_index: u32,
+        status_list: Option<i64>,
+    ) -> Result<()>;
+
+    /// Records that the VC `vc_id` was revoked or unrevoked, or suspended or reinstated when
+    /// it is in a suspension list. Only revocable VCs can be.
+    fn set_vc_revoked(&self, vc_id: i64, revoked: bool) -> Result<()>;
+
+    /// Records when the VC `vc_id` expires, in UTC.
+    fn set_vc_expiration(&self, vc_id: i64, expires_at: NaiveDateTime) -> Result<()>;
+
+    /// Stores the StatusList2021 credential of `issuer` hosted at `url`, with its signed `jwt`.
+    fn save_status_list(
+        &self,
+        issuer: i64,
+        purpose: StatusListPurpose,
+        url: &str,
+        credential: &str,
+        jwt: &str,
+    ) -> Result<i64>;
+
+    /// Replaces the credential of the status list `id` once changed and signed again.
+    fn update_status_list(&self, id: i64, credential: &str, jwt: &str) -> Result<()>;
+
+    fn get_status_list(&self, id: i64) -> Result<StatusList>;
+
+    /// The status lists of `issuer`, or of every issuer.
+    fn get_status_lists(&self, issuer: Option<i64>) -> Result<Vec<StatusList>>;
+
+    /// First index of the `status_list` not given to any VC yet.
+    fn next_status_list_index(&self, status_list: i64) -> Result<u32>;
 
     fn get_vc_from_id(&self, id: i64) -> Result<Vc>;
 
-    fn get_stored_vcs(&self) -> Result<Vec<Vc>>;
+    fn get_stored_vcs(&self) -> Result<Vec<Vc>> {
+        self.search_vcs(&VcQuery::default())
+    }
+
+    fn search_vcs(&self, query: &VcQuery) -> Result<Vec<Vc>>;
 }
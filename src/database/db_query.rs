@@ -0,0 +1,186 @@
+use crate::{ContactRole, DidStatus, TableQuery};
+
+/// Column the DIDs are sorted by.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum DidSortColumn {
+    #[default]
+    CreatedAt,
+    Name,
+}
+
+/// Column the VCs are sorted by.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum VcSortColumn {
+    #[default]
+    CreatedAt,
+    Type,
+    Sd,
+}
+
+/// Filter and sort of the DIDs, applied by the database.
+#[derive(Clone, Debug, Default)]
+pub struct DidQuery {
+    /// Matched against the name and the DID string. Empty matches every DID.
+    pub text: String,
+    pub sort: DidSortColumn,
+    pub descending: bool,
+    /// Only our DIDs with `Some(true)`, only the contacts with `Some(false)`.
+    pub owned: Option<bool>,
+    /// Leaves out the contacts without this role, our DIDs are kept.
+    pub role: Option<ContactRole>,
+    /// Only the DIDs with this status.
+    pub status: Option<DidStatus>,
+}
+
+impl DidQuery {
+    /// Our DIDs, whatever their status.
+    pub fn owned() -> Self {
+        DidQuery {
+            owned: Some(true),
+            ..DidQuery::default()
+        }
+    }
+
+    /// Our DIDs that were neither deactivated nor destroyed, the only ones that can sign.
+    pub fn active() -> Self {
+        DidQuery {
+            status: Some(DidStatus::Active),
+            ..DidQuery::owned()
+        }
+    }
+
+    pub fn contacts() -> Self {
+        DidQuery {
+            owned: Some(false),
+            ..DidQuery::default()
+        }
+    }
+
+    /// Our DIDs and the contacts that can be chosen as `role`.
+    pub fn with_role(role: ContactRole) -> Self {
+        DidQuery {
+            role: Some(role),
+            ..DidQuery::default()
+        }
+    }
+}
+
+/// Filter and sort of the VCs, applied by the database.
+#[derive(Clone, Debug, Default)]
+pub struct VcQuery {
+    /// Matched against the type and the name and DID string of the issuer and the holder.
+    /// Empty matches every VC.
+    pub text: String,
+    pub sort: VcSortColumn,
+    pub descending: bool,
+}
+
+impl DidSortColumn {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DidSortColumn::CreatedAt => "created",
+            DidSortColumn::Name => "name",
+        }
+    }
+
+    pub(crate) fn sql_column(&self) -> &'static str {
+        match self {
+            DidSortColumn::CreatedAt => "created_at",
+            DidSortColumn::Name => "name",
+        }
+    }
+}
+
+impl VcSortColumn {
+    pub fn label(&self) -> &'static str {
+        match self {
+            VcSortColumn::CreatedAt => "created",
+            VcSortColumn::Type => "type",
+            VcSortColumn::Sd => "SD",
+        }
+    }
+
+    pub(crate) fn sql_column(&self) -> &'static str {
+        match self {
+            VcSortColumn::CreatedAt => "vcs.created_at",
+            VcSortColumn::Type => "vcs.type",
+            VcSortColumn::Sd => "vcs.sd",
+        }
+    }
+}
+
+/// Pattern matching the values that contain `text`, for a `LIKE ... ESCAPE '\'` clause.
+pub(crate) fn like_pattern(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+pub(crate) fn sql_order(descending: bool) -> &'static str {
+    if descending {
+        "DESC"
+    } else {
+        "ASC"
+    }
+}
+
+impl TableQuery for DidQuery {
+    fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn text_mut(&mut self) -> &mut String {
+        &mut self.text
+    }
+
+    fn next_sort(&mut self) {
+        self.sort = match self.sort {
+            DidSortColumn::CreatedAt => DidSortColumn::Name,
+            DidSortColumn::Name => DidSortColumn::CreatedAt,
+        };
+    }
+
+    fn reverse(&mut self) {
+        self.descending = !self.descending;
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "{} {}",
+            self.sort.label(),
+            sql_order(self.descending).to_lowercase()
+        )
+    }
+}
+
+impl TableQuery for VcQuery {
+    fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn text_mut(&mut self) -> &mut String {
+        &mut self.text
+    }
+
+    fn next_sort(&mut self) {
+        self.sort = match self.sort {
+            VcSortColumn::CreatedAt => VcSortColumn::Type,
+            VcSortColumn::Type => VcSortColumn::Sd,
+            VcSortColumn::Sd => VcSortColumn::CreatedAt,
+        };
+    }
+
+    fn reverse(&mut self) {
+        self.descending = !self.descending;
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "{} {}",
+            self.sort.label(),
+            sql_order(self.descending).to_lowercase()
+        )
+    }
+}
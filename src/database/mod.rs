@@ -1,10 +1,12 @@
 pub use sqlite_conn::*;
 pub use database_utils::*;
 pub use db_connector::*;
+pub use db_query::*;
 pub use models::*;
 
 
 mod sqlite_conn;
 mod database_utils;
 mod db_connector;
+mod db_query;
 mod models;
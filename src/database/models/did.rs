@@ -1,26 +1,154 @@
-use std::fmt::Debug;
+use std::fmt::{Debug, Display, Formatter};
+use anyhow::Result;
 use chrono::naive::NaiveDateTime;
-use identity_iota::iota::{IotaDID, IotaDocument};
+use identity_iota::did::CoreDID;
+use identity_iota::document::CoreDocument;
+use identity_iota::iota::IotaDID;
 use identity_iota::prelude::Resolver;
+use serde::Serialize;
 
 
-#[derive(Debug, Clone, Default)]
+/// Whether a stored DID can still be resolved and used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DidStatus {
+    #[default]
+    Active,
+    /// Still on the ledger, but resolves as deactivated and without methods.
+    Deactivated,
+    /// Its Alias Output was destroyed and the storage deposit reclaimed.
+    Destroyed,
+}
+
+impl DidStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DidStatus::Active => "active",
+            DidStatus::Deactivated => "deactivated",
+            DidStatus::Destroyed => "destroyed",
+        }
+    }
+
+    pub fn parse(status: &str) -> Result<Self> {
+        match status {
+            "active" => Ok(DidStatus::Active),
+            "deactivated" => Ok(DidStatus::Deactivated),
+            "destroyed" => Ok(DidStatus::Destroyed),
+            _ => Err(anyhow::anyhow!("Unknown DID status: {}", status)),
+        }
+    }
+}
+
+impl Display for DidStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What a contact, a DID controlled by someone else, can be chosen as.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContactRole {
+    Issuer,
+    Holder,
+    Verifier,
+}
+
+impl ContactRole {
+    pub fn all() -> [ContactRole; 3] {
+        [
+            ContactRole::Issuer,
+            ContactRole::Holder,
+            ContactRole::Verifier,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContactRole::Issuer => "issuer",
+            ContactRole::Holder => "holder",
+            ContactRole::Verifier => "verifier",
+        }
+    }
+
+    pub fn parse(role: &str) -> Result<Self> {
+        match role.trim().to_lowercase().as_str() {
+            "issuer" => Ok(ContactRole::Issuer),
+            "holder" => Ok(ContactRole::Holder),
+            "verifier" => Ok(ContactRole::Verifier),
+            _ => Err(anyhow::anyhow!("Unknown contact role: {}", role.trim())),
+        }
+    }
+
+    /// Roles separated by commas, as stored in the database. Blank means no role.
+    pub fn parse_list(roles: &str) -> Result<Vec<Self>> {
+        let mut parsed = Vec::new();
+        for role in roles.split(',').filter(|role| !role.trim().is_empty()) {
+            let role = ContactRole::parse(role)?;
+            if !parsed.contains(&role) {
+                parsed.push(role);
+            }
+        }
+        Ok(parsed)
+    }
+
+    pub fn join(roles: &[ContactRole]) -> String {
+        roles
+            .iter()
+            .map(|role| role.as_str())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+impl Display for ContactRole {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct Did {
     id: i64,
     did: String,
     fragment: String,
     name: String,
+    status: DidStatus,
+    method: String,
+    network: Option<String>,
+    owned: bool,
+    roles: Vec<ContactRole>,
     created_at: NaiveDateTime,
+    services: Vec<String>,
 }
 
 impl Did {
-    pub fn new(id: i64, did: String, fragment: String, name: String, created_at: NaiveDateTime) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: i64,
+        did: String,
+        fragment: String,
+        name: String,
+        status: DidStatus,
+        method: String,
+        network: Option<String>,
+        owned: bool,
+        roles: Vec<ContactRole>,
+        created_at: NaiveDateTime,
+        services: Vec<String>,
+    ) -> Self {
         Self {
             id,
             did,
             fragment,
             name,
+            status,
+            method,
+            network,
+            owned,
+            roles,
             created_at,
+            services,
         }
     }
     
@@ -41,13 +169,58 @@ impl Did {
         &self.name
     }
 
+    pub fn status(&self) -> DidStatus {
+        self.status
+    }
+
+    /// The DID method: `iota`, `web`, or `key` and `jwk` for the DIDs that only exist offline.
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// Name of the network profile an IOTA DID was published with, `None` for other methods.
+    pub fn network(&self) -> Option<&str> {
+        self.network.as_deref()
+    }
+
+    /// Whether we control the DID, with its keys in our Stronghold. The other DIDs are contacts,
+    /// which are never offered to sign.
+    pub fn owned(&self) -> bool {
+        self.owned
+    }
+
+    /// What the contact can be chosen as, empty for our DIDs.
+    pub fn roles(&self) -> &[ContactRole] {
+        &self.roles
+    }
+
     pub fn created_at(&self) -> NaiveDateTime {
         self.created_at
     }
 
-    pub async fn resolve_to_iota_document(&self, resolver : &Resolver<IotaDocument>) -> IotaDocument {
-        let did = IotaDID::parse(&self.did).unwrap();
-        resolver.resolve(&did).await.unwrap()
+    /// The service endpoints recorded for the DID, as `#fragment type endpoint`.
+    pub fn services(&self) -> &[String] {
+        &self.services
+    }
+
+    /// The DID as an IOTA one. Only those are on the ledger, so they are the only ones that
+    /// can be updated, deactivated or destroyed.
+    pub fn iota_did(&self) -> Result<IotaDID> {
+        if self.method != "iota" {
+            anyhow::bail!(
+                "{} is a did:{}, only the DIDs on the ledger can be changed",
+                self.name,
+                self.method
+            );
+        }
+        Ok(IotaDID::parse(&self.did)?)
+    }
 
+    pub async fn resolve_document(
+        &self,
+        resolver: &Resolver<CoreDocument>,
+    ) -> Result<CoreDocument> {
+        let did = CoreDID::parse(&self.did)?;
+        Ok(resolver.resolve(&did).await?)
     }
 }
@@ -0,0 +1,77 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+/// Verification method of a stored DID, whose key was generated by Petrus.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DidMethod {
+    id: i64,
+    did_id: i64,
+    fragment: String,
+    scope: String,
+    alg: String,
+    retire_at: Option<NaiveDateTime>,
+    removed: bool,
+    created_at: NaiveDateTime,
+}
+
+impl DidMethod {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: i64,
+        did_id: i64,
+        fragment: String,
+        scope: String,
+        alg: String,
+        retire_at: Option<NaiveDateTime>,
+        removed: bool,
+        created_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            id,
+            did_id,
+            fragment,
+            scope,
+            alg,
+            retire_at,
+            removed,
+            created_at,
+        }
+    }
+
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    pub fn did_id(&self) -> i64 {
+        self.did_id
+    }
+
+    pub fn fragment(&self) -> &str {
+        &self.fragment
+    }
+
+    /// Name of the scope the method was generated in, see [`scope_name`](crate::scope_name).
+    pub fn scope(&self) -> &str {
+        &self.scope
+    }
+
+    /// JWS algorithm the key signs with, `EdDSA` for the methods recorded before other
+    /// algorithms were supported.
+    pub fn alg(&self) -> &str {
+        &self.alg
+    }
+
+    /// When the method is due to be removed from the document, once its key was rotated.
+    pub fn retire_at(&self) -> Option<NaiveDateTime> {
+        self.retire_at
+    }
+
+    /// Whether the method was removed from the document.
+    pub fn removed(&self) -> bool {
+        self.removed
+    }
+
+    pub fn created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+}
@@ -1,5 +1,9 @@
 pub use did::*;
+pub use did_method::*;
+pub use status_list::*;
 pub use vc::*;
 
 mod did;
-mod vc;
\ No newline at end of file
+mod did_method;
+mod status_list;
+mod vc;
@@ -0,0 +1,117 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+
+/// What setting the entry of a VC in a status list means. A revocation is final, a suspension
+/// can be lifted.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusListPurpose {
+    Revocation,
+    Suspension,
+}
+
+impl StatusListPurpose {
+    pub fn all() -> [StatusListPurpose; 2] {
+        [StatusListPurpose::Revocation, StatusListPurpose::Suspension]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatusListPurpose::Revocation => "revocation",
+            StatusListPurpose::Suspension => "suspension",
+        }
+    }
+
+    pub fn parse(purpose: &str) -> Result<Self> {
+        match purpose {
+            "revocation" => Ok(StatusListPurpose::Revocation),
+            "suspension" => Ok(StatusListPurpose::Suspension),
+            _ => Err(anyhow::anyhow!("Unknown status list purpose: {}", purpose)),
+        }
+    }
+
+    /// State of a VC whose entry is set.
+    pub fn set_state(&self) -> &'static str {
+        match self {
+            StatusListPurpose::Revocation => "revoked",
+            StatusListPurpose::Suspension => "suspended",
+        }
+    }
+}
+
+impl Display for StatusListPurpose {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// StatusList2021 credential of one of our issuers, kept with its last signed JWT.
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusList {
+    id: i64,
+    issuer: i64,
+    purpose: StatusListPurpose,
+    url: String,
+    credential: String,
+    jwt: String,
+    updated_at: NaiveDateTime,
+}
+
+impl StatusList {
+    pub fn new(
+        id: i64,
+        issuer: i64,
+        purpose: StatusListPurpose,
+        url: String,
+        credential: String,
+        jwt: String,
+        updated_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            id,
+            issuer,
+            purpose,
+            url,
+            credential,
+            jwt,
+            updated_at,
+        }
+    }
+
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    /// Database id of the DID that signs the list.
+    pub fn issuer(&self) -> i64 {
+        self.issuer
+    }
+
+    pub fn purpose(&self) -> StatusListPurpose {
+        self.purpose
+    }
+
+    /// Where the list is hosted, which is also the id of the credential.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The unsigned credential, as JSON.
+    pub fn credential(&self) -> &str {
+        &self.credential
+    }
+
+    pub fn jwt(&self) -> &str {
+        &self.jwt
+    }
+
+    pub fn updated_at(&self) -> NaiveDateTime {
+        self.updated_at
+    }
+
+    pub fn label(&self) -> String {
+        format!("{} {} ({})", self.id, self.url, self.purpose)
+    }
+}
@@ -1,19 +1,32 @@
-use crate::Did;
-use chrono::NaiveDateTime;
+use crate::{Did, StatusListPurpose};
+use chrono::{NaiveDateTime, Utc};
+use serde::Serialize;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct Vc {
     id: i64,
     vc: String,
+    #[serde(rename = "type")]
     tp: String,
     issuer: Did,
     holder: Did,
     sd: bool,
+    jpt: bool,
+    /// Index of the VC in the revocation bitmap of its issuer, if it can be revoked.
+    status_index: Option<u32>,
+    /// Status list the index is in, `None` for the revocation bitmap of the issuer.
+    status_list: Option<i64>,
+    status_purpose: Option<StatusListPurpose>,
+    /// Whether the status of the VC is set: revoked, or suspended in a suspension list.
+    revoked: bool,
+    /// When the VC expires, in UTC. `None` when it never does.
+    expires_at: Option<NaiveDateTime>,
     created_at: NaiveDateTime,
     
 }
 
 impl Vc {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: i64,
         vc: String,
@@ -21,6 +34,12 @@ impl Vc {
         issuer: Did,
         holder: Did,
         sd: bool,
+        jpt: bool,
+        status_index: Option<u32>,
+        status_list: Option<i64>,
+        status_purpose: Option<StatusListPurpose>,
+        revoked: bool,
+        expires_at: Option<NaiveDateTime>,
         created_at: NaiveDateTime,
     ) -> Self {
         Self {
@@ -30,6 +49,12 @@ impl Vc {
             issuer,
             holder,
             sd,
+            jpt,
+            status_index,
+            status_list,
+            status_purpose,
+            revoked,
+            expires_at,
             created_at,
         }
     }
@@ -61,4 +86,66 @@ impl Vc {
     pub fn sd(&self) -> bool {
         self.sd
     }
+
+    pub fn jpt(&self) -> bool {
+        self.jpt
+    }
+
+    pub fn status_index(&self) -> Option<u32> {
+        self.status_index
+    }
+
+    pub fn status_list(&self) -> Option<i64> {
+        self.status_list
+    }
+
+    pub fn status_purpose(&self) -> Option<StatusListPurpose> {
+        self.status_purpose
+    }
+
+    pub fn revoked(&self) -> bool {
+        self.revoked
+    }
+
+    pub fn expires_at(&self) -> Option<NaiveDateTime> {
+        self.expires_at
+    }
+
+    /// When the VC expires, if it does, and whether it already has.
+    pub fn expiry(&self) -> String {
+        match self.expires_at {
+            None => "never".to_string(),
+            Some(expires_at) if expires_at < Utc::now().naive_utc() => {
+                format!("{} (expired)", expires_at)
+            }
+            Some(expires_at) => expires_at.to_string(),
+        }
+    }
+
+    /// Whether the VC can be revoked or suspended, and is.
+    pub fn revocation(&self) -> String {
+        let Some(index) = self.status_index else {
+            return "not revocable".to_string();
+        };
+        let state = match (self.revoked, self.status_purpose) {
+            (false, _) => "valid",
+            (true, Some(purpose)) => purpose.set_state(),
+            (true, None) => "revoked",
+        };
+        match self.status_list {
+            Some(list) => format!("{} (status list {}, index {})", state, list, index),
+            None => format!("{} (index {})", state, index),
+        }
+    }
+
+    /// Name of the token format the VC is stored in.
+    pub fn format(&self) -> &'static str {
+        if self.jpt {
+            "JPT"
+        } else if self.sd {
+            "SD-JWT"
+        } else {
+            "JWT"
+        }
+    }
 }
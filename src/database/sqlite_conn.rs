@@ -1,32 +1,81 @@
-use crate::{extract_kid, DBConnector, Did, Vc};
+use crate::{
+    document_methods, document_services, extract_kid, like_pattern, scope_name, sql_order,
+    ContactRole, DBConnector, Did, DidMethod, DidQuery, DidStatus, StatusList, StatusListPurpose,
+    Vc, VcQuery,
+};
 use anyhow::Error;
 use anyhow::Result;
 use chrono::NaiveDateTime;
 use identity_iota::did::DID;
-use identity_iota::iota::IotaDocument;
+use identity_iota::document::CoreDocument;
 use rusqlite::{params, Connection, Params, Row};
+use std::sync::{Mutex, MutexGuard};
+
+// Every VC query selects the issuer and the holder along with the VC, see `build_vc_model`
+fn select_vcs() -> String {
+    format!(
+        r#"
+    SELECT vcs.id, vc, type, sd, vcs.created_at, jpt, status_index, revoked, vcs.status_list,
+        status_lists.purpose, vcs.expires_at,
+        issuer_did.id, issuer_did.did, issuer_did.fragment, issuer_did.name, issuer_did.status,
+        issuer_did.method, issuer_did.network, issuer_did.owned, issuer_did.roles,
+        issuer_did.created_at, {},
+        holder_did.id, holder_did.did, holder_did.fragment, holder_did.name, holder_did.status,
+        holder_did.method, holder_did.network, holder_did.owned, holder_did.roles,
+        holder_did.created_at, {}
+    FROM
+        vcs
+    INNER JOIN
+        dids AS issuer_did ON vcs.issuer = issuer_did.id
+    INNER JOIN
+        dids AS holder_did ON vcs.holder = holder_did.id
+    LEFT JOIN
+        status_lists ON vcs.status_list = status_lists.id
+"#,
+        services_column("issuer_did"),
+        services_column("holder_did")
+    )
+}
+
+// Services of the DID in the `dids_alias` row, one per line, see `Did::services`
+fn services_column(dids_alias: &str) -> String {
+    format!(
+        r#"(
+        SELECT GROUP_CONCAT(service, char(10)) FROM (
+            SELECT '#' || fragment || ' ' || type || ' ' || endpoint AS service FROM did_services
+            WHERE did_id = {}.id ORDER BY id
+        )
+    )"#,
+        dids_alias
+    )
+}
 
 // Define a struct to represent the SQLite database connection
+// The connection is behind a mutex so the connector can be shared between async tasks
 pub struct SQLiteConnector {
-    conn: Connection,
+    conn: Mutex<Connection>,
 }
 
 // Implement the constructor for SQLiteConnector
 impl SQLiteConnector {
     pub fn new(conn_str: &str) -> Result<Self, Error> {
         let conn = if conn_str.is_empty() {
-            let conn = Connection::open_in_memory()?;
-            conn
+            Connection::open_in_memory()?
         } else {
-            let conn = Connection::open(conn_str)?;
-            conn
+            Connection::open(conn_str)?
         };
 
-        Ok(Self { conn })
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn connection(&self) -> MutexGuard<'_, Connection> {
+        self.conn.lock().unwrap()
     }
 
     pub fn execute<P: Params>(&self, query: &str, params: P) -> Result<usize> {
-        let result = self.conn.execute(query, params);
+        let result = self.connection().execute(query, params);
 
         match result {
             Ok(n) => Ok(n),
@@ -34,127 +83,581 @@ impl SQLiteConnector {
         }
     }
 
+    /// Whether `table` has a column named `column`.
+    pub fn has_column(&self, table: &str, column: &str) -> Result<bool> {
+        let count: i64 = self.connection().query_row(
+            "SELECT COUNT(*) FROM pragma_table_info(?1) WHERE name = ?2",
+            [table, column],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
     fn build_did_model(row: &Row) -> Result<Did, Error> {
-        let created_at: String = row.get(4)?;
+        Self::build_did_model_at(row, 0)
+    }
+
+    // Builds a DID from the `id, did, fragment, name, status, method, network, owned, roles,
+    // created_at, services` columns starting at `first`
+    fn build_did_model_at(row: &Row, first: usize) -> Result<Did, Error> {
+        let status: String = row.get(first + 4)?;
+        let roles: Option<String> = row.get(first + 8)?;
+        let created_at: String = row.get(first + 9)?;
+        let services: Option<String> = row.get(first + 10)?;
         Ok(Did::new(
+            row.get(first)?,
+            row.get(first + 1)?,
+            row.get(first + 2)?,
+            row.get(first + 3)?,
+            DidStatus::parse(&status)?,
+            row.get(first + 5)?,
+            row.get(first + 6)?,
+            row.get(first + 7)?,
+            ContactRole::parse_list(&roles.unwrap_or_default())?,
+            NaiveDateTime::parse_from_str(&created_at, "%Y-%m-%d %H:%M:%S")?,
+            services
+                .map(|services| services.lines().map(str::to_string).collect())
+                .unwrap_or_default(),
+        ))
+    }
+
+    fn query_did(conn: &Connection, id: i64) -> Result<Did> {
+        let sql_query = format!(
+            r#"
+           SELECT id, did, fragment, name, status, method, network, owned, roles, created_at, {}
+           FROM dids
+           WHERE id = ?1
+        "#,
+            services_column("dids")
+        );
+
+        let mut stmt = conn.prepare(&sql_query)?;
+        let mut rows = stmt.query([id])?;
+
+        let row = rows.next()?;
+
+        if let Some(row) = row {
+            Self::build_did_model(row)
+        } else {
+            Err(rusqlite::Error::QueryReturnedNoRows.into())
+        }
+    }
+
+    fn insert_did_method(
+        conn: &Connection,
+        did_id: i64,
+        fragment: &str,
+        scope: &str,
+        alg: &str,
+    ) -> Result<i64> {
+        let sql_query = r#"
+            INSERT INTO did_methods (did_id, fragment, scope, alg, created_at)
+            VALUES (?1, ?2, ?3, ?4, CURRENT_TIMESTAMP)
+        "#;
+
+        conn.execute(sql_query, params![did_id, fragment, scope, alg])?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    fn upsert_did_service(
+        conn: &Connection,
+        did_id: i64,
+        fragment: &str,
+        service_type: &str,
+        endpoint: &str,
+    ) -> Result<()> {
+        let sql_query = r#"
+            INSERT INTO did_services (did_id, fragment, type, endpoint, created_at)
+            VALUES (?1, ?2, ?3, ?4, CURRENT_TIMESTAMP)
+            ON CONFLICT (did_id, fragment) DO UPDATE SET type = ?3, endpoint = ?4
+        "#;
+
+        conn.execute(sql_query, params![did_id, fragment, service_type, endpoint])?;
+        Ok(())
+    }
+
+    // Builds a method from the `id, did_id, fragment, scope, alg, retire_at, removed,
+    // created_at` columns
+    fn build_did_method_model(row: &Row) -> Result<DidMethod, Error> {
+        let retire_at: Option<String> = row.get(5)?;
+        let created_at: String = row.get(7)?;
+        Ok(DidMethod::new(
             row.get(0)?,
             row.get(1)?,
             row.get(2)?,
             row.get(3)?,
+            row.get(4)?,
+            retire_at
+                .map(|retire_at| NaiveDateTime::parse_from_str(&retire_at, "%Y-%m-%d %H:%M:%S"))
+                .transpose()?,
+            row.get(6)?,
             NaiveDateTime::parse_from_str(&created_at, "%Y-%m-%d %H:%M:%S")?,
         ))
     }
 
-    fn build_vc_model(&self, row: &Row) -> Result<Vc, Error> {
-        let created_at: String = row.get(6)?;
+    // Builds a status list from the `id, issuer, purpose, url, credential, jwt, updated_at`
+    // columns
+    fn build_status_list_model(row: &Row) -> Result<StatusList, Error> {
+        let purpose: String = row.get(2)?;
+        let updated_at: String = row.get(6)?;
+        Ok(StatusList::new(
+            row.get(0)?,
+            row.get(1)?,
+            StatusListPurpose::parse(&purpose)?,
+            row.get(3)?,
+            row.get(4)?,
+            row.get(5)?,
+            NaiveDateTime::parse_from_str(&updated_at, "%Y-%m-%d %H:%M:%S")?,
+        ))
+    }
+
+    fn query_status_lists<P: Params>(&self, condition: &str, params: P) -> Result<Vec<StatusList>> {
+        let sql_query = format!(
+            r#"
+            SELECT id, issuer, purpose, url, credential, jwt, updated_at
+            FROM status_lists
+            WHERE {}
+            ORDER BY id
+        "#,
+            condition
+        );
+
+        let conn = self.connection();
+        let mut stmt = conn.prepare(&sql_query)?;
+
+        let list_iter = stmt
+            .query_and_then(params, Self::build_status_list_model)?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(list_iter)
+    }
+
+    fn query_did_methods<P: Params>(&self, condition: &str, params: P) -> Result<Vec<DidMethod>> {
+        let sql_query = format!(
+            r#"
+            SELECT id, did_id, fragment, scope, alg, retire_at, removed, created_at
+            FROM did_methods
+            WHERE {}
+            ORDER BY id
+        "#,
+            condition
+        );
+
+        let conn = self.connection();
+        let mut stmt = conn.prepare(&sql_query)?;
+
+        let method_iter = stmt
+            .query_and_then(params, Self::build_did_method_model)?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(method_iter)
+    }
 
-        // print the type of row.get(5) to see if it is a boolean
-        let sd = row.get::<_, bool>(5)?;
+    // Builds a VC from a row of `select_vcs`, which joins the issuer and the holder
+    fn build_vc_model(row: &Row) -> Result<Vc, Error> {
+        let created_at: String = row.get(4)?;
+        let status_purpose: Option<String> = row.get(9)?;
+        let expires_at: Option<String> = row.get(10)?;
         Ok(Vc::new(
             row.get(0)?,
             row.get(1)?,
             row.get(2)?,
-            self.get_did_from_id(row.get(3)?).unwrap_or_default(),
-            self.get_did_from_id(row.get(4)?).unwrap_or_default(),
-            sd,
+            Self::build_did_model_at(row, 11)?,
+            Self::build_did_model_at(row, 22)?,
+            row.get::<_, bool>(3)?,
+            row.get::<_, bool>(5)?,
+            row.get(6)?,
+            row.get(8)?,
+            status_purpose
+                .map(|purpose| StatusListPurpose::parse(&purpose))
+                .transpose()?,
+            row.get::<_, bool>(7)?,
+            expires_at
+                .map(|expires_at| NaiveDateTime::parse_from_str(&expires_at, "%Y-%m-%d %H:%M:%S"))
+                .transpose()?,
             NaiveDateTime::parse_from_str(&created_at, "%Y-%m-%d %H:%M:%S")?,
         ))
     }
+
+    // Records every method and service of `did`, the `fragment` column of the DID keeps the
+    // signing one
+    fn insert_document_entries(conn: &Connection, did_id: i64, did: &CoreDocument) -> Result<()> {
+        for (fragment, scope, alg) in document_methods(did) {
+            Self::insert_did_method(conn, did_id, &fragment, scope_name(&scope), &alg)?;
+        }
+        for (fragment, service_type, endpoint) in document_services(did) {
+            Self::upsert_did_service(conn, did_id, &fragment, &service_type, &endpoint)?;
+        }
+        Ok(())
+    }
 }
 
 impl DBConnector for SQLiteConnector {
-    fn save_did_document(&self, did: &IotaDocument, owner: &String) -> Result<usize, Error> {
+    fn save_did(&self, did: &CoreDocument, owner: &str) -> Result<i64, Error> {
         // The "dids" table has the following columns:
         // - id: INTEGER PRIMARY KEY AUTOINCREMENT
         // - created_at: TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
         // - did: TEXT NOT NULL
         // - fragment: TEXT
+        // - method: TEXT NOT NULL, the DID method
+        // - owned: BOOLEAN NOT NULL, FALSE for the contacts
 
         let sql_query = r#"
-            INSERT INTO dids (did, fragment, name, created_at)
-            VALUES (?1, ?2, ?3, CURRENT_TIMESTAMP)
+            INSERT INTO dids (did, fragment, name, method, created_at)
+            VALUES (?1, ?2, ?3, ?4, CURRENT_TIMESTAMP)
         "#;
 
         let fragment = extract_kid(did)?;
 
-        self.execute(sql_query, params![did.id().as_str(), fragment, owner])
-            .map_err(|e| e.into())
+        // The DID is only saved along with all its methods
+        let mut conn = self.connection();
+        let tx = conn.transaction()?;
+        tx.execute(
+            sql_query,
+            params![did.id().as_str(), fragment, owner, did.id().method()],
+        )?;
+        let did_id = tx.last_insert_rowid();
+
+        Self::insert_document_entries(&tx, did_id, did)?;
+        tx.commit()?;
+        Ok(did_id)
     }
 
-    fn get_did_from_id(&self, id: i64) -> Result<Did> {
+    fn save_contact(&self, did: &CoreDocument, label: &str, roles: &[ContactRole]) -> Result<i64> {
         let sql_query = r#"
-           SELECT id, did, fragment, name, created_at FROM dids WHERE id = ?1
+            INSERT INTO dids (did, fragment, name, method, owned, roles, created_at)
+            VALUES (?1, ?2, ?3, ?4, FALSE, ?5, CURRENT_TIMESTAMP)
         "#;
 
-        let mut stmt = self.conn.prepare(sql_query)?;
-        let mut rows = stmt.query([id])?;
+        // A deactivated DID has no method left
+        let fragment = extract_kid(did).unwrap_or_default();
 
-        let row = rows.next()?;
+        let mut conn = self.connection();
+        let tx = conn.transaction()?;
+        tx.execute(
+            sql_query,
+            params![
+                did.id().as_str(),
+                fragment,
+                label,
+                did.id().method(),
+                ContactRole::join(roles)
+            ],
+        )?;
+        let did_id = tx.last_insert_rowid();
 
-        if let Some(row) = row {
-            Self::build_did_model(row)
-        } else {
-            Err(rusqlite::Error::QueryReturnedNoRows.into())
-        }
+        Self::insert_document_entries(&tx, did_id, did)?;
+        tx.commit()?;
+        Ok(did_id)
     }
 
-    fn get_stored_dids(&self) -> Result<Vec<Did>> {
+    fn update_contact(&self, did_id: i64, label: &str, roles: &[ContactRole]) -> Result<()> {
         let sql_query = r#"
-            SELECT id, did, fragment, name, created_at FROM dids
+            UPDATE dids SET name = ?2, roles = ?3 WHERE id = ?1 AND owned = FALSE
         "#;
 
-        let mut stmt = self.conn.prepare(sql_query)?;
+        if self.execute(sql_query, params![did_id, label, ContactRole::join(roles)])? == 0 {
+            anyhow::bail!("The DID {} is not a contact", did_id);
+        }
+        Ok(())
+    }
+
+    fn refresh_contact(&self, did_id: i64, did: &CoreDocument) -> Result<()> {
+        let mut conn = self.connection();
+        let tx = conn.transaction()?;
+        let updated = tx.execute(
+            "UPDATE dids SET fragment = ?2 WHERE id = ?1 AND owned = FALSE",
+            params![did_id, extract_kid(did).unwrap_or_default()],
+        )?;
+        if updated == 0 {
+            anyhow::bail!("The DID {} is not a contact", did_id);
+        }
+        tx.execute("DELETE FROM did_methods WHERE did_id = ?1", [did_id])?;
+        tx.execute("DELETE FROM did_services WHERE did_id = ?1", [did_id])?;
+        Self::insert_document_entries(&tx, did_id, did)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn get_did_from_id(&self, id: i64) -> Result<Did> {
+        Self::query_did(&self.connection(), id)
+    }
+
+    fn search_dids(&self, query: &DidQuery) -> Result<Vec<Did>> {
+        // The sort column comes from a fixed list, so it is safe to format it into the query
+        let sql_query = format!(
+            r#"
+            SELECT id, did, fragment, name, status, method, network, owned, roles, created_at, {}
+            FROM dids
+            WHERE (name LIKE ?1 ESCAPE '\' OR did LIKE ?1 ESCAPE '\')
+                AND (?2 IS NULL OR owned = ?2)
+                AND (?3 IS NULL OR owned OR ',' || roles || ',' LIKE '%,' || ?3 || ',%')
+                AND (?4 IS NULL OR status = ?4)
+            ORDER BY {} {}, id {}
+        "#,
+            services_column("dids"),
+            query.sort.sql_column(),
+            sql_order(query.descending),
+            sql_order(query.descending)
+        );
+
+        let conn = self.connection();
+        let mut stmt = conn.prepare(&sql_query)?;
 
         let did_iter = stmt
-            .query_map([], |row| Ok(Self::build_did_model(row).unwrap()))?
-            .collect::<Result<Vec<_>, _>>()?;
+            .query_and_then(
+                params![
+                    like_pattern(&query.text),
+                    query.owned,
+                    query.role.map(|role| role.as_str()),
+                    query.status.map(|status| status.as_str())
+                ],
+                Self::build_did_model,
+            )?
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(did_iter)
     }
 
-    fn save_vc(&self, vc: &str, issuer: i64, holder: i64, tp: &String, sd: bool) -> Result<usize> {
+    fn set_did_status(&self, did_id: i64, status: DidStatus) -> Result<()> {
+        let sql_query = r#"
+            UPDATE dids SET status = ?2 WHERE id = ?1
+        "#;
+
+        self.execute(sql_query, params![did_id, status.as_str()])?;
+        Ok(())
+    }
+
+    fn set_did_network(&self, did_id: i64, network: &str) -> Result<()> {
+        let sql_query = r#"
+            UPDATE dids SET network = ?2 WHERE id = ?1
+        "#;
+
+        self.execute(sql_query, params![did_id, network])?;
+        Ok(())
+    }
+
+    fn save_did_method(&self, did_id: i64, fragment: &str, scope: &str, alg: &str) -> Result<i64> {
+        Self::insert_did_method(&self.connection(), did_id, fragment, scope, alg)
+    }
+
+    fn get_did_methods(&self, did_id: i64) -> Result<Vec<DidMethod>> {
+        self.query_did_methods("did_id = ?1", [did_id])
+    }
+
+    fn set_did_fragment(&self, did_id: i64, fragment: &str) -> Result<()> {
+        let sql_query = r#"
+            UPDATE dids SET fragment = ?2 WHERE id = ?1
+        "#;
+
+        self.execute(sql_query, params![did_id, fragment])?;
+        Ok(())
+    }
+
+    fn retire_did_method(&self, method_id: i64, grace_days: u32) -> Result<()> {
+        let sql_query = r#"
+            UPDATE did_methods SET retire_at = datetime('now', ?2) WHERE id = ?1
+        "#;
+
+        self.execute(
+            sql_query,
+            params![method_id, format!("+{} days", grace_days)],
+        )?;
+        Ok(())
+    }
+
+    fn remove_did_method(&self, method_id: i64) -> Result<()> {
+        let sql_query = r#"
+            UPDATE did_methods SET removed = TRUE WHERE id = ?1
+        "#;
+
+        self.execute(sql_query, [method_id])?;
+        Ok(())
+    }
+
+    fn get_due_did_methods(&self) -> Result<Vec<DidMethod>> {
+        self.query_did_methods("retire_at <= CURRENT_TIMESTAMP AND removed = FALSE", [])
+    }
+
+    fn save_did_service(
+        &self,
+        did_id: i64,
+        fragment: &str,
+        service_type: &str,
+        endpoint: &str,
+    ) -> Result<()> {
+        Self::upsert_did_service(&self.connection(), did_id, fragment, service_type, endpoint)
+    }
+
+    fn remove_did_service(&self, did_id: i64, fragment: &str) -> Result<()> {
+        let sql_query = r#"
+            DELETE FROM did_services WHERE did_id = ?1 AND fragment = ?2
+        "#;
+
+        self.execute(sql_query, params![did_id, fragment])?;
+        Ok(())
+    }
+
+    fn save_vc(&self, vc: &str, issuer: i64, holder: i64, tp: &str, sd: bool) -> Result<i64> {
         let sql_query = r#"
             INSERT INTO vcs (vc, type, issuer, holder, sd, created_at)
             VALUES (?1, ?2, ?3, ?4, ?5, CURRENT_TIMESTAMP)
         "#;
 
-        self.execute(sql_query, params![vc, tp, issuer, holder, sd])
-            .map_err(|e| e.into())
+        let conn = self.connection();
+        conn.execute(sql_query, params![vc, tp, issuer, holder, sd])?;
+        Ok(conn.last_insert_rowid())
     }
 
-    fn get_vc_from_id(&self, id: i64) -> Result<Vc> {
+    fn save_jpt_vc(&self, jpt: &str, issuer: i64, holder: i64, tp: &str) -> Result<i64> {
+        let sql_query = r#"
+            INSERT INTO vcs (vc, type, issuer, holder, jpt, created_at)
+            VALUES (?1, ?2, ?3, ?4, TRUE, CURRENT_TIMESTAMP)
+        "#;
+
+        let conn = self.connection();
+        conn.execute(sql_query, params![jpt, tp, issuer, holder])?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    fn next_status_index(&self, issuer: i64) -> Result<u32> {
         let sql_query = r#"
-            SELECT id, vc, type, issuer, holder, created_at FROM vcs WHERE id = ?1
+            SELECT COALESCE(MAX(status_index) + 1, 0) FROM vcs
+            WHERE issuer = ?1 AND status_list IS NULL
         "#;
 
-        let mut stmt = self.conn.prepare(sql_query)?;
+        let conn = self.connection();
+        Ok(conn.query_row(sql_query, [issuer], |row| row.get(0))?)
+    }
+
+    fn set_vc_status_index(
+        &self,
+        vc_id: i64,
+        status_index: u32,
+        status_list: Option<i64>,
+    ) -> Result<()> {
+        let sql_query = r#"
+            UPDATE vcs SET status_index = ?2, status_list = ?3 WHERE id = ?1
+        "#;
+
+        self.execute(sql_query, params![vc_id, status_index, status_list])?;
+        Ok(())
+    }
+
+    fn set_vc_expiration(&self, vc_id: i64, expires_at: NaiveDateTime) -> Result<()> {
+        let sql_query = r#"
+            UPDATE vcs SET expires_at = ?2 WHERE id = ?1
+        "#;
+
+        self.execute(
+            sql_query,
+            params![vc_id, expires_at.format("%Y-%m-%d %H:%M:%S").to_string()],
+        )?;
+        Ok(())
+    }
+
+    fn save_status_list(
+        &self,
+        issuer: i64,
+        purpose: StatusListPurpose,
+        url: &str,
+        credential: &str,
+        jwt: &str,
+    ) -> Result<i64> {
+        let sql_query = r#"
+            INSERT INTO status_lists (issuer, purpose, url, credential, jwt, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, CURRENT_TIMESTAMP)
+        "#;
+
+        let conn = self.connection();
+        conn.execute(
+            sql_query,
+            params![issuer, purpose.as_str(), url, credential, jwt],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    fn update_status_list(&self, id: i64, credential: &str, jwt: &str) -> Result<()> {
+        let sql_query = r#"
+            UPDATE status_lists SET credential = ?2, jwt = ?3, updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?1
+        "#;
+
+        if self.execute(sql_query, params![id, credential, jwt])? == 0 {
+            return Err(rusqlite::Error::QueryReturnedNoRows.into());
+        }
+        Ok(())
+    }
+
+    fn get_status_list(&self, id: i64) -> Result<StatusList> {
+        self.query_status_lists("id = ?1", [id])?
+            .pop()
+            .ok_or_else(|| rusqlite::Error::QueryReturnedNoRows.into())
+    }
+
+    fn get_status_lists(&self, issuer: Option<i64>) -> Result<Vec<StatusList>> {
+        self.query_status_lists("?1 IS NULL OR issuer = ?1", [issuer])
+    }
+
+    fn next_status_list_index(&self, status_list: i64) -> Result<u32> {
+        let sql_query = r#"
+            SELECT COALESCE(MAX(status_index) + 1, 0) FROM vcs WHERE status_list = ?1
+        "#;
+
+        let conn = self.connection();
+        Ok(conn.query_row(sql_query, [status_list], |row| row.get(0))?)
+    }
+
+    fn set_vc_revoked(&self, vc_id: i64, revoked: bool) -> Result<()> {
+        let sql_query = r#"
+            UPDATE vcs SET revoked = ?2 WHERE id = ?1 AND status_index IS NOT NULL
+        "#;
+
+        if self.execute(sql_query, params![vc_id, revoked])? == 0 {
+            anyhow::bail!("The VC {} cannot be revoked", vc_id);
+        }
+        Ok(())
+    }
+
+    fn get_vc_from_id(&self, id: i64) -> Result<Vc> {
+        let sql_query = format!("{} WHERE vcs.id = ?1", select_vcs());
+
+        let conn = self.connection();
+        let mut stmt = conn.prepare(&sql_query)?;
         let mut rows = stmt.query([id])?;
 
         let row = rows.next()?;
 
         if let Some(row) = row {
-            Ok(self.build_vc_model(row)?)
+            Ok(Self::build_vc_model(row)?)
         } else {
             Err(rusqlite::Error::QueryReturnedNoRows.into())
         }
     }
 
-    fn get_stored_vcs(&self) -> Result<Vec<Vc>> {
-        let sql_query = r#"
-            SELECT vcs.id, vc, type, issuer, holder, sd, vcs.created_at
-            FROM
-                vcs
-            INNER JOIN
-                dids AS issuer_did ON vcs.issuer = issuer_did.id
-            INNER JOIN
-                dids AS holder_did ON vcs.holder = holder_did.id
-        "#;
+    fn search_vcs(&self, query: &VcQuery) -> Result<Vec<Vc>> {
+        // The sort column comes from a fixed list, so it is safe to format it into the query
+        let sql_query = format!(
+            r#"
+            {}
+            WHERE
+                vcs.type LIKE ?1 ESCAPE '\'
+                OR issuer_did.name LIKE ?1 ESCAPE '\' OR issuer_did.did LIKE ?1 ESCAPE '\'
+                OR holder_did.name LIKE ?1 ESCAPE '\' OR holder_did.did LIKE ?1 ESCAPE '\'
+            ORDER BY {} {}, vcs.id {}
+        "#,
+            select_vcs(),
+            query.sort.sql_column(),
+            sql_order(query.descending),
+            sql_order(query.descending)
+        );
 
-        let mut stmt = self.conn.prepare(sql_query)?;
+        let conn = self.connection();
+        let mut stmt = conn.prepare(&sql_query)?;
 
         let vc_iter = stmt
-            .query_map([], |row| Ok(self.build_vc_model(row).unwrap()))?
-            .collect::<Result<Vec<_>, _>>()?;
+            .query_and_then([like_pattern(&query.text)], Self::build_vc_model)?
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(vc_iter)
     }
@@ -1,17 +1,33 @@
-pub struct Input;
+use async_trait::async_trait;
+use std::io::stdin;
+use std::sync::Arc;
+use termion::event::Key;
+use termion::input::TermRead;
+use tokio::sync::{mpsc, Mutex};
 
-impl Input {
+/// Source of every user interaction of the app.
+///
+/// The commands never read from stdin directly, they ask the [`InputSource`] stored in the
+/// [`AppContext`](crate::AppContext), so a session can be driven by a script or recorded.
+///
+/// The reads are awaited, so a command waiting for the user can still be cancelled.
+#[async_trait]
+pub trait InputSource: Send + Sync {
+    /// Reads the next line typed by the user, without the trailing newline.
+    async fn read_line(&self) -> String;
 
-    pub fn wait_for_user_input(msg: &str) -> String {
+    /// Reads a single key press. The full-screen widgets switch the terminal to raw mode, so
+    /// the key is read without waiting for enter.
+    async fn read_key(&self) -> Key;
+
+    async fn wait_for_user_input(&self, msg: &str) -> String {
         println!("{}", msg);
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input).unwrap();
-        input.trim().to_string()
+        self.read_line().await.trim().to_string()
     }
-    pub fn get_number_input(min: usize , max: usize) -> usize {
+
+    async fn get_number_input(&self, min: usize, max: usize) -> usize {
         loop {
-            let mut input = String::new();
-            std::io::stdin().read_line(&mut input).unwrap();
+            let input = self.read_line().await;
 
             let trimmed_input = input.trim();
 
@@ -40,3 +56,65 @@ impl Input {
         }
     }
 }
+
+// Allows keeping a handle to the source after handing it to the context, e.g. to check
+// that a script was consumed entirely
+#[async_trait]
+impl<T: InputSource + ?Sized> InputSource for Arc<T> {
+    async fn read_line(&self) -> String {
+        (**self).read_line().await
+    }
+
+    async fn read_key(&self) -> Key {
+        (**self).read_key().await
+    }
+}
+
+/// Reads the input from the terminal.
+///
+/// Stdin is read by a thread of its own, which hands over the keys as they come, so a command
+/// cancelled while waiting for the user leaves no read blocking stdin behind it.
+#[derive(Default)]
+pub struct StdinInput {
+    keys: Mutex<Option<mpsc::UnboundedReceiver<Key>>>,
+}
+
+impl StdinInput {
+    // Next key of stdin, `None` once it is closed
+    async fn next_key(&self) -> Option<Key> {
+        let mut keys = self.keys.lock().await;
+        let keys = keys.get_or_insert_with(|| {
+            let (sender, receiver) = mpsc::unbounded_channel();
+            std::thread::spawn(move || {
+                // In cooked mode the terminal hands over whole lines, already edited
+                for key in stdin().keys() {
+                    let Ok(key) = key else { break };
+                    if sender.send(key).is_err() {
+                        break;
+                    }
+                }
+            });
+            receiver
+        });
+        keys.recv().await
+    }
+}
+
+#[async_trait]
+impl InputSource for StdinInput {
+    async fn read_line(&self) -> String {
+        let mut line = String::new();
+        while let Some(key) = self.next_key().await {
+            match key {
+                Key::Char('\n') => break,
+                Key::Char(c) => line.push(c),
+                _ => {}
+            }
+        }
+        line
+    }
+
+    async fn read_key(&self) -> Key {
+        self.next_key().await.unwrap_or(Key::Null)
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{InputSource, TuiSession, EXIT_ERROR};
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use termion::event::Key;
+
+// In a script, a key press is written as a line: a single character for that key, the name of
+// a special key, or `ctrl-`, `alt-` or `f` followed by the character or the number of the key.
+// `key` stands for any key, and is replayed as enter
+const NAMED_KEYS: [(&str, Key); 31] = [
+    ("esc", Key::Esc),
+    ("enter", Key::Char('\n')),
+    // Lines are trimmed, so the blank keys need a name
+    ("space", Key::Char(' ')),
+    ("tab", Key::Char('\t')),
+    ("backtab", Key::BackTab),
+    ("backspace", Key::Backspace),
+    ("delete", Key::Delete),
+    ("insert", Key::Insert),
+    ("up", Key::Up),
+    ("down", Key::Down),
+    ("left", Key::Left),
+    ("right", Key::Right),
+    ("shift-up", Key::ShiftUp),
+    ("shift-down", Key::ShiftDown),
+    ("shift-left", Key::ShiftLeft),
+    ("shift-right", Key::ShiftRight),
+    ("alt-up", Key::AltUp),
+    ("alt-down", Key::AltDown),
+    ("alt-left", Key::AltLeft),
+    ("alt-right", Key::AltRight),
+    ("ctrl-up", Key::CtrlUp),
+    ("ctrl-down", Key::CtrlDown),
+    ("ctrl-left", Key::CtrlLeft),
+    ("ctrl-right", Key::CtrlRight),
+    ("home", Key::Home),
+    ("end", Key::End),
+    ("ctrl-home", Key::CtrlHome),
+    ("ctrl-end", Key::CtrlEnd),
+    ("pageup", Key::PageUp),
+    ("pagedown", Key::PageDown),
+    ("null", Key::Null),
+];
+const ANY_KEY_LINE: &str = "key";
+
+fn single_char(text: &str) -> Option<char> {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+fn key_from_line(line: &str) -> Key {
+    if let Some((_, key)) = NAMED_KEYS.iter().find(|(name, _)| *name == line) {
+        return *key;
+    }
+    if let Some(c) = line.strip_prefix("ctrl-").and_then(single_char) {
+        return Key::Ctrl(c);
+    }
+    if let Some(c) = line.strip_prefix("alt-").and_then(single_char) {
+        return Key::Alt(c);
+    }
+    if let Some(n) = line.strip_prefix('f').and_then(|n| n.parse().ok()) {
+        return Key::F(n);
+    }
+
+    single_char(line).map_or(Key::Char('\n'), Key::Char)
+}
+
+fn line_from_key(key: Key) -> String {
+    if let Some((name, _)) = NAMED_KEYS.iter().find(|(_, named)| *named == key) {
+        return name.to_string();
+    }
+
+    match key {
+        Key::Char(c) => c.to_string(),
+        Key::Ctrl(c) => format!("ctrl-{}", c),
+        Key::Alt(c) => format!("alt-{}", c),
+        Key::F(n) => format!("f{}", n),
+        _ => ANY_KEY_LINE.to_string(),
+    }
+}
+
+/// Replays a script of inputs, one input per line.
+///
+/// Each line is echoed to the terminal when consumed, so a replayed session
+/// looks exactly like the recorded one.
+pub struct ScriptedInput {
+    lines: Mutex<VecDeque<String>>,
+    /// Whether running out of lines is a bug of the code that wrote the script, rather than a
+    /// short script given by the user.
+    panic_at_end: bool,
+}
+
+impl ScriptedInput {
+    /// Script written in code, such as the one of a test. Running out of lines panics.
+    pub fn new<I: IntoIterator<Item = S>, S: Into<String>>(lines: I) -> Self {
+        ScriptedInput {
+            lines: Mutex::new(lines.into_iter().map(Into::into).collect()),
+            panic_at_end: true,
+        }
+    }
+
+    /// Script of the user. Running out of lines ends the session with an error.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(ScriptedInput {
+            panic_at_end: false,
+            ..Self::new(content.lines())
+        })
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.lines.lock().unwrap().len()
+    }
+
+    fn next_line(&self) -> String {
+        let line = self.lines.lock().unwrap().pop_front();
+        match line {
+            Some(line) => line,
+            None if self.panic_at_end => panic!("The input script ended before the session did"),
+            None => {
+                TuiSession::restore_terminal();
+                eprintln!("Error: The input script ended before the session did");
+                std::process::exit(EXIT_ERROR);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl InputSource for ScriptedInput {
+    async fn read_line(&self) -> String {
+        let line = self.next_line();
+        println!("{}", line);
+        line
+    }
+
+    async fn read_key(&self) -> Key {
+        key_from_line(self.next_line().trim())
+    }
+}
+
+/// Forwards every input of another [`InputSource`] and appends it to a script file,
+/// which can be replayed later with [`ScriptedInput`].
+pub struct RecordingInput {
+    inner: Box<dyn InputSource>,
+    file: Mutex<File>,
+}
+
+impl RecordingInput {
+    pub fn new<P: AsRef<Path>>(inner: Box<dyn InputSource>, path: P) -> anyhow::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(path)?;
+
+        Ok(RecordingInput {
+            inner,
+            file: Mutex::new(file),
+        })
+    }
+
+    fn record(&self, line: &str) {
+        let mut file = self.file.lock().unwrap();
+        writeln!(file, "{}", line).unwrap();
+        file.flush().unwrap();
+    }
+}
+
+#[async_trait]
+impl InputSource for RecordingInput {
+    async fn read_line(&self) -> String {
+        let line = self.inner.read_line().await;
+        self.record(&line);
+        line
+    }
+
+    async fn read_key(&self) -> Key {
+        let key = self.inner.read_key().await;
+        self.record(&line_from_key(key));
+        key
+    }
+}
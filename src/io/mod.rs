@@ -1,7 +1,7 @@
-pub use output::*;
 pub use input::*;
+pub use input_script::*;
+pub use output::*;
 
-
-
+mod input;
+mod input_script;
 mod output;
-mod input;
\ No newline at end of file
@@ -1,20 +1,46 @@
 // Create a class that will handle the output of the program.
 // This class will be used to print the output of the program.
 
-use crate::ScreenEvent;
+use crate::Progress;
 use colored::*;
 use crossterm::execute;
 use crossterm::terminal::ClearType;
 use std::io::{stdout, Write};
 use std::iter::Map;
 use std::str::Split;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 use tokio::sync::watch;
 use tokio::time::{sleep, Duration};
 use users::{get_current_uid, get_user_by_uid};
 
 pub struct Output;
 
+// When set, human readable messages are suppressed so only the JSON documents reach stdout
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+// Titles of the screens opened on the way to the current one, shown above the screen title
+static BREADCRUMBS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+pub(crate) const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
 impl Output {
+    pub fn set_json_output(enabled: bool) {
+        JSON_OUTPUT.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn is_json_output() -> bool {
+        JSON_OUTPUT.load(Ordering::Relaxed)
+    }
+
+    pub fn set_breadcrumbs(breadcrumbs: Vec<String>) {
+        *BREADCRUMBS.lock().unwrap() = breadcrumbs;
+    }
+
+    pub fn breadcrumbs() -> Vec<String> {
+        BREADCRUMBS.lock().unwrap().clone()
+    }
+
     //Clear the screen
     pub fn clear_screen() {
         execute!(stdout(), crossterm::terminal::Clear(ClearType::All)).unwrap();
@@ -46,11 +72,9 @@ impl Output {
     }
 
     pub async fn loading_animation(rx: watch::Receiver<bool>) {
-        let braille_frames = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
-
         while *rx.borrow() {
             // Adjust the range to control how long the animation runs
-            for &frame in &braille_frames {
+            for &frame in &SPINNER_FRAMES {
                 print!("\r{} Loading ", frame); // `\r` moves the cursor to the start of the line
                 stdout().flush().unwrap();
                 sleep(Duration::from_millis(100)).await;
@@ -59,9 +83,38 @@ impl Output {
     }
 
     pub fn print_during_loading(message: &str) {
+        // A progress screen shows the message itself
+        if Self::is_json_output() || Progress::push_step(message) {
+            return;
+        }
         println!("\r{}", message);
     }
+
+    // Print the beginning of a step, to be completed by `print_step_ok`
+    pub fn print_step(message: &str) {
+        if Self::is_json_output() {
+            return;
+        }
+        print!("{}", message);
+        stdout().flush().unwrap_or_default();
+    }
+
+    pub fn print_step_ok() {
+        Self::print_info("Ok!");
+    }
+
+    pub fn print_info(message: &str) {
+        if Self::is_json_output() {
+            return;
+        }
+        println!("{}", message);
+    }
     pub fn print_screen_title(title: &str) {
+        let breadcrumbs = BREADCRUMBS.lock().unwrap();
+        if !breadcrumbs.is_empty() {
+            let trail = format!("{} > {}", breadcrumbs.join(" > "), title);
+            println!("\n{}", trail.dimmed());
+        }
         println!("\n{}", title.bold().blue());
         // Print 2 blank lines
         println!("\n");
@@ -87,7 +140,7 @@ impl Output {
         Self::uppercase_first_letter(input).collect::<String>()
     }
 
-    fn uppercase_first_letter(input: &str) -> Map<Split<char>, fn(&str) -> String> {
+    fn uppercase_first_letter(input: &str) -> Map<Split<'_, char>, fn(&str) -> String> {
         input.split('_').map(|word| {
             let mut chars = word.chars();
             match chars.next() {
@@ -96,126 +149,4 @@ impl Output {
             }
         })
     }
-
-    pub fn display_with_pagination<'a, T: Clone>(
-        items: &Vec<T>,
-        display_fn: fn(&Vec<T>, usize),
-        page_size: usize,
-        selectable: bool,
-        print_title: Option<Box<dyn Fn() + 'a>>,
-    ) -> usize {
-        let total_pages = (items.len() + page_size - 1) / page_size;
-        let mut current_page = 0;
-        let mut error = String::new();
-
-        loop {
-            if let Some(print_title) = print_title.as_ref() {
-                print_title();
-            }
-            // Calculate the start and end indices for the current page
-            let start_index = current_page * page_size;
-            let end_index = usize::min(start_index + page_size, items.len());
-
-            display_fn(&items[start_index..end_index].to_vec(), start_index + 1);
-
-            println!("Page {}/{}", current_page + 1, total_pages);
-
-            // Navigation options
-            Self::print_navigation_options(selectable);
-
-            if !error.is_empty() {
-                println!("Error: {}", error.clone());
-                error.clear();
-            }
-
-            // Wait for user input
-            let mut input = String::new();
-            std::io::stdin().read_line(&mut input).unwrap();
-            let input = input.trim();
-
-            Self::clear_screen();
-
-            match input {
-                "" => {
-                    if current_page + 1 < total_pages {
-                        current_page += 1;
-                    } else {
-                        error = "You are already on the last page.".to_string();
-                    }
-                }
-                "p" => {
-                    if current_page > 0 {
-                        current_page -= 1;
-                    } else {
-                        error = "You are already on the first page.".to_string();
-                    }
-                }
-                "q" => {
-                    if !selectable {
-                        println!("Exiting navigation.");
-                        return 0;
-                    } else {
-                        error = "Please own up to your choices and end this stream".to_string();
-                    }
-                }
-                _ => {
-                    if selectable {
-                        // Check if input is a number
-                        if !input.chars().all(char::is_numeric) {
-                            println!("Invalid input. Please enter a number.");
-                            continue;
-                        }
-
-                        // Check if the input is within the valid range
-                        if let Ok(selection) = input.parse::<usize>() {
-                            if selection >= start_index + 1 && selection <= end_index {
-                                return selection;
-                            }
-                        }
-
-                        error = format!(
-                            "Invalid input. Please enter a number between {} and {}.",
-                            start_index + 1,
-                            end_index
-                        );
-                    } else {
-                        error =
-                            "Invalid input. Use 'n' for next, 'p' for previous, or 'q' to quit."
-                                .to_string();
-                    }
-                }
-            }
-        }
-    }
-
-    fn print_navigation_options(selectable: bool) {
-        if selectable {
-            println!(
-                "{} {} {}",
-                "Navigation:",
-                "enter - Next page".green().bold(),
-                "p - Previous page".bold().red()
-            );
-        } else {
-            println!(
-                "{} {} {} {}",
-                "Navigation:",
-                "enter - Next page".green().bold(),
-                "p - Previous page".bold().red(),
-                "q - Quit".bold().blue()
-            );
-        }
-    }
-
-    pub fn print_options_vec(ops: &Vec<(String, ScreenEvent)>) {
-        for (index, option) in ops.iter().enumerate() {
-            println!("{}. {}", index + 1, option.0);
-        }
-    }
-
-    pub fn print_options_vec_generic<T: ToString>(ops: &Vec<T>) {
-        for (index, option) in ops.iter().enumerate() {
-            println!("{}. {}", index + 1, option.to_string());
-        }
-    }
 }
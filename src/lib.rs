@@ -1,13 +1,20 @@
+// A module folder may keep its main file under its own name, e.g. `utils/utils.rs`
+#![allow(clippy::module_inception)]
+
 pub use app::*;
+pub use cli::*;
 pub use command::*;
 pub use database::*;
 pub use io::*;
 pub use state::*;
+pub use tui::*;
 pub use utils::*;
 
 mod app;
+mod cli;
 mod command;
 mod database;
 mod io;
 mod state;
+mod tui;
 mod utils;
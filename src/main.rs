@@ -1,9 +1,72 @@
-use tcc::{App, AppContext, Output};
+use clap::Parser;
+use std::io::IsTerminal;
+use tcc::{
+    App, AppContext, Cli, CliReport, CliRunner, InputSource, Output, OutputFormat, RecordingInput,
+    ScriptedInput, StdinInput, TuiSession, EXIT_ERROR,
+};
 
 #[tokio::main]
 async fn main() {
+    let cli = Cli::parse();
+
+    // Run the subcommand without user interaction, if one was given
+    if let Some(command) = cli.command {
+        Output::set_json_output(cli.output == OutputFormat::Json);
+        let context = match AppContext::my_app_context(
+            Box::new(StdinInput::default()),
+            cli.network.as_deref(),
+        )
+        .await
+        {
+            Ok(context) => context,
+            Err(e) => {
+                CliReport::print_error(&e, cli.output);
+                std::process::exit(EXIT_ERROR);
+            }
+        };
+        let exit_code = match CliRunner::new(&context).run(command).await {
+            Ok(report) => {
+                report.print(cli.output);
+                report.exit_code()
+            }
+            Err(e) => {
+                CliReport::print_error(&e, cli.output);
+                EXIT_ERROR
+            }
+        };
+        std::process::exit(exit_code);
+    }
+
+    let input: Box<dyn InputSource> = match (cli.script, cli.record) {
+        (Some(script), _) => match ScriptedInput::from_file(&script) {
+            Ok(input) => Box::new(input),
+            Err(e) => {
+                eprintln!(
+                    "Error: cannot read the script {}: {:#}",
+                    script.display(),
+                    e
+                );
+                std::process::exit(EXIT_ERROR);
+            }
+        },
+        (None, Some(record)) => {
+            Box::new(RecordingInput::new(Box::new(StdinInput::default()), record).unwrap())
+        }
+        (None, None) => Box::new(StdinInput::default()),
+    };
+
+    // Draw the full-screen widgets on the terminal, unless the output is redirected
+    TuiSession::set_terminal_enabled(std::io::stdout().is_terminal());
+
     Output::show_welcome_message();
-    let context = AppContext::build_app_context_with_loading().await;
+    let context =
+        match AppContext::build_app_context_with_loading(input, cli.network.as_deref()).await {
+            Ok(context) => context,
+            Err(e) => {
+                eprintln!("Error: {:#}", e);
+                std::process::exit(EXIT_ERROR);
+            }
+        };
     let mut app = App::new(context);
-    app.run();
-}
\ No newline at end of file
+    app.run().await;
+}
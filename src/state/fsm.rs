@@ -1,69 +1,110 @@
+use crate::{AppContext, Command, CommandRegistry, ScreenEvent, ScreenState};
 
-use rust_fsm::{StateMachineImpl};
-use crate::{ScreenEvent, ScreenState};
-
-pub struct ScreenFSM;
-
-impl StateMachineImpl for ScreenFSM{
-    type Input = ScreenEvent;
-    type State = ScreenState;
-    type Output = ();
-    const INITIAL_STATE: Self::State = ScreenState::MainMenu;
-
-
-    fn transition(state: &ScreenState, event: &ScreenEvent) -> Option<ScreenState> {
-        match (state, event) {
-            // Main Menu
-            (ScreenState::MainMenu, ScreenEvent::ListItems) => Some(ScreenState::ListItemsMenu),
-            (ScreenState::MainMenu, ScreenEvent::CreateDID) => Some(ScreenState::CreateDIDWorkflow),
-            (ScreenState::MainMenu, ScreenEvent::CreateVC) => Some(ScreenState::CreateVCMenu),
-            (ScreenState::MainMenu, ScreenEvent::VerifyVC) => Some(ScreenState::VerifyVCWorkflow),
-            (ScreenState::MainMenu, ScreenEvent::CreateVP) => Some(ScreenState::CreateVPWorkflow),
-            
-            // List Create Items Menu
-            (ScreenState::ListItemsMenu, ScreenEvent::Cancel) => Some(ScreenState::MainMenu),
-            (ScreenState::ListItemsMenu, ScreenEvent::ListDIDs) => Some(ScreenState::ListDIDsWorkflow),
-            (ScreenState::ListItemsMenu, ScreenEvent::ListVCs) => Some(ScreenState::ListVCsWorkflow),
-            
-            // Create VC Menu
-            (ScreenState::CreateVCMenu, ScreenEvent::Cancel) => Some(ScreenState::MainMenu),
-            (ScreenState::CreateVCMenu, ScreenEvent::CreateNormalVC) => Some(ScreenState::CreateNormalVCWorkflow),
-            (ScreenState::CreateVCMenu, ScreenEvent::CreateSDVC) => Some(ScreenState::CreateSDVCWorkflow),
-            
-             // Exit the program
-            (ScreenState::MainMenu, ScreenEvent::Cancel) => Some(ScreenState::ExitAppWorkflow),
-            
-            // In case of cancel, return to the main menu
-            (_, ScreenEvent::Cancel) => Some(ScreenState::MainMenu),
-            // For the default case of success, return to the main menu
-            (_, ScreenEvent::Success) => Some(ScreenState::MainMenu),
-            
-            _ => None,
+/// State machine of the screens. The transitions come from the [`CommandRegistry`].
+///
+/// The screens opened on the way to the current one are kept in a history, so `Cancel` and
+/// `Success` go back to the previous screen instead of the main menu.
+pub struct ScreenFSM {
+    state: ScreenState,
+    history: Vec<ScreenState>,
+    registry: CommandRegistry,
+    last_error: Option<String>,
+}
+
+impl ScreenFSM {
+    pub fn new(registry: CommandRegistry) -> Self {
+        ScreenFSM {
+            state: ScreenState::MainMenu,
+            history: Vec::new(),
+            registry,
+            last_error: None,
         }
     }
-    
-    fn output(_state: &Self::State, _input: &Self::Input) -> Option<Self::Output> {
-        None
+
+    pub fn state(&self) -> &ScreenState {
+        &self.state
     }
-}
 
-// state_machine! {
-//     #[state_machine(input(crate::ScreenEvent), state(crate::ScreenState))]
-//     screen_fsm(MainMenu)
-// 
-//     MainMenu => {
-//         SelectCreateDID => CreateDIDWorkflow,
-//         SelectCreateVC => CreateVCWorkflow,
-//         SelectVerifyVC => VerifyVCWorkflow,
-//     },
-//     CreateDIDWorkflow(Cancel) => MainMenu,
-//     CreateVCWorkflow(Cancel) => MainMenu,
-//     VerifyVCWorkflow(Cancel) => MainMenu
-// }
-
-// fn test(){
-//     let mut fsm = screen_fsm::StateMachine::new();
-//     let event = ScreenEvent::SelectCreateDID;
-//     let new_state =fsm.consume(&event).unwrap();
-//     println!("{:?}", new_state);
-// }
+    pub fn registry(&self) -> &CommandRegistry {
+        &self.registry
+    }
+
+    /// Failure shown by the error screen.
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    /// Titles of the screens opened on the way to the current one, starting from the main menu.
+    pub fn breadcrumbs(&self) -> Vec<String> {
+        self.history
+            .iter()
+            .map(|state| self.registry.title(state))
+            .collect()
+    }
+
+    pub fn build_command<'a>(&self, context: &'a AppContext) -> Option<Box<dyn Command + 'a>> {
+        self.registry
+            .find(&self.state)
+            .map(|registration| registration.build(context, self))
+    }
+
+    /// Moves to the state `event` leads to from the current state.
+    ///
+    /// An event without transition does not stop the app, it opens the error screen instead.
+    pub fn consume(&mut self, event: &ScreenEvent) -> &ScreenState {
+        if let Some(next) = self.registry.transition(&self.state, event) {
+            self.open(next);
+            return &self.state;
+        }
+
+        match (&self.state, event) {
+            (_, ScreenEvent::Error(message)) => self.fail(message.clone()),
+
+            // Run the screen that failed again
+            (ScreenState::Error, ScreenEvent::Retry) => {
+                self.last_error = None;
+                self.back();
+            }
+            // Leave the screen that failed as well
+            (ScreenState::Error, ScreenEvent::Cancel) => {
+                self.last_error = None;
+                self.back();
+                self.back();
+            }
+
+            (_, ScreenEvent::Cancel | ScreenEvent::Success) if !self.history.is_empty() => {
+                self.back()
+            }
+
+            (state, event) => {
+                let message = format!("No transition from {:?} on {:?}", state, event);
+                self.fail(message)
+            }
+        }
+
+        &self.state
+    }
+
+    /// Opens the error screen for a failure of the current screen.
+    pub fn fail(&mut self, message: String) {
+        self.last_error = Some(message);
+        if self.state != ScreenState::Error {
+            self.open(ScreenState::Error);
+        }
+    }
+
+    fn open(&mut self, next: ScreenState) {
+        // Opening a screen already in the history goes back to it, e.g. a workflow leading to
+        // the main menu
+        if let Some(index) = self.history.iter().position(|state| *state == next) {
+            self.history.truncate(index);
+        } else {
+            self.history.push(self.state.clone());
+        }
+        self.state = next;
+    }
+
+    fn back(&mut self) {
+        self.state = self.history.pop().unwrap_or(ScreenState::MainMenu);
+    }
+}
@@ -1,15 +1,36 @@
 #[derive(Clone, Debug, PartialEq)]
 pub enum ScreenEvent {
     CreateDID,
+    UpdateDID,
+    AddMethods,
+    ManageServices,
+    RotateKey,
+    RemoveRetiredKeys,
+    DeactivateDID,
+    DestroyDID,
+    Revocation,
     ListDIDs,
     ListVCs,
+    ShowDIDDetails,
     CreateVC,
     CreateNormalVC,
     CreateSDVC,
+    CreateJptVC,
+    StatusLists,
     VerifyVC,
     CreateVP,
+    InspectToken,
     ListItems,
+    Contacts,
+    SwitchNetwork,
+    /// Network profile chosen to connect to, by name.
+    NetworkSelected(String),
     Cancel,
     Success,
     Exit,
+    Retry,
+    /// Failure of a screen, shown by the error screen.
+    Error(String),
+    /// Event of a screen registered outside of Petrus.
+    Custom(&'static str),
 }
@@ -3,12 +3,29 @@ pub enum ScreenState {
     MainMenu,
     ListItemsMenu,
     CreateDIDWorkflow,
+    UpdateDIDMenu,
+    AddMethodsWorkflow,
+    ManageServicesWorkflow,
+    RotateKeyWorkflow,
+    RemoveRetiredKeysWorkflow,
+    DeactivateDIDWorkflow,
+    DestroyDIDWorkflow,
+    RevocationWorkflow,
     ListDIDsWorkflow,
     ListVCsWorkflow,
+    DIDDetailsWorkflow,
     CreateVCMenu,
     CreateNormalVCWorkflow,
     CreateSDVCWorkflow,
+    CreateJptVCWorkflow,
+    StatusListsWorkflow,
     VerifyVCWorkflow,
     CreateVPWorkflow,
+    InspectTokenWorkflow,
+    ContactsWorkflow,
+    SwitchNetworkWorkflow,
     ExitAppWorkflow,
+    Error,
+    /// Screen registered outside of Petrus.
+    Custom(&'static str),
 }
@@ -0,0 +1,87 @@
+use crate::{InputSource, TuiSession};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, List, ListState};
+use std::collections::BTreeSet;
+use termion::event::Key;
+
+const CHECKLIST_KEYS: &str = "↑/↓ move · space toggle · 1-9 toggle item · enter confirm";
+
+/// Full-screen list of items the user can check, e.g. the disclosures of a presentation.
+pub struct Checklist<'a> {
+    title: &'a str,
+    items: Vec<String>,
+    checked: BTreeSet<usize>,
+}
+
+impl<'a> Checklist<'a> {
+    pub fn new(title: &'a str, items: Vec<String>) -> Self {
+        Checklist {
+            title,
+            items,
+            checked: BTreeSet::new(),
+        }
+    }
+
+    /// Items checked when the list is shown, e.g. the current choices when editing them.
+    pub fn checked(mut self, indexes: &[usize]) -> Self {
+        self.checked = indexes.iter().copied().collect();
+        self
+    }
+
+    /// Returns the indexes of the checked items, in order.
+    pub async fn select(&self, input: &dyn InputSource) -> Vec<usize> {
+        let count = self.items.len();
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let mut session = TuiSession::start();
+        let mut state = ListState::default().with_selected(Some(0));
+        let mut checked: BTreeSet<usize> = self.checked.clone();
+
+        loop {
+            session.draw(|frame| self.render(frame, &mut state, &checked));
+
+            let selected = state.selected().unwrap_or(0);
+            match input.read_key().await {
+                Key::Up | Key::Char('k') => state.select(Some((selected + count - 1) % count)),
+                Key::Down | Key::Char('j') => state.select(Some((selected + 1) % count)),
+                Key::Char(' ') => Self::toggle(&mut checked, selected),
+                Key::Char(c) if c.is_ascii_digit() => {
+                    let number = c.to_digit(10).unwrap() as usize;
+                    if number >= 1 && number <= count {
+                        state.select(Some(number - 1));
+                        Self::toggle(&mut checked, number - 1);
+                    }
+                }
+                Key::Char('\n') => return checked.into_iter().collect(),
+                _ => {}
+            }
+        }
+    }
+
+    fn toggle(checked: &mut BTreeSet<usize>, index: usize) {
+        if !checked.remove(&index) {
+            checked.insert(index);
+        }
+    }
+
+    fn render(&self, frame: &mut ratatui::Frame, state: &mut ListState, checked: &BTreeSet<usize>) {
+        let body = TuiSession::render_screen(frame, self.title, CHECKLIST_KEYS);
+
+        let items = self.items.iter().enumerate().map(|(index, item)| {
+            let mark = if checked.contains(&index) {
+                "[x]"
+            } else {
+                "[ ]"
+            };
+            Line::from(format!("{} {}. {}", mark, index + 1, item))
+        });
+        let list = List::new(items)
+            .block(Block::bordered().title(format!(" {} selected ", checked.len())))
+            .highlight_symbol("> ")
+            .highlight_style(Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+        frame.render_stateful_widget(list, body, state);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{InputSource, TuiSession};
+use ratatui::style::Stylize;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Wrap};
+use termion::event::Key;
+
+const DETAILS_KEYS: &str = "↑/↓ scroll · pgup/pgdn page · q/esc back";
+
+/// Group of fields shown under a heading by a [`DetailsView`].
+#[derive(Clone, Debug, Default)]
+pub struct DetailSection {
+    pub title: String,
+    pub fields: Vec<(String, String)>,
+}
+
+impl DetailSection {
+    pub fn new(title: &str) -> Self {
+        DetailSection {
+            title: title.to_string(),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn field(mut self, name: &str, value: impl ToString) -> Self {
+        self.fields.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+/// Scrollable full-screen view of the details of a single item.
+pub struct DetailsView<'a> {
+    title: &'a str,
+    sections: Vec<DetailSection>,
+    page_size: u16,
+}
+
+impl<'a> DetailsView<'a> {
+    pub fn new(title: &'a str, sections: Vec<DetailSection>) -> Self {
+        DetailsView {
+            title,
+            sections,
+            page_size: 10,
+        }
+    }
+
+    /// Lets the user scroll through the sections until they go back.
+    pub async fn show(&self, input: &dyn InputSource) {
+        let mut session = TuiSession::start();
+        let lines = self.lines();
+        let last = lines.len().saturating_sub(1) as u16;
+        let mut scroll: u16 = 0;
+
+        loop {
+            session.draw(|frame| {
+                let body = TuiSession::render_screen(frame, self.title, DETAILS_KEYS);
+                let paragraph = Paragraph::new(lines.clone())
+                    .wrap(Wrap { trim: false })
+                    .scroll((scroll, 0))
+                    .block(Block::bordered());
+                frame.render_widget(paragraph, body);
+            });
+
+            match input.read_key().await {
+                Key::Up | Key::Char('k') => scroll = scroll.saturating_sub(1),
+                Key::Down | Key::Char('j') => scroll = u16::min(scroll + 1, last),
+                Key::PageUp | Key::Char('p') => scroll = scroll.saturating_sub(self.page_size),
+                Key::PageDown | Key::Char(' ') => {
+                    scroll = u16::min(scroll.saturating_add(self.page_size), last)
+                }
+                Key::Home => scroll = 0,
+                Key::End => scroll = last,
+                Key::Char('q') | Key::Esc | Key::Ctrl('c') | Key::Char('\n') => return,
+                _ => {}
+            }
+        }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        for section in &self.sections {
+            lines.push(Line::from(section.title.clone()).bold().blue());
+            if section.fields.is_empty() {
+                lines.push(Line::from("  None").dark_gray());
+            }
+            for (name, value) in &section.fields {
+                lines.push(Line::from(vec![
+                    Span::from(format!("  {}: ", name)).bold(),
+                    Span::from(value.clone()),
+                ]));
+            }
+            lines.push(Line::default());
+        }
+        lines
+    }
+}
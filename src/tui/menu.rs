@@ -0,0 +1,107 @@
+use crate::{InputSource, TuiSession};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, List, ListState, Paragraph, Wrap};
+use termion::event::Key;
+
+const MENU_KEYS: &str = "↑/↓ move · enter select · 1-9, 0 select option · esc back";
+
+/// Full-screen list of options, navigated with the arrow keys.
+///
+/// The number of an option selects it directly, which keeps the scripted sessions readable.
+/// The tenth option is numbered 0.
+pub struct Menu<'a> {
+    title: &'a str,
+    message: Option<String>,
+    options: Vec<String>,
+}
+
+impl<'a> Menu<'a> {
+    pub fn new(title: &'a str, options: Vec<String>) -> Self {
+        Menu {
+            title,
+            message: None,
+            options,
+        }
+    }
+
+    /// Text shown above the options.
+    pub fn message(mut self, message: &str) -> Self {
+        self.message = Some(message.to_string());
+        self
+    }
+
+    /// Returns the index of the chosen option, or `None` when the user goes back.
+    pub async fn select(&self, input: &dyn InputSource) -> Option<usize> {
+        let count = self.options.len();
+        if count == 0 {
+            return None;
+        }
+
+        let mut session = TuiSession::start();
+        let mut state = ListState::default().with_selected(Some(0));
+
+        loop {
+            session.draw(|frame| self.render(frame, &mut state));
+
+            let selected = state.selected().unwrap_or(0);
+            match input.read_key().await {
+                Key::Up | Key::Char('k') => state.select(Some((selected + count - 1) % count)),
+                Key::Down | Key::Char('j') => state.select(Some((selected + 1) % count)),
+                Key::Home => state.select(Some(0)),
+                Key::End => state.select(Some(count - 1)),
+                Key::Char('\n') => return Some(selected),
+                Key::Char(c) if c.is_ascii_digit() => {
+                    let number = match c.to_digit(10).unwrap() as usize {
+                        0 => 10,
+                        number => number,
+                    };
+                    if number >= 1 && number <= count {
+                        return Some(number - 1);
+                    }
+                }
+                Key::Esc | Key::Ctrl('c') => return None,
+                _ => {}
+            }
+        }
+    }
+
+    // Number of the option at `index`, the key that selects it
+    fn number(index: usize) -> usize {
+        if index == 9 {
+            0
+        } else {
+            index + 1
+        }
+    }
+
+    fn render(&self, frame: &mut ratatui::Frame, state: &mut ListState) {
+        let body = TuiSession::render_screen(frame, self.title, MENU_KEYS);
+
+        let message_height = match &self.message {
+            Some(message) => message.lines().count() as u16 + 2,
+            None => 0,
+        };
+        let [message_area, list_area] =
+            Layout::vertical([Constraint::Length(message_height), Constraint::Min(0)]).areas(body);
+
+        if let Some(message) = &self.message {
+            frame.render_widget(
+                Paragraph::new(message.as_str()).wrap(Wrap { trim: false }),
+                message_area,
+            );
+        }
+
+        let items = self
+            .options
+            .iter()
+            .enumerate()
+            .map(|(index, option)| Line::from(format!("{}. {}", Self::number(index), option)));
+        let list = List::new(items)
+            .block(Block::bordered())
+            .highlight_symbol("> ")
+            .highlight_style(Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+        frame.render_stateful_widget(list, list_area, state);
+    }
+}
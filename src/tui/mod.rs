@@ -0,0 +1,15 @@
+pub use checklist::*;
+pub use details_view::*;
+pub use menu::*;
+pub use modal::*;
+pub use progress::*;
+pub use table_view::*;
+pub use tui_session::*;
+
+mod checklist;
+mod details_view;
+mod menu;
+mod modal;
+mod progress;
+mod table_view;
+mod tui_session;
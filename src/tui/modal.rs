@@ -0,0 +1,50 @@
+use crate::{InputSource, TuiSession};
+use ratatui::layout::Alignment;
+use ratatui::style::Stylize;
+use ratatui::widgets::{Block, Clear, Paragraph, Wrap};
+use termion::event::Key;
+
+const MODAL_WIDTH: u16 = 60;
+
+/// Confirmation dialog in the middle of the screen.
+pub struct Modal<'a> {
+    title: &'a str,
+    message: &'a str,
+}
+
+impl<'a> Modal<'a> {
+    pub fn new(title: &'a str, message: &'a str) -> Self {
+        Modal { title, message }
+    }
+
+    /// Only `y` confirms, any other key cancels. For the actions that cannot be undone.
+    pub async fn confirm(&self, input: &dyn InputSource) -> bool {
+        let mut session = TuiSession::start();
+        session.draw(|frame| self.render(frame, "y confirm · any other key cancel"));
+
+        input.read_key().await == Key::Char('y')
+    }
+
+    /// Any key continues, except Esc which cancels.
+    pub async fn acknowledge(&self, input: &dyn InputSource) -> bool {
+        let mut session = TuiSession::start();
+        session.draw(|frame| self.render(frame, "any key continue · esc cancel"));
+
+        !matches!(input.read_key().await, Key::Esc | Key::Ctrl('c'))
+    }
+
+    fn render(&self, frame: &mut ratatui::Frame, keys: &str) {
+        let body = TuiSession::render_screen(frame, self.title, keys);
+
+        // The borders and an empty line around the message
+        let height = self.message.lines().count() as u16 + 4;
+        let area = TuiSession::centered_area(body, MODAL_WIDTH, height);
+
+        let dialog = Paragraph::new(format!("\n{}", self.message))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false })
+            .block(Block::bordered().title(self.title).yellow());
+        frame.render_widget(Clear, area);
+        frame.render_widget(dialog, area);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{Output, TuiSession, SPINNER_FRAMES};
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Clear, Paragraph};
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Mutex;
+use tokio::time::{interval, Duration, Instant};
+
+const PROGRESS_WIDTH: u16 = 60;
+
+// Steps reported while a progress screen is shown, `None` when there is none
+static STEPS: Mutex<Option<Vec<String>>> = Mutex::new(None);
+
+/// Progress screen of a long operation, e.g. publishing a DID document on the ledger.
+pub struct Progress;
+
+impl Progress {
+    /// Awaits `operation`, showing a spinner, the elapsed time and the steps reported with
+    /// [`Output::print_during_loading`] meanwhile.
+    pub async fn run<F: Future>(title: &str, operation: F) -> F::Output {
+        *STEPS.lock().unwrap() = Some(Vec::new());
+
+        let mut session = TuiSession::start();
+        let mut operation = pin!(operation);
+        let mut ticker = interval(Duration::from_millis(100));
+        let start = Instant::now();
+        let mut frame_index = 0;
+
+        let output = loop {
+            tokio::select! {
+                output = &mut operation => break output,
+                _ = ticker.tick() => {
+                    let steps = STEPS.lock().unwrap().clone().unwrap_or_default();
+                    let spinner = SPINNER_FRAMES[frame_index % SPINNER_FRAMES.len()];
+                    session.draw(|frame| {
+                        Self::render(frame, title, spinner, &steps, start.elapsed())
+                    });
+                    frame_index += 1;
+                }
+            }
+        };
+        drop(session);
+
+        // Keep the steps on the screen once the progress screen is gone
+        let steps = STEPS.lock().unwrap().take().unwrap_or_default();
+        for step in steps {
+            Output::print_during_loading(&step);
+        }
+
+        output
+    }
+
+    /// Adds a step to the progress screen. Returns `false` when no progress screen is shown.
+    pub fn push_step(message: &str) -> bool {
+        match STEPS.lock().unwrap().as_mut() {
+            Some(steps) => {
+                steps.push(message.to_string());
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn render(
+        frame: &mut ratatui::Frame,
+        title: &str,
+        spinner: &str,
+        steps: &[String],
+        elapsed: Duration,
+    ) {
+        let body = TuiSession::render_screen(frame, title, "please wait");
+
+        let mut lines: Vec<Line> = steps
+            .iter()
+            .map(|step| Line::from(format!("  {}", step)))
+            .collect();
+        lines.push(Line::default());
+        lines.push(Line::from(format!("{} {} s", spinner, elapsed.as_secs())).yellow());
+
+        // The borders and an empty line above the steps
+        let height = lines.len() as u16 + 3;
+        let area = TuiSession::centered_area(body, PROGRESS_WIDTH, height);
+        let mut content = vec![Line::default()];
+        content.extend(lines);
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(content).block(Block::bordered().title(title)),
+            area,
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{InputSource, TuiSession};
+use anyhow::Result;
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState, Wrap};
+use termion::event::Key;
+
+const BROWSE_KEYS: &str = "↑/↓ move · pgup/pgdn page · / filter · s sort · r reverse · q/esc quit";
+const SELECT_KEYS: &str =
+    "↑/↓ move · pgup/pgdn page · / filter · s sort · r reverse · enter select · q/esc back";
+
+/// Filter and sort of a [`TableView`], applied by whoever loads its rows, e.g. the database.
+pub trait TableQuery: Default {
+    /// Text the rows must match.
+    fn text(&self) -> &str;
+
+    fn text_mut(&mut self) -> &mut String;
+
+    /// Sorts by the next column.
+    fn next_sort(&mut self);
+
+    /// Swaps between ascending and descending order.
+    fn reverse(&mut self);
+
+    /// Short description of the sort, e.g. `created asc`.
+    fn describe(&self) -> String;
+}
+
+/// Scrollable table, with the details of the highlighted row in a side panel.
+pub struct TableView<'a, T> {
+    title: &'a str,
+    header: Vec<&'a str>,
+    widths: Vec<Constraint>,
+    row: fn(&T) -> Vec<String>,
+    details: fn(&T) -> Vec<(&'static str, String)>,
+    page_size: usize,
+}
+
+impl<'a, T: Clone> TableView<'a, T> {
+    /// `row` gives the cells of an item, in the order of `header`, and `details` the fields
+    /// shown in the side panel.
+    pub fn new(
+        title: &'a str,
+        header: Vec<&'a str>,
+        widths: Vec<Constraint>,
+        row: fn(&T) -> Vec<String>,
+        details: fn(&T) -> Vec<(&'static str, String)>,
+    ) -> Self {
+        TableView {
+            title,
+            header,
+            widths,
+            row,
+            details,
+            page_size: 10,
+        }
+    }
+
+    /// Number of rows skipped by page up and page down.
+    pub fn page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Lets the user browse the items returned by `load` until they quit.
+    pub async fn show<Q: TableQuery>(
+        &self,
+        input: &dyn InputSource,
+        load: impl Fn(&Q) -> Result<Vec<T>>,
+    ) {
+        self.run(input, load, false).await;
+    }
+
+    /// Asks the user to choose one of the items returned by `load`. Returns `None` when the
+    /// user goes back.
+    pub async fn select<Q: TableQuery>(
+        &self,
+        input: &dyn InputSource,
+        load: impl Fn(&Q) -> Result<Vec<T>>,
+    ) -> Option<T> {
+        self.run(input, load, true).await
+    }
+
+    async fn run<Q: TableQuery>(
+        &self,
+        input: &dyn InputSource,
+        load: impl Fn(&Q) -> Result<Vec<T>>,
+        selectable: bool,
+    ) -> Option<T> {
+        let mut session = TuiSession::start();
+        let mut query = Q::default();
+        let mut editing_filter = false;
+        let mut result = load(&query);
+        let mut state = TableState::default().with_selected(Some(0));
+
+        loop {
+            let items: &[T] = result.as_deref().unwrap_or_default();
+            let error = result.as_ref().err().map(|e| e.to_string());
+            session.draw(|frame| {
+                let status = Self::status_line(&query, items.len(), editing_filter, error);
+                self.render(frame, items, &mut state, status, selectable)
+            });
+
+            let selected = state.selected().unwrap_or(0);
+            let last = items.len().saturating_sub(1);
+            let key = input.read_key().await;
+
+            // While the filter is edited every character goes to it, the list follows each change
+            if editing_filter {
+                match key {
+                    Key::Char('\n') => editing_filter = false,
+                    Key::Esc => {
+                        query.text_mut().clear();
+                        editing_filter = false;
+                    }
+                    Key::Backspace => {
+                        query.text_mut().pop();
+                    }
+                    Key::Char(c) => query.text_mut().push(c),
+                    _ => continue,
+                }
+                result = load(&query);
+                state.select(Some(0));
+                continue;
+            }
+
+            match key {
+                Key::Up | Key::Char('k') => state.select(Some(selected.saturating_sub(1))),
+                Key::Down | Key::Char('j') => state.select(Some(usize::min(selected + 1, last))),
+                Key::PageUp | Key::Char('p') => {
+                    state.select(Some(selected.saturating_sub(self.page_size)))
+                }
+                Key::PageDown | Key::Char(' ') => {
+                    state.select(Some(usize::min(selected + self.page_size, last)))
+                }
+                Key::Home => state.select(Some(0)),
+                Key::End => state.select(Some(last)),
+                Key::Char('/') => editing_filter = true,
+                Key::Char('s') => {
+                    query.next_sort();
+                    result = load(&query);
+                    state.select(Some(0));
+                }
+                Key::Char('r') => {
+                    query.reverse();
+                    result = load(&query);
+                    state.select(Some(0));
+                }
+                Key::Char('\n') if selectable && !items.is_empty() => {
+                    return items.get(selected).cloned()
+                }
+                Key::Char('q') | Key::Esc | Key::Ctrl('c') => return None,
+                _ => {}
+            }
+        }
+    }
+
+    fn status_line<Q: TableQuery>(
+        query: &Q,
+        count: usize,
+        editing_filter: bool,
+        error: Option<String>,
+    ) -> Line<'static> {
+        if let Some(error) = error {
+            return Line::from(format!("Error: {}", error)).red();
+        }
+
+        let cursor = if editing_filter { "_" } else { "" };
+        let filter = Span::from(format!("Filter: {}{}", query.text(), cursor));
+        let filter = if editing_filter {
+            filter.yellow()
+        } else {
+            filter
+        };
+        Line::from(vec![
+            filter,
+            Span::from(format!(
+                "  ·  Sort: {}  ·  {} rows",
+                query.describe(),
+                count
+            )),
+        ])
+    }
+
+    fn render(
+        &self,
+        frame: &mut ratatui::Frame,
+        items: &[T],
+        state: &mut TableState,
+        status: Line,
+        selectable: bool,
+    ) {
+        let keys = if selectable { SELECT_KEYS } else { BROWSE_KEYS };
+        let body = TuiSession::render_screen(frame, self.title, keys);
+        let [status_area, body] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(body);
+        frame.render_widget(Paragraph::new(status), status_area);
+
+        let [table_area, details_area] =
+            Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(body);
+
+        let header = Row::new(self.header.iter().map(|title| Cell::from(*title)))
+            .style(Style::new().add_modifier(Modifier::BOLD));
+        let rows = items.iter().map(|item| Row::new((self.row)(item)));
+        let position = match items.len() {
+            0 => " 0/0 ".to_string(),
+            count => format!(" {}/{} ", state.selected().unwrap_or(0) + 1, count),
+        };
+        let table = Table::new(rows, self.widths.clone())
+            .header(header)
+            .block(Block::bordered().title_bottom(Line::from(position).right_aligned()))
+            .highlight_symbol("> ")
+            .row_highlight_style(Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+        frame.render_stateful_widget(table, table_area, state);
+
+        let details: Vec<Line> = match state.selected().and_then(|index| items.get(index)) {
+            Some(item) => (self.details)(item)
+                .into_iter()
+                .flat_map(|(name, value)| {
+                    [
+                        Line::from(Span::from(name).bold().blue()),
+                        Line::from(value),
+                        Line::default(),
+                    ]
+                })
+                .collect(),
+            None => vec![Line::from("Nothing to show")],
+        };
+        frame.render_widget(
+            Paragraph::new(details)
+                .wrap(Wrap { trim: false })
+                .block(Block::bordered().title(" Details ")),
+            details_area,
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use crate::Output;
+use crossterm::cursor::Show;
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::backend::{CrosstermBackend, TestBackend};
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::{Frame, Terminal};
+use std::io::{stdout, Stdout};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Size of the in-memory screen used when the terminal is not enabled
+const HEADLESS_WIDTH: u16 = 120;
+const HEADLESS_HEIGHT: u16 = 40;
+
+// Only set by the binary when stdout is a terminal, so tests and scripts never touch it
+static TERMINAL_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Full-screen session of a widget: the terminal is switched to the alternate screen and raw
+/// mode while it lives, and restored when it is dropped.
+///
+/// When the terminal is not enabled the widgets are drawn on an in-memory screen, so they can
+/// be driven by an [`InputSource`](crate::InputSource) without a terminal.
+pub struct TuiSession {
+    terminal: SessionTerminal,
+}
+
+enum SessionTerminal {
+    Terminal(Terminal<CrosstermBackend<Stdout>>),
+    Headless(Terminal<TestBackend>),
+}
+
+impl TuiSession {
+    pub fn set_terminal_enabled(enabled: bool) {
+        TERMINAL_ENABLED.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn start() -> Self {
+        let terminal = if TERMINAL_ENABLED.load(Ordering::Relaxed) {
+            enable_raw_mode().unwrap();
+            execute!(stdout(), EnterAlternateScreen).unwrap();
+            SessionTerminal::Terminal(Terminal::new(CrosstermBackend::new(stdout())).unwrap())
+        } else {
+            let backend = TestBackend::new(HEADLESS_WIDTH, HEADLESS_HEIGHT);
+            SessionTerminal::Headless(Terminal::new(backend).unwrap())
+        };
+
+        TuiSession { terminal }
+    }
+
+    /// Leaves the raw mode and the alternate screen, for when the process exits while a session
+    /// may be running and is never dropped.
+    pub fn restore_terminal() {
+        if TERMINAL_ENABLED.load(Ordering::Relaxed) {
+            let _ = disable_raw_mode();
+            let _ = execute!(stdout(), LeaveAlternateScreen, Show);
+        }
+    }
+
+    pub fn draw<F: FnOnce(&mut Frame)>(&mut self, render: F) {
+        match &mut self.terminal {
+            SessionTerminal::Terminal(terminal) => {
+                terminal.draw(render).unwrap();
+            }
+            SessionTerminal::Headless(terminal) => {
+                terminal.draw(render).unwrap();
+            }
+        }
+    }
+
+    /// Draws the header with the breadcrumbs and `title`, and the footer with the `keys`
+    /// help. Returns the area left for the body of the screen.
+    pub fn render_screen(frame: &mut Frame, title: &str, keys: &str) -> Rect {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        // The trail is only worth showing below the main menu
+        let mut breadcrumbs = Output::breadcrumbs();
+        let trail = if breadcrumbs.is_empty() {
+            String::new()
+        } else {
+            breadcrumbs.push(title.to_string());
+            breadcrumbs.join(" > ")
+        };
+        let header_lines = vec![
+            Line::from(trail).style(Style::new().fg(Color::DarkGray)),
+            Line::from(title.to_string())
+                .style(Style::new().fg(Color::Blue).add_modifier(Modifier::BOLD)),
+        ];
+        frame.render_widget(Paragraph::new(header_lines), header);
+        frame.render_widget(Paragraph::new(keys.to_string()).dark_gray(), footer);
+
+        body
+    }
+
+    /// Area of `width` x `height` in the middle of `area`, for the dialogs.
+    pub fn centered_area(area: Rect, width: u16, height: u16) -> Rect {
+        let [area] = Layout::horizontal([Constraint::Length(width)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+        area
+    }
+}
+
+impl Drop for TuiSession {
+    fn drop(&mut self) {
+        if let SessionTerminal::Terminal(terminal) = &mut self.terminal {
+            let _ = disable_raw_mode();
+            let _ = execute!(terminal.backend_mut(), LeaveAlternateScreen);
+            let _ = terminal.show_cursor();
+        }
+    }
+}
@@ -1,5 +1,7 @@
+use super::{NetworkProfile, DEFAULT_NETWORK_PROFILE};
 use dotenv::dotenv;
 use once_cell::sync::Lazy;
+use std::path::Path;
 use std::{collections::HashMap, env};
 
 // Trait definition for configuration management
@@ -13,6 +15,8 @@ pub struct VariablesConfig {
     config: HashMap<String, String>,
     vc_table_size: usize,
     did_table_size: usize,
+    /// The error of the `NETWORK_PROFILES_FILE` when it cannot be read.
+    network_profiles: Result<Vec<NetworkProfile>, String>,
 }
 
 // Singleton instance of VariablesConfig
@@ -23,11 +27,11 @@ static CONFIG: Lazy<VariablesConfig> = Lazy::new(|| {
         config: HashMap::from([
             (
                 "api_endpoint".to_string(),
-                env::var("API_ENDPOINT").expect("API_ENDPOINT must be set"),
+                env::var("API_ENDPOINT").unwrap_or_default(),
             ),
             (
                 "faucet_endpoint".to_string(),
-                env::var("FAUCET_ENDPOINT").expect("FAUCET_ENDPOINT must be set"),
+                env::var("FAUCET_ENDPOINT").unwrap_or_default(),
             ),
             (
                 "stronghold_password".to_string(),
@@ -43,7 +47,7 @@ static CONFIG: Lazy<VariablesConfig> = Lazy::new(|| {
             ),
             (
                 "network_address".to_string(),
-                env::var("NETWORK_ADDRESS").expect("NETWORK_ADDRESS must be set"),
+                env::var("NETWORK_ADDRESS").unwrap_or_default(),
             ),
             (
                 "credentials_template_directory".to_string(),
@@ -54,12 +58,62 @@ static CONFIG: Lazy<VariablesConfig> = Lazy::new(|| {
                 "credentials_sd_directory".to_string(),
                 env::var("CREDENTIALS_SD_DIRECTORY").expect("CREDENTIALS_SD_DIRECTORY must be set"),
             ),
+            (
+                "credentials_validity_file".to_string(),
+                env::var("CREDENTIALS_VALIDITY_FILE").unwrap_or_default(),
+            ),
+            (
+                "network_profile".to_string(),
+                env::var("NETWORK_PROFILE").unwrap_or_default(),
+            ),
+            (
+                "did_web_directory".to_string(),
+                env::var("DID_WEB_DIRECTORY").unwrap_or("did_web".to_string()),
+            ),
+            (
+                "did_web_base_url".to_string(),
+                env::var("DID_WEB_BASE_URL").unwrap_or_default(),
+            ),
+            (
+                "status_list_directory".to_string(),
+                env::var("STATUS_LIST_DIRECTORY").unwrap_or("status_lists".to_string()),
+            ),
         ]),
         vc_table_size: 10,
         did_table_size: 10,
+        network_profiles: network_profiles(),
     }
 });
 
+// The built-in profiles, the one of the environment endpoints and the ones of the
+// `NETWORK_PROFILES_FILE`, which replace any built-in one with the same name
+fn network_profiles() -> Result<Vec<NetworkProfile>, String> {
+    let mut profiles = NetworkProfile::builtin();
+
+    let api_endpoint = env::var("API_ENDPOINT").unwrap_or_default();
+    if !api_endpoint.is_empty() {
+        let faucet = env::var("FAUCET_ENDPOINT").ok().filter(|faucet| !faucet.is_empty());
+        profiles.push(NetworkProfile::new(
+            DEFAULT_NETWORK_PROFILE,
+            &[&api_endpoint],
+            faucet.as_deref(),
+            &env::var("NETWORK_HRP").unwrap_or("tst".to_string()),
+        ));
+    }
+
+    if let Ok(path) = env::var("NETWORK_PROFILES_FILE") {
+        let custom = NetworkProfile::from_file(Path::new(&path)).map_err(|e| {
+            format!(
+                "NETWORK_PROFILES_FILE must be a JSON array of network profiles: {:#}",
+                e
+            )
+        })?;
+        profiles.retain(|profile| !custom.iter().any(|other| other.name == profile.name));
+        profiles.extend(custom);
+    }
+    Ok(profiles)
+}
+
 impl VariablesConfig {
     pub fn get() -> &'static Self {
         &CONFIG
@@ -72,6 +126,26 @@ impl VariablesConfig {
     pub fn did_table_size(&self) -> usize {
         self.did_table_size
     }
+
+    pub fn network_profiles(&self) -> anyhow::Result<&[NetworkProfile]> {
+        match &self.network_profiles {
+            Ok(profiles) => Ok(profiles),
+            Err(e) => Err(anyhow::anyhow!(e.clone())),
+        }
+    }
+
+    /// The profile called `name`, or else the one of `NETWORK_PROFILE`. Without either, the
+    /// profile of the environment endpoints if they are set, the testnet otherwise.
+    pub fn network_profile(&self, name: Option<&str>) -> anyhow::Result<&NetworkProfile> {
+        let configured = self.get_value("network_profile");
+        let name = match name {
+            Some(name) => name,
+            None if !configured.is_empty() => configured,
+            None if !self.get_value("api_endpoint").is_empty() => DEFAULT_NETWORK_PROFILE,
+            None => "testnet",
+        };
+        NetworkProfile::find(self.network_profiles()?, name)
+    }
 }
 
 // Implementation of the Config trait for VariablesConfig
@@ -0,0 +1,45 @@
+use clap::ValueEnum;
+
+/// DID method of the identities Petrus creates.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum DidKind {
+    /// Published in an Alias Output, needs a funded address and a node.
+    #[default]
+    Iota,
+    /// `did:key`, the public key encoded in the DID itself.
+    Key,
+    /// `did:jwk`, the public JWK encoded in the DID itself.
+    Jwk,
+    /// `did:web`, a document hosted on a domain of ours.
+    Web,
+}
+
+impl DidKind {
+    pub fn all() -> [DidKind; 4] {
+        [DidKind::Iota, DidKind::Key, DidKind::Jwk, DidKind::Web]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DidKind::Iota => "did:iota (published on the ledger)",
+            DidKind::Key => "did:key (offline)",
+            DidKind::Jwk => "did:jwk (offline)",
+            DidKind::Web => "did:web (hosted on a domain)",
+        }
+    }
+
+    /// Method name, as in the DIDs and the `method` column of the `dids` table.
+    pub fn method(&self) -> &'static str {
+        match self {
+            DidKind::Iota => "iota",
+            DidKind::Key => "key",
+            DidKind::Jwk => "jwk",
+            DidKind::Web => "web",
+        }
+    }
+
+    /// Whether the DID is resolved from the DID itself, without a node.
+    pub fn is_offline(&self) -> bool {
+        matches!(self, DidKind::Key | DidKind::Jwk)
+    }
+}
@@ -0,0 +1,152 @@
+use super::KeyAlgorithm;
+use identity_iota::core::{FromJson, Object, ToJson, Url};
+use identity_iota::did::{CoreDID, DID};
+use identity_iota::document::CoreDocument;
+use identity_iota::resolver::Resolver;
+use identity_iota::storage::{JwkDocumentExt, JwkStorage, KeyIdStorage, Storage};
+use identity_iota::verification::MethodScope;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `did:web` of a document hosted at `address`, a domain with an optional port and path such
+/// as `example.com` or `localhost:8080/users/alice`.
+pub fn did_web(address: &str) -> anyhow::Result<CoreDID> {
+    let address = address.trim().trim_end_matches('/');
+    let mut segments = address.split('/');
+    let domain = segments.next().unwrap_or_default();
+    if domain.is_empty() || address.contains("://") {
+        anyhow::bail!(
+            "Invalid did:web address: {}, expected a domain and path",
+            address
+        );
+    }
+
+    let mut method_id = domain.replace(':', "%3A");
+    for segment in segments {
+        if segment.is_empty() {
+            anyhow::bail!("Invalid did:web address: {}", address);
+        }
+        method_id.push(':');
+        method_id.push_str(segment);
+    }
+    Ok(CoreDID::parse(format!("did:web:{}", method_id))?)
+}
+
+/// Path of the `did.json` of a `did:web`, relative to the root of its domain:
+/// `.well-known/did.json` without a path in the DID, `<path>/did.json` otherwise.
+fn document_path(did: &CoreDID) -> anyhow::Result<(String, String)> {
+    if did.method() != "web" {
+        anyhow::bail!("{} is not a did:web", did);
+    }
+
+    let mut segments = did.method_id().split(':');
+    let domain = segments
+        .next()
+        .unwrap_or_default()
+        .replace("%3A", ":")
+        .replace("%3a", ":");
+    let path: Vec<&str> = segments.collect();
+    let path = if path.is_empty() {
+        ".well-known/did.json".to_string()
+    } else {
+        format!("{}/did.json", path.join("/"))
+    };
+    Ok((domain, path))
+}
+
+/// URL the document of a `did:web` is fetched from. With a `base_url` the domain is looked up
+/// under it, as in `<base_url>/<domain>/.well-known/did.json`, which is also the layout of
+/// [`export_did_web`].
+pub fn did_web_url(did: &CoreDID, base_url: Option<&Url>) -> anyhow::Result<Url> {
+    let (domain, path) = document_path(did)?;
+    let url = match base_url {
+        Some(base_url) => format!(
+            "{}/{}/{}",
+            base_url.as_str().trim_end_matches('/'),
+            domain,
+            path
+        ),
+        None => format!("https://{}/{}", domain, path),
+    };
+    Ok(Url::parse(url)?)
+}
+
+/// Writes the document of a `did:web` to `<directory>/<domain>/<path>`, ready to be copied to
+/// the web server of the domain. Returns the path of the file.
+pub fn export_did_web(document: &CoreDocument, directory: &Path) -> anyhow::Result<PathBuf> {
+    let (domain, path) = document_path(document.id())?;
+    let file = directory.join(domain).join(path);
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&file, document.to_json_pretty()?)?;
+    Ok(file)
+}
+
+/// Generates a key in `storage` for a new `did:web` hosted at `address`, see [`did_web`].
+///
+/// Returns the document with the fragment of its method.
+pub async fn create_did_web<K, I>(
+    storage: &Storage<K, I>,
+    address: &str,
+    algorithm: KeyAlgorithm,
+) -> anyhow::Result<(CoreDocument, String)>
+where
+    K: JwkStorage,
+    I: KeyIdStorage,
+{
+    let mut document = CoreDocument::builder(Object::new())
+        .id(did_web(address)?)
+        .build()?;
+    let fragment = document
+        .generate_method(
+            storage,
+            algorithm.key_type(),
+            algorithm.jws_algorithm(),
+            None,
+            MethodScope::VerificationMethod,
+        )
+        .await?;
+    Ok((document, fragment))
+}
+
+/// Resolves `did:web` DIDs over HTTP, optionally from a `base_url` instead of their domain.
+#[derive(Clone, Debug)]
+pub struct DidWebResolver {
+    client: reqwest::Client,
+    base_url: Option<Url>,
+}
+
+impl DidWebResolver {
+    pub fn new(base_url: Option<Url>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url,
+        }
+    }
+
+    /// Adds the resolver as the `web` handler of `resolver`.
+    pub fn attach_to(self, resolver: &mut Resolver<CoreDocument>) {
+        resolver.attach_handler("web".to_owned(), move |did: CoreDID| {
+            let web = self.clone();
+            async move { web.resolve(&did).await }
+        });
+    }
+
+    pub async fn resolve(&self, did: &CoreDID) -> anyhow::Result<CoreDocument> {
+        let url = did_web_url(did, self.base_url.as_ref())?;
+        let response = self
+            .client
+            .get(url.as_str())
+            .send()
+            .await?
+            .error_for_status()?;
+        let document = CoreDocument::from_json_slice(&response.bytes().await?)?;
+
+        // The server could host the document of another DID
+        if document.id() != did {
+            anyhow::bail!("{} hosts the document of {}", url, document.id());
+        }
+        Ok(document)
+    }
+}
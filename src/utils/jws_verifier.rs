@@ -0,0 +1,30 @@
+use identity_ecdsa_verifier::EcDSAJwsVerifier;
+use identity_eddsa_verifier::EdDSAJwsVerifier;
+use identity_iota::verification::jwk::Jwk;
+use identity_iota::verification::jws::{
+    JwsAlgorithm, JwsVerifier, SignatureVerificationError, SignatureVerificationErrorKind,
+    VerificationInput,
+};
+
+/// Verifies JWS signatures of every algorithm Petrus generates keys for, choosing the verifier
+/// from the `alg` header.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CompositeJwsVerifier;
+
+impl JwsVerifier for CompositeJwsVerifier {
+    fn verify(
+        &self,
+        input: VerificationInput,
+        public_key: &Jwk,
+    ) -> Result<(), SignatureVerificationError> {
+        match input.alg {
+            JwsAlgorithm::EdDSA => EdDSAJwsVerifier::default().verify(input, public_key),
+            JwsAlgorithm::ES256 | JwsAlgorithm::ES256K => {
+                EcDSAJwsVerifier::default().verify(input, public_key)
+            }
+            _ => Err(SignatureVerificationError::new(
+                SignatureVerificationErrorKind::UnsupportedAlg,
+            )),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use clap::ValueEnum;
+use identity_iota::storage::{JwkMemStore, KeyType};
+use identity_iota::verification::jwk::Jwk;
+use identity_iota::verification::jws::JwsAlgorithm;
+
+/// Key type and JWS algorithm of the verification methods Petrus generates.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum KeyAlgorithm {
+    #[default]
+    Ed25519,
+    /// ECDSA on P-256, ES256.
+    P256,
+    /// ECDSA on secp256k1, ES256K.
+    Secp256k1,
+}
+
+impl KeyAlgorithm {
+    pub const P256_KEY_TYPE: KeyType = KeyType::from_static_str("P-256");
+    pub const SECP256K1_KEY_TYPE: KeyType = KeyType::from_static_str("secp256k1");
+
+    pub fn all() -> [KeyAlgorithm; 3] {
+        [
+            KeyAlgorithm::Ed25519,
+            KeyAlgorithm::P256,
+            KeyAlgorithm::Secp256k1,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            KeyAlgorithm::Ed25519 => "Ed25519 (EdDSA)",
+            KeyAlgorithm::P256 => "P-256 (ES256)",
+            KeyAlgorithm::Secp256k1 => "secp256k1 (ES256K)",
+        }
+    }
+
+    pub fn key_type(&self) -> KeyType {
+        match self {
+            KeyAlgorithm::Ed25519 => JwkMemStore::ED25519_KEY_TYPE,
+            KeyAlgorithm::P256 => Self::P256_KEY_TYPE,
+            KeyAlgorithm::Secp256k1 => Self::SECP256K1_KEY_TYPE,
+        }
+    }
+
+    pub fn jws_algorithm(&self) -> JwsAlgorithm {
+        match self {
+            KeyAlgorithm::Ed25519 => JwsAlgorithm::EdDSA,
+            KeyAlgorithm::P256 => JwsAlgorithm::ES256,
+            KeyAlgorithm::Secp256k1 => JwsAlgorithm::ES256K,
+        }
+    }
+
+    /// The algorithm of a key type, `None` for the types Petrus does not generate.
+    pub fn from_key_type(key_type: &KeyType) -> Option<Self> {
+        Self::all()
+            .into_iter()
+            .find(|algorithm| algorithm.key_type().as_str() == key_type.as_str())
+    }
+
+    /// The algorithm recorded in the database, see [`JwsAlgorithm::name`].
+    pub fn from_jws_name(name: &str) -> anyhow::Result<Self> {
+        Self::all()
+            .into_iter()
+            .find(|algorithm| algorithm.jws_algorithm().name() == name)
+            .ok_or_else(|| anyhow::anyhow!("Unsupported key algorithm: {}", name))
+    }
+
+    /// The algorithm of a public or private key, from its `alg` or else from its curve.
+    pub fn of_jwk(jwk: &Jwk) -> Option<Self> {
+        if let Some(alg) = jwk.alg() {
+            return Self::from_jws_name(alg).ok();
+        }
+        let curve = match (jwk.try_okp_params(), jwk.try_ec_params()) {
+            (Ok(okp), _) => okp.crv.as_str(),
+            (_, Ok(ec)) => ec.crv.as_str(),
+            _ => return None,
+        };
+        match curve {
+            "Ed25519" => Some(KeyAlgorithm::Ed25519),
+            "P-256" => Some(KeyAlgorithm::P256),
+            "secp256k1" => Some(KeyAlgorithm::Secp256k1),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use super::KeyAlgorithm;
+use async_trait::async_trait;
+use identity_iota::storage::{
+    JwkGenOutput, JwkStorage, JwkStorageBbsPlusExt, KeyId, KeyStorageError, KeyStorageErrorKind,
+    KeyStorageResult, KeyType, ProofUpdateCtx,
+};
+use identity_iota::verification::jwk::Jwk;
+use identity_iota::verification::jws::JwsAlgorithm;
+use identity_stronghold::StrongholdStorage;
+use iota_sdk::client::secret::SecretManager;
+use iota_sdk::client::storage::StorageAdapter;
+use iota_sdk::client::stronghold::StrongholdAdapter;
+use jsonprooftoken::jpa::algs::ProofAlgorithm;
+use p256::ecdsa::signature::Signer;
+use rand::rngs::OsRng;
+
+// The ids of the ECDSA keys start with this prefix, the other ids are Stronghold ones
+const ECDSA_KEY_ID_PREFIX: &str = "ecdsa-";
+
+/// Key storage of the app. Stronghold only generates Ed25519 and BLS12-381 keys, so the P-256
+/// and secp256k1 keys are generated here.
+///
+/// These ECDSA keys are not protected by the vault: their private JWK is kept as plain bytes in
+/// the Stronghold store, under `petrus-key:<key id>`. The snapshot encrypts the store with the
+/// same password at rest, but each signature reads the private key back into memory, which the
+/// vault never does for its own keys.
+#[derive(Clone, Debug)]
+pub struct StrongholdKeyStorage {
+    stronghold: StrongholdStorage,
+}
+
+impl StrongholdKeyStorage {
+    pub fn new(stronghold: StrongholdStorage) -> Self {
+        StrongholdKeyStorage { stronghold }
+    }
+
+    fn adapter(&self) -> KeyStorageResult<&StrongholdAdapter> {
+        match self.stronghold.as_secret_manager() {
+            SecretManager::Stronghold(adapter) => Ok(adapter),
+            _ => Err(KeyStorageError::new(KeyStorageErrorKind::Unavailable)),
+        }
+    }
+
+    fn is_ecdsa_key(key_id: &KeyId) -> bool {
+        key_id.as_str().starts_with(ECDSA_KEY_ID_PREFIX)
+    }
+
+    fn record_key(key_id: &KeyId) -> String {
+        format!("petrus-key:{}", key_id.as_str())
+    }
+
+    async fn private_jwk(&self, key_id: &KeyId) -> KeyStorageResult<Jwk> {
+        let record = self
+            .adapter()?
+            .get_bytes(&Self::record_key(key_id))
+            .await
+            .map_err(|e| storage_error(KeyStorageErrorKind::Unavailable, e))?
+            .ok_or_else(|| KeyStorageError::new(KeyStorageErrorKind::KeyNotFound))?;
+        serde_json::from_slice(&record)
+            .map_err(|e| storage_error(KeyStorageErrorKind::Unspecified, e))
+    }
+
+    async fn save_snapshot(&self) -> KeyStorageResult<()> {
+        self.adapter()?
+            .write_stronghold_snapshot(None)
+            .await
+            .map_err(|e| storage_error(KeyStorageErrorKind::RetryableIOFailure, e))
+    }
+}
+
+#[async_trait]
+impl JwkStorage for StrongholdKeyStorage {
+    async fn generate(
+        &self,
+        key_type: KeyType,
+        alg: JwsAlgorithm,
+    ) -> KeyStorageResult<JwkGenOutput> {
+        let private_jwk = match KeyAlgorithm::from_key_type(&key_type) {
+            Some(algorithm) if algorithm.jws_algorithm() != alg => {
+                return Err(KeyStorageError::new(
+                    KeyStorageErrorKind::KeyAlgorithmMismatch,
+                ));
+            }
+            Some(KeyAlgorithm::P256) => p256::SecretKey::random(&mut OsRng).to_jwk_string(),
+            Some(KeyAlgorithm::Secp256k1) => k256::SecretKey::random(&mut OsRng).to_jwk_string(),
+            Some(KeyAlgorithm::Ed25519) | None => {
+                return self.stronghold.generate(key_type, alg).await
+            }
+        };
+
+        let mut jwk: Jwk = serde_json::from_str(&private_jwk)
+            .map_err(|e| storage_error(KeyStorageErrorKind::Unspecified, e))?;
+        jwk.set_alg(alg.name());
+        let public_jwk = jwk
+            .to_public()
+            .ok_or_else(|| KeyStorageError::new(KeyStorageErrorKind::Unspecified))?;
+
+        let key_id = KeyId::new(format!("{}{}", ECDSA_KEY_ID_PREFIX, uuid::Uuid::new_v4()));
+        let record = serde_json::to_vec(&jwk)
+            .map_err(|e| storage_error(KeyStorageErrorKind::Unspecified, e))?;
+        self.adapter()?
+            .set_bytes(&Self::record_key(&key_id), &record)
+            .await
+            .map_err(|e| storage_error(KeyStorageErrorKind::Unavailable, e))?;
+        self.save_snapshot().await?;
+
+        Ok(JwkGenOutput::new(key_id, public_jwk))
+    }
+
+    async fn insert(&self, jwk: Jwk) -> KeyStorageResult<KeyId> {
+        self.stronghold.insert(jwk).await
+    }
+
+    async fn sign(
+        &self,
+        key_id: &KeyId,
+        data: &[u8],
+        public_key: &Jwk,
+    ) -> KeyStorageResult<Vec<u8>> {
+        if !Self::is_ecdsa_key(key_id) {
+            return self.stronghold.sign(key_id, data, public_key).await;
+        }
+
+        let jwk = self.private_jwk(key_id).await?;
+        // The key stored under `key_id` has to be the one of the method that signs
+        if jwk.to_public().as_ref().map(Jwk::params) != Some(public_key.params()) {
+            return Err(KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+                .with_custom_message(format!(
+                    "the key {} does not match the public key of the method",
+                    key_id
+                )));
+        }
+        // The curves only read the key fields of a JWK, its `alg` would be rejected
+        let jwk_string = serde_json::to_string(&Jwk::from_params(jwk.params().clone()))
+            .map_err(|e| storage_error(KeyStorageErrorKind::Unspecified, e))?;
+        // JWS signatures are the raw `r || s` bytes of the ECDSA signature
+        match KeyAlgorithm::of_jwk(&jwk) {
+            Some(KeyAlgorithm::P256) => {
+                let key = p256::SecretKey::from_jwk_str(&jwk_string)
+                    .map_err(|e| storage_error(KeyStorageErrorKind::Unspecified, e))?;
+                let signature: p256::ecdsa::Signature =
+                    p256::ecdsa::SigningKey::from(key).sign(data);
+                Ok(signature.to_bytes().to_vec())
+            }
+            Some(KeyAlgorithm::Secp256k1) => {
+                let key = k256::SecretKey::from_jwk_str(&jwk_string)
+                    .map_err(|e| storage_error(KeyStorageErrorKind::Unspecified, e))?;
+                let signature: k256::ecdsa::Signature =
+                    k256::ecdsa::SigningKey::from(key).sign(data);
+                Ok(signature.to_bytes().to_vec())
+            }
+            _ => Err(KeyStorageError::new(
+                KeyStorageErrorKind::UnsupportedKeyType,
+            )),
+        }
+    }
+
+    async fn delete(&self, key_id: &KeyId) -> KeyStorageResult<()> {
+        if !Self::is_ecdsa_key(key_id) {
+            return self.stronghold.delete(key_id).await;
+        }
+
+        // Fails with KeyNotFound if there is no such key
+        self.private_jwk(key_id).await?;
+        self.adapter()?
+            .delete(&Self::record_key(key_id))
+            .await
+            .map_err(|e| storage_error(KeyStorageErrorKind::Unavailable, e))?;
+        self.save_snapshot().await
+    }
+
+    async fn exists(&self, key_id: &KeyId) -> KeyStorageResult<bool> {
+        if !Self::is_ecdsa_key(key_id) {
+            return self.stronghold.exists(key_id).await;
+        }
+
+        match self.private_jwk(key_id).await {
+            Ok(_) => Ok(true),
+            Err(e) if matches!(e.kind(), KeyStorageErrorKind::KeyNotFound) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+// Stronghold generates and signs with BLS12-381 keys itself
+#[async_trait]
+impl JwkStorageBbsPlusExt for StrongholdKeyStorage {
+    async fn generate_bbs(
+        &self,
+        key_type: KeyType,
+        alg: ProofAlgorithm,
+    ) -> KeyStorageResult<JwkGenOutput> {
+        self.stronghold.generate_bbs(key_type, alg).await
+    }
+
+    async fn sign_bbs(
+        &self,
+        key_id: &KeyId,
+        data: &[Vec<u8>],
+        header: &[u8],
+        public_key: &Jwk,
+    ) -> KeyStorageResult<Vec<u8>> {
+        self.stronghold
+            .sign_bbs(key_id, data, header, public_key)
+            .await
+    }
+
+    async fn update_signature(
+        &self,
+        key_id: &KeyId,
+        public_key: &Jwk,
+        signature: &[u8],
+        ctx: ProofUpdateCtx,
+    ) -> KeyStorageResult<Vec<u8>> {
+        self.stronghold
+            .update_signature(key_id, public_key, signature, ctx)
+            .await
+    }
+}
+
+fn storage_error(kind: KeyStorageErrorKind, source: impl std::fmt::Display) -> KeyStorageError {
+    KeyStorageError::new(kind).with_custom_message(source.to_string())
+}
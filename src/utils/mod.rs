@@ -1,9 +1,33 @@
-pub use config::VariablesConfig;
 pub use config::Config;
+pub use config::VariablesConfig;
+pub use did_kind::*;
+pub use did_web::*;
+pub use jws_verifier::*;
+pub use key_algorithm::*;
+pub use key_storage::*;
+pub use network_profile::*;
+pub use offline_did::*;
+pub use revocation::*;
+pub use status_list::*;
+pub use token_inspector::*;
 pub use utils::*;
+pub use validity::*;
+pub use verification_report::*;
 
 
 
 mod config;
+mod did_kind;
+mod did_web;
+mod jws_verifier;
+mod key_algorithm;
+mod key_storage;
+mod network_profile;
+mod offline_did;
+mod revocation;
+mod status_list;
+mod token_inspector;
 mod utils;
+mod validity;
+mod verification_report;
 
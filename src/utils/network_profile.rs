@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Name of the profile made of the `API_ENDPOINT` and `FAUCET_ENDPOINT` of the environment.
+pub const DEFAULT_NETWORK_PROFILE: &str = "default";
+
+/// Nodes and faucet of an IOTA network the DIDs can be published on.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NetworkProfile {
+    pub name: String,
+    /// Nodes of the network, the first one is tried first.
+    pub endpoints: Vec<String>,
+    /// Faucet endpoint, on the networks that have one.
+    #[serde(default)]
+    pub faucet: Option<String>,
+    /// Bech32 HRP of the addresses, which is also the network name in the IOTA DIDs.
+    pub hrp: String,
+}
+
+impl NetworkProfile {
+    pub fn new(name: &str, endpoints: &[&str], faucet: Option<&str>, hrp: &str) -> Self {
+        NetworkProfile {
+            name: name.to_string(),
+            endpoints: endpoints.iter().map(|endpoint| endpoint.to_string()).collect(),
+            faucet: faucet.map(str::to_string),
+            hrp: hrp.to_string(),
+        }
+    }
+
+    /// Public networks known to Petrus, and the IOTA sandbox for private tangles.
+    pub fn builtin() -> Vec<NetworkProfile> {
+        vec![
+            NetworkProfile::new(
+                "testnet",
+                &["https://api.testnet.iotaledger.net"],
+                Some("https://faucet.testnet.iotaledger.net/api/enqueue"),
+                "tst",
+            ),
+            NetworkProfile::new(
+                "iota",
+                &["https://api.stardust-mainnet.iotaledger.net"],
+                None,
+                "iota",
+            ),
+            NetworkProfile::new(
+                "shimmer",
+                &["https://api.shimmer.network"],
+                None,
+                "smr",
+            ),
+            NetworkProfile::new(
+                "shimmer-testnet",
+                &["https://api.testnet.shimmer.network"],
+                Some("https://faucet.testnet.shimmer.network/api/enqueue"),
+                "rms",
+            ),
+            NetworkProfile::new(
+                "sandbox",
+                &["http://localhost"],
+                Some("http://localhost/faucet/api/enqueue"),
+                "tst",
+            ),
+        ]
+    }
+
+    /// Reads the profiles of private tangles from a JSON file holding an array of profiles.
+    pub fn from_file(path: &Path) -> anyhow::Result<Vec<NetworkProfile>> {
+        let profiles: Vec<NetworkProfile> = serde_json::from_slice(&std::fs::read(path)?)?;
+        if let Some(profile) = profiles.iter().find(|profile| profile.endpoints.is_empty()) {
+            anyhow::bail!("The network profile {} has no endpoints", profile.name);
+        }
+        Ok(profiles)
+    }
+
+    /// The profile called `name` among `profiles`.
+    pub fn find<'a>(
+        profiles: &'a [NetworkProfile],
+        name: &str,
+    ) -> anyhow::Result<&'a NetworkProfile> {
+        profiles
+            .iter()
+            .find(|profile| profile.name == name)
+            .ok_or_else(|| {
+                let names: Vec<&str> = profiles.iter().map(|profile| profile.name.as_str()).collect();
+                anyhow::anyhow!(
+                    "Unknown network profile {}. Available: {}",
+                    name,
+                    names.join(", ")
+                )
+            })
+    }
+
+    pub fn has_faucet(&self) -> bool {
+        self.faucet.is_some()
+    }
+
+    pub fn label(&self) -> String {
+        let faucet = if self.has_faucet() { ", faucet" } else { "" };
+        format!(
+            "{} ({}{})",
+            self.name,
+            self.endpoints.first().map(String::as_str).unwrap_or("no nodes"),
+            faucet
+        )
+    }
+}
@@ -0,0 +1,169 @@
+use super::{DidKind, KeyAlgorithm};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use identity_iota::core::{BaseEncoding, Object};
+use identity_iota::did::{CoreDID, DID};
+use identity_iota::document::CoreDocument;
+use identity_iota::storage::{JwkGenOutput, JwkStorage, KeyIdStorage, MethodDigest, Storage};
+use identity_iota::verification::jwk::{Jwk, JwkParamsOkp, JwkUse};
+use identity_iota::verification::{MethodRelationship, MethodScope, VerificationMethod};
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+
+// Multicodec prefixes of the public keys of a `did:key`, as unsigned varints
+const ED25519_CODEC: [u8; 2] = [0xed, 0x01];
+const P256_CODEC: [u8; 2] = [0x80, 0x24];
+const SECP256K1_CODEC: [u8; 2] = [0xe7, 0x01];
+
+const SIGNING_RELATIONSHIPS: [MethodRelationship; 4] = [
+    MethodRelationship::Authentication,
+    MethodRelationship::AssertionMethod,
+    MethodRelationship::CapabilityInvocation,
+    MethodRelationship::CapabilityDelegation,
+];
+
+/// `did:key` of a public key: the key with its multicodec prefix, in base58btc. The EC keys
+/// are compressed.
+pub fn did_key(public_jwk: &Jwk) -> anyhow::Result<CoreDID> {
+    let jwk_string = serde_json::to_string(public_jwk)?;
+    let (codec, key) = match KeyAlgorithm::of_jwk(public_jwk) {
+        Some(KeyAlgorithm::Ed25519) => (
+            ED25519_CODEC,
+            URL_SAFE_NO_PAD.decode(&public_jwk.try_okp_params()?.x)?,
+        ),
+        Some(KeyAlgorithm::P256) => (
+            P256_CODEC,
+            p256::PublicKey::from_jwk_str(&jwk_string)?
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec(),
+        ),
+        Some(KeyAlgorithm::Secp256k1) => (
+            SECP256K1_CODEC,
+            k256::PublicKey::from_jwk_str(&jwk_string)?
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec(),
+        ),
+        None => anyhow::bail!("A did:key cannot be made from this key"),
+    };
+
+    let multibase = BaseEncoding::encode_multibase(&[codec.as_slice(), &key].concat(), None);
+    Ok(CoreDID::parse(format!("did:key:{}", multibase))?)
+}
+
+/// `did:jwk` of a public key: the JWK in base64url.
+pub fn did_jwk(public_jwk: &Jwk) -> anyhow::Result<CoreDID> {
+    let encoded = URL_SAFE_NO_PAD.encode(serde_json::to_vec(public_jwk)?);
+    Ok(CoreDID::parse(format!("did:jwk:{}", encoded))?)
+}
+
+/// Document of a `did:key` or `did:jwk`, which is derived from the DID alone.
+///
+/// Its single method is `#0` for a `did:jwk` and the multibase key for a `did:key`. It is
+/// referenced by every signing relationship, and by `keyAgreement` when the JWK of a
+/// `did:jwk` is not restricted to signatures.
+pub fn expand_offline_did(did: &CoreDID) -> anyhow::Result<CoreDocument> {
+    let (jwk, fragment, key_agreement) = match did.method() {
+        "key" => (
+            did_key_jwk(did.method_id())?,
+            did.method_id().to_string(),
+            false,
+        ),
+        "jwk" => {
+            let jwk: Jwk = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(did.method_id())?)?;
+            let key_agreement = jwk.use_() != Some(JwkUse::Signature);
+            (jwk, "0".to_string(), key_agreement)
+        }
+        method => anyhow::bail!("did:{} is not resolved offline", method),
+    };
+
+    let mut document = CoreDocument::builder(Object::new())
+        .id(did.clone())
+        .build()?;
+    let method = VerificationMethod::new_from_jwk(did.clone(), jwk, Some(&fragment))?;
+    let method_id = method.id().clone();
+    document.insert_method(method, MethodScope::VerificationMethod)?;
+
+    let mut relationships = SIGNING_RELATIONSHIPS.to_vec();
+    if key_agreement {
+        relationships.push(MethodRelationship::KeyAgreement);
+    }
+    for relationship in relationships {
+        document.attach_method_relationship(&method_id, relationship)?;
+    }
+    Ok(document)
+}
+
+/// Generates a key in `storage` and makes an offline DID of `kind` from it. The key is
+/// registered for the method of the DID, so the document can sign like a published one.
+///
+/// Returns the document with the fragment of its method.
+pub async fn create_offline_did<K, I>(
+    storage: &Storage<K, I>,
+    kind: DidKind,
+    algorithm: KeyAlgorithm,
+) -> anyhow::Result<(CoreDocument, String)>
+where
+    K: JwkStorage,
+    I: KeyIdStorage,
+{
+    let JwkGenOutput { key_id, jwk, .. } = storage
+        .key_storage()
+        .generate(algorithm.key_type(), algorithm.jws_algorithm())
+        .await?;
+    let did = match kind {
+        DidKind::Key => did_key(&jwk)?,
+        DidKind::Jwk => did_jwk(&jwk)?,
+        DidKind::Iota | DidKind::Web => {
+            anyhow::bail!("A did:{} is not made from its key alone", kind.method())
+        }
+    };
+
+    let document = expand_offline_did(&did)?;
+    let method = document
+        .methods(None)
+        .into_iter()
+        .next()
+        .ok_or(anyhow::anyhow!("Methods not Found"))?;
+    let fragment = method.id().fragment().unwrap_or_default().to_string();
+    storage
+        .key_id_storage()
+        .insert_key_id(MethodDigest::new(method)?, key_id)
+        .await?;
+
+    Ok((document, fragment))
+}
+
+/// Resolver handler of the `did:key` and `did:jwk` methods, which need no node.
+pub async fn resolve_offline_did(did: CoreDID) -> anyhow::Result<CoreDocument> {
+    expand_offline_did(&did)
+}
+
+fn did_key_jwk(multibase: &str) -> anyhow::Result<Jwk> {
+    let bytes = BaseEncoding::decode_multibase(multibase)?;
+    if bytes.len() < ED25519_CODEC.len() {
+        anyhow::bail!("Invalid did:key: {}", multibase);
+    }
+
+    let (codec, key) = bytes.split_at(2);
+    let (mut jwk, algorithm): (Jwk, _) = match [codec[0], codec[1]] {
+        ED25519_CODEC => {
+            let mut params = JwkParamsOkp::new();
+            params.crv = "Ed25519".to_string();
+            params.x = URL_SAFE_NO_PAD.encode(key);
+            (Jwk::from_params(params), KeyAlgorithm::Ed25519)
+        }
+        P256_CODEC => {
+            let jwk_string = p256::PublicKey::from_sec1_bytes(key)?.to_jwk_string();
+            (serde_json::from_str(&jwk_string)?, KeyAlgorithm::P256)
+        }
+        SECP256K1_CODEC => {
+            let jwk_string = k256::PublicKey::from_sec1_bytes(key)?.to_jwk_string();
+            (serde_json::from_str(&jwk_string)?, KeyAlgorithm::Secp256k1)
+        }
+        _ => anyhow::bail!("Unsupported key in did:key: {}", multibase),
+    };
+    // The method signs with the `alg` of its JWK, which the key bytes do not carry
+    jwk.set_alg(algorithm.jws_algorithm().name());
+    Ok(jwk)
+}
@@ -0,0 +1,73 @@
+use anyhow::Context;
+use identity_iota::credential::{
+    Credential, JwtCredentialValidatorUtils, JwtValidationError, RevocationBitmap,
+    RevocationBitmapStatus, Status, StatusCheck,
+};
+use identity_iota::did::{DIDUrl, DID};
+use identity_iota::document::{CoreDocument, Service};
+
+/// Fragment of the revocation bitmap service attached to the issuers.
+pub const REVOCATION_SERVICE_FRAGMENT: &str = "revocation";
+
+/// The RevocationBitmap2022 service of `document`, if it has one.
+pub fn revocation_service(document: &CoreDocument) -> Option<&Service> {
+    document
+        .service()
+        .iter()
+        .find(|service| service.type_().contains(RevocationBitmap::TYPE))
+}
+
+/// An empty revocation bitmap service for `document`, nothing revoked yet.
+pub fn new_revocation_service(document: &CoreDocument) -> anyhow::Result<Service> {
+    let id: DIDUrl = document
+        .id()
+        .to_url()
+        .join(format!("#{}", REVOCATION_SERVICE_FRAGMENT))?;
+    Ok(RevocationBitmap::new().to_service(id)?)
+}
+
+/// The `credentialStatus` of a VC given the `index` in the revocation bitmap of its issuer.
+pub fn revocation_status(issuer_document: &CoreDocument, index: u32) -> anyhow::Result<Status> {
+    let service = revocation_service(issuer_document).with_context(|| {
+        format!(
+            "{} has no revocation bitmap, attach one first",
+            issuer_document.id()
+        )
+    })?;
+    Ok(RevocationBitmapStatus::new(service.id().clone(), index).into())
+}
+
+/// The revocation `service` once the VC at `index` is revoked, or unrevoked. It replaces the
+/// previous one in the document of the issuer.
+pub fn update_revocation_service(
+    service: &Service,
+    index: u32,
+    revoked: bool,
+) -> anyhow::Result<Service> {
+    let mut bitmap = RevocationBitmap::try_from(service)?;
+    if revoked {
+        bitmap.revoke(index);
+    } else {
+        bitmap.unrevoke(index);
+    }
+    Ok(bitmap.to_service(service.id().clone())?)
+}
+
+/// Fails if the `credential` was revoked in the bitmap of the `issuer_document`. The VCs without
+/// a `credentialStatus` cannot be revoked and always pass.
+pub fn check_revocation(
+    credential: &Credential,
+    issuer_document: &CoreDocument,
+) -> anyhow::Result<()> {
+    match JwtCredentialValidatorUtils::check_status(
+        credential,
+        &[issuer_document],
+        StatusCheck::Strict,
+    ) {
+        Ok(()) => Ok(()),
+        Err(JwtValidationError::Revoked) => {
+            anyhow::bail!("The VC was revoked by its issuer {}", issuer_document.id())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{check_revocation, CompositeJwsVerifier, StatusListPurpose};
+use anyhow::Context;
+use identity_iota::core::{Object, Url};
+use identity_iota::credential::status_list_2021::{
+    StatusList2021, StatusList2021Credential, StatusList2021CredentialBuilder, StatusList2021Entry,
+    StatusPurpose,
+};
+use identity_iota::credential::{
+    Credential, DecodedJwtCredential, FailFast, Issuer, Jwt, JwtCredentialValidationOptions,
+    JwtCredentialValidator, JwtCredentialValidatorUtils, JwtValidationError, RevocationBitmap,
+    Status, StatusCheck,
+};
+use identity_iota::did::DID;
+use identity_iota::document::CoreDocument;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+impl From<StatusListPurpose> for StatusPurpose {
+    fn from(purpose: StatusListPurpose) -> Self {
+        match purpose {
+            StatusListPurpose::Revocation => StatusPurpose::Revocation,
+            StatusListPurpose::Suspension => StatusPurpose::Suspension,
+        }
+    }
+}
+
+impl From<StatusPurpose> for StatusListPurpose {
+    fn from(purpose: StatusPurpose) -> Self {
+        match purpose {
+            StatusPurpose::Revocation => StatusListPurpose::Revocation,
+            StatusPurpose::Suspension => StatusListPurpose::Suspension,
+        }
+    }
+}
+
+/// An empty StatusList2021 credential of the `issuer_document`, to be hosted at `url`.
+pub fn new_status_list_credential(
+    issuer_document: &CoreDocument,
+    url: Url,
+    purpose: StatusListPurpose,
+) -> anyhow::Result<StatusList2021Credential> {
+    Ok(
+        StatusList2021CredentialBuilder::new(StatusList2021::default())
+            .purpose(purpose.into())
+            .subject_id(url)
+            .issuer(Issuer::Url(Url::parse(issuer_document.id().as_str())?))
+            .build()?,
+    )
+}
+
+/// Reads a status list credential stored as JSON.
+pub fn parse_status_list_credential(json: &str) -> anyhow::Result<StatusList2021Credential> {
+    let credential: Credential = serde_json::from_str(json)?;
+    Ok(StatusList2021Credential::try_from(credential)?)
+}
+
+/// The `credentialStatus` of a VC given the `index` in the status list hosted at `url`.
+pub fn status_list_status(
+    url: &str,
+    purpose: StatusListPurpose,
+    index: u32,
+) -> anyhow::Result<Status> {
+    Ok(StatusList2021Entry::new(Url::parse(url)?, purpose.into(), index as usize, None).into())
+}
+
+/// Sets the entry of the VC at `index`, which revokes or suspends it, or clears it to reinstate
+/// a suspended VC. Revocations cannot be cleared.
+pub fn set_status_list_entry(
+    list: &mut StatusList2021Credential,
+    index: u32,
+    value: bool,
+) -> anyhow::Result<()> {
+    list.update(|status_list| status_list.set_entry(index as usize, value))?;
+    Ok(())
+}
+
+/// Fails if the entry of the `credential` is set in the status `list`.
+pub fn check_status_list(
+    credential: &Credential,
+    list: &StatusList2021Credential,
+) -> anyhow::Result<()> {
+    match JwtCredentialValidatorUtils::check_status_with_status_list_2021(
+        credential,
+        list,
+        StatusCheck::Strict,
+    ) {
+        Ok(()) => Ok(()),
+        Err(JwtValidationError::Revoked | JwtValidationError::Suspended) => {
+            // The entry was read above, so the status is a valid one
+            let status = credential
+                .credential_status
+                .as_ref()
+                .context("The VC has no status")?;
+            let purpose = StatusList2021Entry::try_from(status)?.purpose();
+            anyhow::bail!(
+                "The VC was {} by its issuer",
+                StatusListPurpose::from(purpose).set_state()
+            )
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Where the status list hosted at `url` is kept under `directory`, as `<directory>/<host>/<path>`
+/// like the documents of the `did:web`.
+pub fn status_list_path(directory: &Path, url: &Url) -> anyhow::Result<PathBuf> {
+    let host = url
+        .host_str()
+        .with_context(|| format!("The status list URL {} has no host", url))?;
+    let path = url.path().trim_matches('/');
+    if path.is_empty() {
+        anyhow::bail!("The status list URL {} has no path", url);
+    }
+    Ok(directory.join(host).join(path))
+}
+
+/// Writes the signed status list to [`status_list_path`], ready to be copied to the web server
+/// that hosts it. Returns the path of the file.
+pub fn export_status_list(jwt: &str, url: &Url, directory: &Path) -> anyhow::Result<PathBuf> {
+    let file = status_list_path(directory, url)?;
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&file, jwt)?;
+    Ok(file)
+}
+
+/// Reads the status list hosted at `url` from its copy under `directory`, and checks it was
+/// signed by the `issuer_document`.
+pub fn read_status_list(
+    url: &Url,
+    issuer_document: &CoreDocument,
+    directory: &Path,
+) -> anyhow::Result<StatusList2021Credential> {
+    let file = status_list_path(directory, url)?;
+    let jwt = fs::read_to_string(&file)
+        .with_context(|| format!("No copy of the status list {} at {}", url, file.display()))?;
+
+    let decoded: DecodedJwtCredential<Object> =
+        JwtCredentialValidator::with_signature_verifier(CompositeJwsVerifier).validate(
+            &Jwt::from(jwt.trim().to_string()),
+            issuer_document,
+            &JwtCredentialValidationOptions::default(),
+            FailFast::FirstError,
+        )?;
+    Ok(StatusList2021Credential::try_from(decoded.credential)?)
+}
+
+/// Checks the `credentialStatus` of the `credential`, whichever its type: the revocation bitmap
+/// in the `issuer_document`, or the status list read from the local copies under
+/// `status_list_directory`. VCs without a status always pass.
+pub fn check_credential_status(
+    credential: &Credential,
+    issuer_document: &CoreDocument,
+    status_list_directory: &Path,
+) -> anyhow::Result<()> {
+    let Some(status) = &credential.credential_status else {
+        return Ok(());
+    };
+    if status.type_ == RevocationBitmap::TYPE {
+        return check_revocation(credential, issuer_document);
+    }
+
+    let entry = StatusList2021Entry::try_from(status)?;
+    let list = read_status_list(
+        entry.status_list_credential(),
+        issuer_document,
+        status_list_directory,
+    )?;
+    check_status_list(credential, &list)
+}
@@ -0,0 +1,218 @@
+use super::decode_base64;
+use sd_jwt_payload::{Hasher, Sha256Hasher};
+use serde_json::Value;
+
+/// Shape of an inspected token.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TokenKind {
+    Jwt,
+    SdJwt,
+    /// SD-JWT presentation ending with a key-binding JWT.
+    SdJwtWithKeyBinding,
+}
+
+/// Everything the inspector decoded from a token.
+#[derive(Clone, Debug)]
+pub struct InspectedToken {
+    pub kind: TokenKind,
+    pub header: Value,
+    pub payload: Value,
+    pub disclosures: Vec<InspectedDisclosure>,
+    /// Digests of the `_sd` arrays and `...` array entries no disclosure of the token matches:
+    /// decoys, or claims the holder did not disclose.
+    pub undisclosed_digests: Vec<String>,
+    pub key_binding: Option<InspectedKeyBinding>,
+}
+
+/// Decoded SD-JWT disclosure, `[salt, key, value]` or `[salt, value]` for array elements.
+#[derive(Clone, Debug)]
+pub struct InspectedDisclosure {
+    pub encoded: String,
+    pub salt: String,
+    /// `None` for the disclosure of an array element.
+    pub key: Option<String>,
+    pub value: Value,
+    /// Digest recomputed from the encoded disclosure.
+    pub digest: String,
+    /// JSON pointers of the `_sd` arrays, or arrays with `...` entries, containing the digest.
+    /// Empty when the digest is not referenced anywhere.
+    pub locations: Vec<String>,
+}
+
+/// Decoded key-binding JWT, with its claims checked against the presentation.
+#[derive(Clone, Debug)]
+pub struct InspectedKeyBinding {
+    pub header: Value,
+    pub payload: Value,
+    /// `sd_hash` recomputed from the issuer JWT and the disclosures of the presentation.
+    pub expected_sd_hash: String,
+    pub sd_hash_matches: bool,
+    /// Whether the `nonce` is the expected one, `None` when no nonce was expected.
+    pub nonce_matches: Option<bool>,
+    /// Whether the `aud` is the expected one, `None` when no audience was expected.
+    pub aud_matches: Option<bool>,
+}
+
+/// Decodes a JWT, an SD-JWT or an SD-JWT presentation with a key-binding JWT.
+///
+/// The signatures are not verified. `nonce` and `aud` are the values the key-binding JWT
+/// is expected to carry, if any.
+pub fn inspect_token(
+    token: &str,
+    nonce: Option<&str>,
+    aud: Option<&str>,
+) -> anyhow::Result<InspectedToken> {
+    let token = token.trim();
+    let mut parts: Vec<&str> = token.split('~').collect();
+    let jwt = parts.remove(0);
+    let (header, payload) = decode_jwt(jwt)?;
+
+    // A plain JWT has no `~`, an SD-JWT ends with one unless a key-binding JWT follows it
+    let (kind, key_binding_jwt) = match parts.pop() {
+        None => (TokenKind::Jwt, None),
+        Some("") => (TokenKind::SdJwt, None),
+        Some(last) => (TokenKind::SdJwtWithKeyBinding, Some(last)),
+    };
+
+    if let Some(alg) = payload.get("_sd_alg").and_then(Value::as_str) {
+        if alg != "sha-256" {
+            anyhow::bail!("Unsupported _sd_alg: {}", alg);
+        }
+    }
+
+    let hasher = Sha256Hasher::new();
+    let mut digests = Vec::new();
+    collect_digests(&payload, "", &mut digests);
+
+    let mut disclosures = Vec::new();
+    for encoded in parts.iter().filter(|part| !part.is_empty()) {
+        let disclosure = decode_disclosure(encoded, hasher.encoded_digest(encoded))?;
+        // Disclosed objects and arrays can hide claims of their own
+        let path = format!("disclosure:{}", disclosure.key.as_deref().unwrap_or("[]"));
+        collect_digests(&disclosure.value, &path, &mut digests);
+        disclosures.push(disclosure);
+    }
+
+    for disclosure in &mut disclosures {
+        disclosure.locations = digests
+            .iter()
+            .filter(|(_, digest)| *digest == disclosure.digest)
+            .map(|(location, _)| location.clone())
+            .collect();
+    }
+    let undisclosed_digests = digests
+        .into_iter()
+        .map(|(_, digest)| digest)
+        .filter(|digest| !disclosures.iter().any(|d| &d.digest == digest))
+        .collect();
+
+    let key_binding = match key_binding_jwt {
+        Some(kb_jwt) => {
+            // The hash covers the presentation up to the `~` before the key-binding JWT
+            let presented = &token[..token.len() - kb_jwt.len()];
+            Some(inspect_key_binding(
+                kb_jwt,
+                hasher.encoded_digest(presented),
+                nonce,
+                aud,
+            )?)
+        }
+        None => None,
+    };
+
+    Ok(InspectedToken {
+        kind,
+        header,
+        payload,
+        disclosures,
+        undisclosed_digests,
+        key_binding,
+    })
+}
+
+fn decode_jwt(jwt: &str) -> anyhow::Result<(Value, Value)> {
+    let parts: Vec<&str> = jwt.split('.').collect();
+    if parts.len() != 3 {
+        return Err(anyhow::anyhow!("Invalid JWT"));
+    }
+    let header: Value = serde_json::from_str(&decode_base64(parts[0])?)?;
+    let payload: Value = serde_json::from_str(&decode_base64(parts[1])?)?;
+    Ok((header, payload))
+}
+
+fn decode_disclosure(encoded: &str, digest: String) -> anyhow::Result<InspectedDisclosure> {
+    let decoded: Value = serde_json::from_str(&decode_base64(encoded)?)?;
+    let invalid = || anyhow::anyhow!("Invalid disclosure: {}", encoded);
+
+    let array = decoded.as_array().ok_or_else(invalid)?;
+    let salt = array.first().and_then(Value::as_str).ok_or_else(invalid)?;
+    let (key, value) = match array.as_slice() {
+        [_, value] => (None, value.clone()),
+        [_, Value::String(key), value] => (Some(key.clone()), value.clone()),
+        _ => return Err(invalid()),
+    };
+
+    Ok(InspectedDisclosure {
+        encoded: encoded.to_string(),
+        salt: salt.to_string(),
+        key,
+        value,
+        digest,
+        locations: Vec::new(),
+    })
+}
+
+/// Collects the `(location, digest)` pairs of the `_sd` arrays and `...` entries in `value`.
+fn collect_digests(value: &Value, path: &str, digests: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::Array(sd)) = map.get("_sd") {
+                let location = format!("{}/_sd", path);
+                for digest in sd.iter().filter_map(Value::as_str) {
+                    digests.push((location.clone(), digest.to_string()));
+                }
+            }
+            for (key, value) in map.iter().filter(|(key, _)| *key != "_sd") {
+                collect_digests(value, &format!("{}/{}", path, key), digests);
+            }
+        }
+        Value::Array(array) => {
+            for (index, element) in array.iter().enumerate() {
+                let element_path = format!("{}/{}", path, index);
+                match element.get("...").and_then(Value::as_str) {
+                    Some(digest) => digests.push((element_path, digest.to_string())),
+                    None => collect_digests(element, &element_path, digests),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn inspect_key_binding(
+    kb_jwt: &str,
+    expected_sd_hash: String,
+    nonce: Option<&str>,
+    aud: Option<&str>,
+) -> anyhow::Result<InspectedKeyBinding> {
+    let (header, payload) = decode_jwt(kb_jwt)?;
+    let claim = |name: &str| {
+        payload
+            .get(name)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+
+    let sd_hash_matches = claim("sd_hash").as_deref() == Some(expected_sd_hash.as_str());
+    let nonce_matches = nonce.map(|nonce| claim("nonce").as_deref() == Some(nonce));
+    let aud_matches = aud.map(|aud| claim("aud").as_deref() == Some(aud));
+
+    Ok(InspectedKeyBinding {
+        header,
+        payload,
+        expected_sd_hash,
+        sd_hash_matches,
+        nonce_matches,
+        aud_matches,
+    })
+}
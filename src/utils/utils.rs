@@ -6,8 +6,12 @@ use std::io::{BufRead, Write};
 use std::path::PathBuf;
 use std::process::Command;
 
+use identity_iota::core::Url;
+use identity_iota::document::{CoreDocument, Service, ServiceEndpoint};
 use identity_iota::iota::block::output::AliasOutput;
+use identity_iota::iota::block::output::AliasOutputBuilder;
 use identity_iota::iota::IotaClientExt;
+use identity_iota::iota::IotaDID;
 use identity_iota::iota::IotaDocument;
 use identity_iota::iota::IotaIdentityClientExt;
 use identity_iota::iota::NetworkName;
@@ -15,8 +19,11 @@ use identity_iota::storage::JwkDocumentExt;
 use identity_iota::storage::JwkMemStore;
 use identity_iota::storage::KeyIdMemstore;
 use identity_iota::storage::Storage;
+use identity_iota::verification::MethodRelationship;
 use identity_iota::verification::MethodScope;
+use identity_iota::verification::VerificationMethod;
 
+use identity_iota::verification::jwk::Jwk;
 use identity_iota::verification::jws::JwsAlgorithm;
 use iota_sdk::client::api::GetAddressesOptions;
 use iota_sdk::client::node_api::indexer::query_parameters::QueryParameter;
@@ -31,6 +38,8 @@ use serde_json::Value;
 
 use super::config;
 use super::config::Config;
+use crate::KeyAlgorithm;
+use crate::Output;
 
 use base64::engine::general_purpose;
 use base64::Engine;
@@ -65,6 +74,46 @@ pub async fn create_did(
     Ok((address, document, fragment))
 }
 
+/// Publishes the changes made to `document` in its Alias Output, whose storage deposit is
+/// raised if the document grew.
+pub async fn publish_did_update(
+    client: &Client,
+    secret_manager: &SecretManager,
+    document: IotaDocument,
+) -> anyhow::Result<IotaDocument> {
+    let alias_output: AliasOutput = client.update_did_output(document).await?;
+    publish_alias_output(client, secret_manager, alias_output).await
+}
+
+/// Deactivates `did`: its Alias Output is kept, but it resolves as deactivated and without
+/// methods. The storage deposit freed by the empty document goes back to the controller.
+pub async fn publish_did_deactivation(
+    client: &Client,
+    secret_manager: &SecretManager,
+    did: &IotaDID,
+) -> anyhow::Result<IotaDocument> {
+    let alias_output: AliasOutput = client.deactivate_did_output(did).await?;
+    publish_alias_output(client, secret_manager, alias_output).await
+}
+
+// Adjusts the storage deposit of `alias_output` to the size of the document it holds
+async fn publish_alias_output(
+    client: &Client,
+    secret_manager: &SecretManager,
+    alias_output: AliasOutput,
+) -> anyhow::Result<IotaDocument> {
+    let rent_structure = client.get_rent_structure().await?;
+    let alias_output: AliasOutput = AliasOutputBuilder::from(&alias_output)
+        .with_minimum_storage_deposit(rent_structure)
+        .finish()?;
+
+    let document: IotaDocument = client
+        .publish_did_output(secret_manager, alias_output)
+        .await?;
+
+    Ok(document)
+}
+
 /// Creates an example DID document with the given `network_name`.
 ///
 /// Its functionality is equivalent to the "create DID" example
@@ -260,6 +309,47 @@ pub fn generate_json_paths(json: &Value, prefix: &str) -> Vec<String> {
     paths
 }
 
+/// Generates the paths of the claims of a credential subject in the form taken by
+/// `SelectiveDisclosurePresentation::conceal_in_subject`, e.g. `degree.name` or `courses[1]`.
+///
+/// # Arguments
+/// * `json` - The credential subject to traverse.
+/// * `prefix` - The current path prefix (used for recursion).
+///
+/// # Returns
+/// A vector with the path of every leaf claim. The `id` of the subject is left out, it
+/// identifies the holder and is never concealed.
+pub fn jpt_claim_paths(json: &Value, prefix: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+
+    match json {
+        Value::Object(map) => {
+            for (key, value) in map {
+                if prefix.is_empty() && key == "id" {
+                    continue;
+                }
+                let new_prefix = if prefix.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                paths.extend(jpt_claim_paths(value, &new_prefix));
+            }
+        }
+        Value::Array(array) => {
+            for (index, value) in array.iter().enumerate() {
+                let new_prefix = format!("{}[{}]", prefix, index);
+                paths.extend(jpt_claim_paths(value, &new_prefix));
+            }
+        }
+        _ => {
+            paths.push(prefix.to_string());
+        }
+    }
+
+    paths
+}
+
 /// Compares the structure of two JSON values by generating and comparing their paths.
 ///
 /// # Arguments
@@ -302,7 +392,7 @@ pub fn prepend_comment_to_file(file: &mut File) -> anyhow::Result<()> {
     # Just use the '#' character at the beginning of the line.
     "#;
 
-    file.write(comment.as_bytes())?;
+    file.write_all(comment.as_bytes())?;
     // add a new line
     writeln!(file)?;
     file.flush()?;
@@ -345,7 +435,7 @@ pub(crate) fn edit_file(editor: String, path: &String) -> anyhow::Result<()> {
     if editor == "code" {
         let status = Command::new(editor)
             .arg("--wait")
-            .arg(&path)
+            .arg(path)
             .status()
             .expect("Failed to open editor");
 
@@ -355,7 +445,7 @@ pub(crate) fn edit_file(editor: String, path: &String) -> anyhow::Result<()> {
         }
     } else {
         let status = Command::new(editor)
-            .arg(&path)
+            .arg(path)
             .status()
             .expect("Failed to open editor");
 
@@ -375,6 +465,9 @@ fn create_directory_is_not_exists(path: &PathBuf) -> anyhow::Result<()> {
 }
 
 pub fn pretty_print_json(label: &str, value: &str) {
+    if Output::is_json_output() {
+        return;
+    }
     let data: Value = serde_json::from_str(value).unwrap();
     let pretty_json = serde_json::to_string_pretty(&data).unwrap();
     println!("--------------------------------------");
@@ -383,7 +476,10 @@ pub fn pretty_print_json(label: &str, value: &str) {
     println!("{} \n", pretty_json);
 }
 
-pub fn extract_kid(resolved_document: &IotaDocument) -> Result<String, anyhow::Error> {
+/// The fragment of the first method of the document, or else the `kid` of its key. The methods
+/// we generate on the ledger are named after their `kid`, the `did:key` and `did:jwk` ones after
+/// the DID.
+pub fn extract_kid(resolved_document: &CoreDocument) -> Result<String, anyhow::Error> {
     let binding = resolved_document.methods(Some(MethodScope::VerificationMethod));
 
     let method = binding
@@ -395,13 +491,118 @@ pub fn extract_kid(resolved_document: &IotaDocument) -> Result<String, anyhow::E
         .public_key_jwk()
         .ok_or(anyhow::anyhow!("No JWK provided"))?;
 
-    let kid = public_key_jwk
-        .kid()
+    let kid = method
+        .id()
+        .fragment()
+        .or(public_key_jwk.kid())
         .ok_or(anyhow::anyhow!("Kid not founded"))?;
 
     Ok(kid.to_string())
 }
 
+/// Scopes a verification method can be generated in, with their names.
+pub fn method_scopes() -> Vec<(&'static str, MethodScope)> {
+    vec![
+        ("VerificationMethod", MethodScope::VerificationMethod),
+        ("Authentication", MethodRelationship::Authentication.into()),
+        (
+            "AssertionMethod",
+            MethodRelationship::AssertionMethod.into(),
+        ),
+        ("KeyAgreement", MethodRelationship::KeyAgreement.into()),
+        (
+            "CapabilityDelegation",
+            MethodRelationship::CapabilityDelegation.into(),
+        ),
+        (
+            "CapabilityInvocation",
+            MethodRelationship::CapabilityInvocation.into(),
+        ),
+    ]
+}
+
+pub fn scope_name(scope: &MethodScope) -> &'static str {
+    method_scopes()
+        .into_iter()
+        .find(|(_, candidate)| candidate == scope)
+        .map(|(name, _)| name)
+        .unwrap_or("VerificationMethod")
+}
+
+/// Fragments of every method defined in `document`, with the scope each one is defined in and
+/// the JWS algorithm of its key. A method referenced by a relationship is only listed once,
+/// under its own scope.
+pub fn document_methods(document: &CoreDocument) -> Vec<(String, MethodScope, String)> {
+    let mut methods: Vec<(String, MethodScope, String)> = Vec::new();
+    for (_, scope) in method_scopes() {
+        for method in document.methods(Some(scope)) {
+            let Some(fragment) = method.id().fragment() else {
+                continue;
+            };
+            if !methods.iter().any(|(known, _, _)| known == fragment) {
+                methods.push((fragment.to_string(), scope, method_alg(method)));
+            }
+        }
+    }
+    methods
+}
+
+/// JWS algorithm name of the key of `method`, from its `alg` or else from its curve.
+pub fn method_alg(method: &VerificationMethod) -> String {
+    let jwk = method.data().public_key_jwk();
+    match (jwk.and_then(Jwk::alg), jwk.and_then(KeyAlgorithm::of_jwk)) {
+        (Some(alg), _) => alg.to_string(),
+        (None, Some(algorithm)) => algorithm.jws_algorithm().name().to_string(),
+        (None, None) => "unknown".to_string(),
+    }
+}
+
+/// Fragment, types and endpoint of every service of `document`, for the database.
+pub fn document_services(document: &CoreDocument) -> Vec<(String, String, String)> {
+    document
+        .service()
+        .iter()
+        .filter_map(|service| {
+            let fragment = service.id().fragment()?;
+            Some((
+                fragment.to_string(),
+                service_types(service),
+                service_endpoint_string(service.service_endpoint()),
+            ))
+        })
+        .collect()
+}
+
+pub fn service_types(service: &Service) -> String {
+    service
+        .type_()
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A single URL as is, a set or a map of URLs as JSON.
+pub fn service_endpoint_string(endpoint: &ServiceEndpoint) -> String {
+    match endpoint {
+        ServiceEndpoint::One(url) => url.to_string(),
+        endpoint => serde_json::to_string(endpoint).unwrap_or_default(),
+    }
+}
+
+/// Parses a service endpoint typed by the user: a URL, a JSON array of URLs or a JSON map of
+/// URL sets.
+pub fn parse_service_endpoint(input: &str) -> anyhow::Result<ServiceEndpoint> {
+    let input = input.trim();
+    if input.starts_with('{') || input.starts_with('[') {
+        serde_json::from_str(input).context("Invalid endpoint, expected URLs")
+    } else {
+        Ok(ServiceEndpoint::One(
+            Url::parse(input).context("Invalid endpoint URL")?,
+        ))
+    }
+}
+
 pub fn is_command_available(command: &str) -> bool {
     Command::new("sh")
         .arg("-c")
@@ -414,7 +615,7 @@ pub fn is_command_available(command: &str) -> bool {
 pub fn add_base64_padding(encoded: &str) -> String {
     let padding_needed = (4 - (encoded.len() % 4)) % 4;
     let mut padded = encoded.to_string();
-    padded.extend(std::iter::repeat('=').take(padding_needed));
+    padded.extend(std::iter::repeat_n('=', padding_needed));
     padded
 }
 
@@ -429,11 +630,7 @@ pub fn extract_disclosure_keys(disclosures: &Vec<String>) -> anyhow::Result<Vec<
     let mut extracted_fields = Vec::new();
 
     for disclosure in disclosures {
-        let padded = add_base64_padding(disclosure);
-        // Decode the base64url-encoded disclosure
-        let decoded = general_purpose::URL_SAFE.decode(padded)?;
-        // Parse the JSON object
-        let json: Value = serde_json::from_slice(&decoded)?;
+        let json = parse_disclosure(disclosure)?;
 
         // Ensure the JSON is an array and has at least two elements
         if let Some(array) = json.as_array() {
@@ -450,6 +647,29 @@ pub fn extract_disclosure_keys(disclosures: &Vec<String>) -> anyhow::Result<Vec<
     Ok(extracted_fields)
 }
 
+/// Decodes a disclosure and extracts its claim name.
+///
+/// # Returns
+/// - `None` if the disclosure hides an array element, which has no claim name.
+pub fn disclosure_key(disclosure: &str) -> anyhow::Result<Option<String>> {
+    let json = parse_disclosure(disclosure)?;
+
+    // A claim is disclosed as [salt, key, value], an array element as [salt, value]
+    Ok(json
+        .as_array()
+        .filter(|array| array.len() > 2)
+        .and_then(|array| array[1].as_str())
+        .map(str::to_string))
+}
+
+fn parse_disclosure(disclosure: &str) -> anyhow::Result<Value> {
+    let padded = add_base64_padding(disclosure);
+    // Decode the base64url-encoded disclosure
+    let decoded = general_purpose::URL_SAFE.decode(padded)?;
+    // Parse the JSON object
+    Ok(serde_json::from_slice(&decoded)?)
+}
+
 pub fn decode_base64(encoded: &str) -> anyhow::Result<String> {
     // Add padding if necessary
     let padded = add_base64_padding(encoded);
@@ -480,7 +700,7 @@ pub fn decode_base64(encoded: &str) -> anyhow::Result<String> {
 /// * The payload cannot be decoded from Base64.
 /// * The payload is not valid JSON.
 /// * The issuer (`iss`) or subject (`sub`) claims are missing or not strings.
-pub fn get_entities_from_jwt(jwt: &String) -> anyhow::Result<(String, String)> {
+pub fn get_entities_from_jwt(jwt: &str) -> anyhow::Result<(String, String)> {
     // Split the JWT into its three parts
     let parts: Vec<&str> = jwt.split('.').collect();
     if parts.len() != 3 {
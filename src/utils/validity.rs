@@ -0,0 +1,188 @@
+use anyhow::Context;
+use chrono::{DateTime, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use identity_iota::core::Timestamp;
+use identity_iota::credential::{
+    Credential, CredentialBuilder, JptCredentialValidationOptions, JwtCredentialValidationOptions,
+    StatusCheck,
+};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Written instead of an end to issue a VC that never expires.
+pub const NO_EXPIRATION: &str = "never";
+
+/// When an issued VC is valid: from its issuance date on, until its expiration date if it has
+/// one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Validity {
+    pub valid_from: Timestamp,
+    pub expires_at: Option<Timestamp>,
+}
+
+impl Validity {
+    /// Valid from now on, and never expires.
+    pub fn unlimited() -> Self {
+        Validity {
+            valid_from: Timestamp::now_utc(),
+            expires_at: None,
+        }
+    }
+
+    /// The window from the `valid_from` date, now without one, until `valid_until`, a date or a
+    /// duration after the start, see [`parse_validity_end`]. Without an end, or with
+    /// [`NO_EXPIRATION`], the VC never expires.
+    pub fn parse(valid_from: Option<&str>, valid_until: Option<&str>) -> anyhow::Result<Self> {
+        let valid_from = match valid_from.map(str::trim) {
+            None | Some("") => Timestamp::now_utc(),
+            Some(date) => parse_date(date)?,
+        };
+        let expires_at = match valid_until.map(str::trim) {
+            None | Some("") | Some(NO_EXPIRATION) => None,
+            Some(end) => Some(parse_validity_end(end, valid_from)?),
+        };
+        if let Some(expires_at) = expires_at {
+            if expires_at <= valid_from {
+                anyhow::bail!(
+                    "The VC would expire on {}, before it is valid on {}",
+                    expires_at,
+                    valid_from
+                );
+            }
+        }
+        Ok(Validity {
+            valid_from,
+            expires_at,
+        })
+    }
+
+    /// Sets the issuance and expiration dates of the credential being built.
+    pub fn apply<T>(&self, builder: CredentialBuilder<T>) -> CredentialBuilder<T> {
+        let builder = builder.issuance_date(self.valid_from);
+        match self.expires_at {
+            Some(expires_at) => builder.expiration_date(expires_at),
+            None => builder,
+        }
+    }
+
+    /// The expiration date as stored with the VC, in UTC.
+    pub fn expiration(&self) -> Option<NaiveDateTime> {
+        self.expires_at
+            .and_then(|expires_at| DateTime::from_timestamp(expires_at.to_unix(), 0))
+            .map(|expires_at| expires_at.naive_utc())
+    }
+}
+
+/// Parses an RFC 3339 date and time, or a plain `YYYY-MM-DD` date taken at midnight UTC.
+pub fn parse_date(input: &str) -> anyhow::Result<Timestamp> {
+    if let Ok(timestamp) = Timestamp::parse(input) {
+        return Ok(timestamp);
+    }
+    let date = NaiveDate::parse_from_str(input, "%Y-%m-%d").with_context(|| {
+        format!(
+            "Invalid date {}, expected YYYY-MM-DD or an RFC 3339 date and time",
+            input
+        )
+    })?;
+    Ok(Timestamp::from_unix(
+        date.and_time(NaiveTime::MIN).and_utc().timestamp(),
+    )?)
+}
+
+/// The end of a validity window starting at `start`: a date, see [`parse_date`], or a duration
+/// in hours, days, weeks, months or years, such as `12h`, `90d`, `2w`, `6mo` or `5y`.
+pub fn parse_validity_end(input: &str, start: Timestamp) -> anyhow::Result<Timestamp> {
+    if let Ok(date) = parse_date(input) {
+        return Ok(date);
+    }
+
+    let invalid = || {
+        anyhow::anyhow!(
+            "Invalid validity {}, expected a date or a duration such as 90d, 6mo or 5y",
+            input
+        )
+    };
+    let unit_start = input
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(invalid)?;
+    let (amount, unit) = input.split_at(unit_start);
+    let amount: u32 = amount.parse().map_err(|_| invalid())?;
+
+    let start = DateTime::from_timestamp(start.to_unix(), 0).ok_or_else(invalid)?;
+    let after = |delta: Option<TimeDelta>| delta.and_then(|delta| start.checked_add_signed(delta));
+    let end = match unit {
+        "h" => after(TimeDelta::try_hours(amount.into())),
+        "d" => after(TimeDelta::try_days(amount.into())),
+        "w" => after(TimeDelta::try_weeks(amount.into())),
+        "mo" => start.checked_add_months(Months::new(amount)),
+        "y" => amount
+            .checked_mul(12)
+            .and_then(|months| start.checked_add_months(Months::new(months))),
+        _ => return Err(invalid()),
+    };
+    let end = end.ok_or_else(|| anyhow::anyhow!("The validity {} is too long", input))?;
+    Ok(Timestamp::from_unix(end.timestamp())?)
+}
+
+/// Default validity end of the VCs issued from `template`, read from the `defaults_file`: a JSON
+/// object from template file names to dates or durations, e.g. `{"drivers_license.json": "5y"}`.
+pub fn template_validity(defaults_file: &Path, template: &str) -> anyhow::Result<Option<String>> {
+    let json = fs::read_to_string(defaults_file).with_context(|| {
+        format!(
+            "Cannot read the validity defaults {}",
+            defaults_file.display()
+        )
+    })?;
+    let defaults: HashMap<String, String> = serde_json::from_str(&json)?;
+    Ok(defaults.get(template).cloned())
+}
+
+/// Fails if the `credential` is not valid `at` the given time, telling whether it expired or is
+/// not valid yet.
+pub fn check_validity(credential: &Credential, at: Timestamp) -> anyhow::Result<()> {
+    if credential.issuance_date > at {
+        anyhow::bail!(
+            "The VC is not valid yet, it is valid from {}",
+            credential.issuance_date
+        );
+    }
+    if let Some(expiration_date) = credential.expiration_date {
+        if expiration_date < at {
+            anyhow::bail!("The VC expired on {}", expiration_date);
+        }
+    }
+    Ok(())
+}
+
+// The validators check the dates themselves, with an error that does not say which one failed.
+// Their window is opened wide so that `check_validity` reports it instead.
+fn widest_window() -> (Timestamp, Timestamp) {
+    (
+        Timestamp::from_unix(0).expect("the epoch is a valid timestamp"),
+        Timestamp::from_unix(253_402_300_799).expect("the year 9999 is a valid timestamp"),
+    )
+}
+
+/// Options of the JWT and SD-JWT validators, leaving the validity window to [`check_validity`]
+/// and the status to [`check_credential_status`], which knows the status lists too.
+///
+/// [`check_credential_status`]: crate::check_credential_status
+pub fn jwt_validation_options() -> JwtCredentialValidationOptions {
+    let (earliest_expiry, latest_issuance) = widest_window();
+    JwtCredentialValidationOptions::default()
+        .earliest_expiry_date(earliest_expiry)
+        .latest_issuance_date(latest_issuance)
+        .status_check(StatusCheck::SkipAll)
+}
+
+/// Options of the JPT validator, leaving the validity window to [`check_validity`] and the
+/// status to [`check_credential_status`].
+///
+/// [`check_credential_status`]: crate::check_credential_status
+pub fn jpt_validation_options() -> JptCredentialValidationOptions {
+    let (earliest_expiry, latest_issuance) = widest_window();
+    JptCredentialValidationOptions::default()
+        .earliest_expiry_date(earliest_expiry)
+        .latest_issuance_date(latest_issuance)
+        .status_check(StatusCheck::SkipAll)
+}
@@ -0,0 +1,203 @@
+use anyhow::Context;
+use colored::Colorize;
+use comfy_table::modifiers::UTF8_ROUND_CORNERS;
+use comfy_table::{Cell, Color, Table};
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+/// The checks a VC goes through when it is verified, in the order they run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationCheck {
+    IssuerResolution,
+    SdDigests,
+    Signature,
+    Expiry,
+    Status,
+    SubjectHolder,
+}
+
+impl VerificationCheck {
+    pub fn label(&self) -> &'static str {
+        match self {
+            VerificationCheck::IssuerResolution => "Issuer DID resolution",
+            VerificationCheck::SdDigests => "SD digest integrity",
+            VerificationCheck::Signature => "Signature",
+            VerificationCheck::Expiry => "Expiry",
+            VerificationCheck::Status => "Revocation status",
+            VerificationCheck::SubjectHolder => "Subject/holder relationship",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckOutcome {
+    Pass,
+    Fail,
+    /// The check does not apply to the VC, or needs what a failed check would have given.
+    Skip,
+}
+
+impl Display for CheckOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            CheckOutcome::Pass => "pass",
+            CheckOutcome::Fail => "fail",
+            CheckOutcome::Skip => "skip",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub check: VerificationCheck,
+    pub outcome: CheckOutcome,
+    pub reason: String,
+}
+
+/// Outcome of every check of a VC verification. A failure does not stop the verification: the
+/// checks that need what the failed one would have given are skipped, the others still run.
+#[derive(Debug, Clone, Serialize)]
+pub struct VerificationReport {
+    /// `None` for a token that is not stored.
+    pub vc_id: Option<i64>,
+    pub issuer: String,
+    pub format: String,
+    pub valid: bool,
+    pub checks: Vec<CheckResult>,
+    /// What looks wrong without failing a check.
+    pub warnings: Vec<String>,
+    /// The decoded credential, once its signature was verified.
+    pub credential: Option<Value>,
+}
+
+impl VerificationReport {
+    pub fn new(vc_id: Option<i64>, issuer: &str, format: &str) -> Self {
+        VerificationReport {
+            vc_id,
+            issuer: issuer.to_string(),
+            format: format.to_string(),
+            valid: true,
+            checks: vec![],
+            warnings: vec![],
+            credential: None,
+        }
+    }
+
+    pub fn pass(&mut self, check: VerificationCheck, reason: impl Into<String>) {
+        self.push(check, CheckOutcome::Pass, reason.into());
+    }
+
+    pub fn fail(&mut self, check: VerificationCheck, reason: impl Into<String>) {
+        self.push(check, CheckOutcome::Fail, reason.into());
+    }
+
+    pub fn skip(&mut self, check: VerificationCheck, reason: impl Into<String>) {
+        self.push(check, CheckOutcome::Skip, reason.into());
+    }
+
+    pub fn warn(&mut self, warning: impl Into<String>) {
+        self.warnings.push(warning.into());
+    }
+
+    /// Records a check that passed with the `passed` reason, or failed with the error of
+    /// `result`. Returns whether it passed.
+    pub fn record<T>(
+        &mut self,
+        check: VerificationCheck,
+        result: anyhow::Result<T>,
+        passed: impl FnOnce(T) -> String,
+    ) -> bool {
+        match result {
+            Ok(value) => {
+                self.pass(check, passed(value));
+                true
+            }
+            Err(e) => {
+                self.fail(check, format!("{:#}", e));
+                false
+            }
+        }
+    }
+
+    /// Skips all the `checks` for the same `reason`.
+    pub fn skip_all(&mut self, checks: &[VerificationCheck], reason: &str) {
+        for check in checks {
+            self.skip(*check, reason);
+        }
+    }
+
+    pub fn outcome(&self, check: VerificationCheck) -> Option<CheckOutcome> {
+        self.checks
+            .iter()
+            .find(|result| result.check == check)
+            .map(|result| result.outcome)
+    }
+
+    pub fn failures(&self) -> Vec<&CheckResult> {
+        self.checks
+            .iter()
+            .filter(|result| result.outcome == CheckOutcome::Fail)
+            .collect()
+    }
+
+    /// Prints the checks as a table, the failed ones in red, followed by the warnings.
+    pub fn display(&self) {
+        match self.vc_id {
+            Some(vc_id) => println!("VC {} ({}) issued by {}", vc_id, self.format, self.issuer),
+            None => println!("{} issued by {}", self.format, self.issuer),
+        }
+        Self::print_checks(&self.checks);
+        Self::print_warnings(&self.warnings);
+    }
+
+    pub fn print_checks(checks: &[CheckResult]) {
+        let mut table = Table::new();
+        table.apply_modifier(UTF8_ROUND_CORNERS);
+        table.set_content_arrangement(comfy_table::ContentArrangement::Dynamic);
+        table.set_header(vec!["Check", "Result", "Reason"]);
+
+        for result in checks {
+            let color = match result.outcome {
+                CheckOutcome::Pass => Color::Green,
+                CheckOutcome::Fail => Color::Red,
+                CheckOutcome::Skip => Color::Yellow,
+            };
+            table.add_row(vec![
+                Cell::new(result.check.label()),
+                Cell::new(result.outcome).fg(color),
+                Cell::new(&result.reason),
+            ]);
+        }
+
+        println!("{table}");
+    }
+
+    pub fn print_warnings(warnings: &[String]) {
+        for warning in warnings {
+            println!("{} {}", "Warning:".yellow().bold(), warning);
+        }
+    }
+
+    /// Writes the report as pretty JSON to `path`.
+    pub fn export(&self, path: &Path) -> anyhow::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)
+            .with_context(|| format!("Cannot write the report to {}", path.display()))
+    }
+
+    fn push(&mut self, check: VerificationCheck, outcome: CheckOutcome, reason: String) {
+        if outcome == CheckOutcome::Fail {
+            self.valid = false;
+        }
+        self.checks.push(CheckResult {
+            check,
+            outcome,
+            reason,
+        });
+    }
+}
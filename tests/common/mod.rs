@@ -0,0 +1,154 @@
+//! Fixtures shared by the integration tests.
+
+// Every test crate compiles this module but uses only some of the fixtures
+#![allow(dead_code)]
+
+use identity_iota::core::{FromJson, Url};
+use identity_iota::credential::{Credential, CredentialBuilder, Subject};
+use identity_iota::did::DID;
+use identity_iota::document::CoreDocument;
+use identity_iota::iota::{IotaDocument, NetworkName};
+use identity_iota::storage::{JwkDocumentExt, JwkMemStore, JwsSignatureOptions, KeyIdMemstore};
+use identity_iota::verification::jws::JwsAlgorithm;
+use identity_iota::verification::MethodScope;
+use serde_json::json;
+use tcc::{
+    create_database_tables, create_offline_did, DBConnector, DidKind, KeyAlgorithm, MemStorage,
+    SQLiteConnector, Validity, Vc,
+};
+
+/// IOTA document on `network`, with an Ed25519 method for each of the `scopes`.
+pub async fn iota_document(network: &'static str, scopes: &[MethodScope]) -> IotaDocument {
+    let network_name = NetworkName::try_from(network).unwrap();
+    let mut document = IotaDocument::new(&network_name);
+
+    let storage = MemStorage::new(JwkMemStore::new(), KeyIdMemstore::new());
+    for scope in scopes {
+        document
+            .generate_method(
+                &storage,
+                JwkMemStore::ED25519_KEY_TYPE,
+                JwsAlgorithm::EdDSA,
+                None,
+                *scope,
+            )
+            .await
+            .unwrap();
+    }
+    document
+}
+
+/// IOTA document on the test network, with an Ed25519 verification method.
+pub async fn new_document() -> IotaDocument {
+    iota_document("tst", &[MethodScope::VerificationMethod]).await
+}
+
+/// Offline DID whose key is kept in memory to sign with it.
+pub struct TestDid {
+    pub storage: MemStorage,
+    pub document: CoreDocument,
+    pub fragment: String,
+}
+
+impl TestDid {
+    pub async fn new() -> Self {
+        let storage = MemStorage::new(JwkMemStore::new(), KeyIdMemstore::new());
+        let (document, fragment) =
+            create_offline_did(&storage, DidKind::Jwk, KeyAlgorithm::Ed25519)
+                .await
+                .unwrap();
+        TestDid {
+            storage,
+            document,
+            fragment,
+        }
+    }
+
+    pub async fn sign(&self, credential: &Credential) -> String {
+        let jwt = self
+            .document
+            .create_credential_jwt(
+                credential,
+                &self.storage,
+                &self.fragment,
+                &JwsSignatureOptions::default(),
+                None,
+            )
+            .await
+            .unwrap();
+        jwt.as_str().to_string()
+    }
+}
+
+/// Document of an offline DID, when its key is not needed.
+pub async fn offline_document() -> CoreDocument {
+    TestDid::new().await.document
+}
+
+/// VC of `type_` issued by `issuer` to `subject`, to be completed with a status or a validity.
+pub fn credential_builder(
+    issuer: &CoreDocument,
+    type_: &str,
+    subject: &CoreDocument,
+) -> CredentialBuilder {
+    CredentialBuilder::default()
+        .issuer(Url::parse(issuer.id().as_str()).unwrap())
+        .type_(type_)
+        .subject(Subject::from_json_value(json!({ "id": subject.id().as_str() })).unwrap())
+}
+
+/// Database holding the DIDs of Alice, the issuer, and Bob, the holder.
+pub struct Wallet {
+    pub db: SQLiteConnector,
+    pub issuer: TestDid,
+    pub issuer_id: i64,
+    pub holder: TestDid,
+    pub holder_id: i64,
+}
+
+impl Wallet {
+    pub async fn new() -> Self {
+        let db = SQLiteConnector::default();
+        create_database_tables(&db).unwrap();
+        let issuer = TestDid::new().await;
+        let holder = TestDid::new().await;
+        let issuer_id = db.save_did(&issuer.document, "Alice").unwrap();
+        let holder_id = db.save_did(&holder.document, "Bob").unwrap();
+        Wallet {
+            db,
+            issuer,
+            issuer_id,
+            holder,
+            holder_id,
+        }
+    }
+
+    // Signs a degree issued to `subject`
+    pub async fn sign(&self, subject: &CoreDocument, validity: &Validity) -> String {
+        let credential = validity
+            .apply(credential_builder(
+                &self.issuer.document,
+                "UniversityDegreeCredential",
+                subject,
+            ))
+            .build()
+            .unwrap();
+        self.issuer.sign(&credential).await
+    }
+
+    // Signs a degree issued to `subject` and saves it as held by Bob
+    pub async fn issue(&self, subject: &CoreDocument, validity: &Validity) -> Vc {
+        let jwt = self.sign(subject, validity).await;
+        let id = self
+            .db
+            .save_vc(
+                &jwt,
+                self.issuer_id,
+                self.holder_id,
+                "UniversityDegree",
+                false,
+            )
+            .unwrap();
+        self.db.get_vc_from_id(id).unwrap()
+    }
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use common::offline_document;
+use identity_iota::core::{Object, Url};
+use identity_iota::did::DID;
+use identity_iota::document::Service;
+use tcc::{
+    create_database_tables, migrate_did_contacts, ContactRole, DBConnector, Did, DidQuery,
+    SQLiteConnector,
+};
+
+fn names(dids: Vec<Did>) -> Vec<String> {
+    dids.iter().map(|did| did.name().to_string()).collect()
+}
+
+#[test]
+fn contact_roles_are_parsed_from_a_list() {
+    assert_eq!(
+        ContactRole::parse_list("verifier, Issuer,verifier").unwrap(),
+        [ContactRole::Verifier, ContactRole::Issuer]
+    );
+    assert!(ContactRole::parse_list("").unwrap().is_empty());
+    assert!(ContactRole::parse_list("issuer,signer").is_err());
+    assert_eq!(
+        ContactRole::join(&[ContactRole::Holder, ContactRole::Verifier]),
+        "holder,verifier"
+    );
+}
+
+#[tokio::test]
+async fn contacts_are_never_offered_to_sign() {
+    let db = SQLiteConnector::default();
+    create_database_tables(&db).unwrap();
+
+    let ours = db.save_did(&offline_document().await, "Alice").unwrap();
+    let university = db
+        .save_contact(
+            &offline_document().await,
+            "University",
+            &[ContactRole::Issuer],
+        )
+        .unwrap();
+    db.save_contact(&offline_document().await, "Shop", &[ContactRole::Verifier])
+        .unwrap();
+
+    let contact = db.get_did_from_id(university).unwrap();
+    assert!(!contact.owned());
+    assert_eq!(contact.roles(), [ContactRole::Issuer]);
+    assert!(db.get_did_from_id(ours).unwrap().owned());
+    assert!(db.get_did_from_id(ours).unwrap().roles().is_empty());
+
+    assert_eq!(names(db.get_stored_dids().unwrap()).len(), 3);
+    assert_eq!(
+        names(db.search_dids(&DidQuery::owned()).unwrap()),
+        ["Alice"]
+    );
+    assert_eq!(
+        names(db.search_dids(&DidQuery::contacts()).unwrap()),
+        ["University", "Shop"]
+    );
+    // Our DIDs can always be chosen, the contacts only for their roles
+    assert_eq!(
+        names(
+            db.search_dids(&DidQuery::with_role(ContactRole::Issuer))
+                .unwrap()
+        ),
+        ["Alice", "University"]
+    );
+    assert_eq!(
+        names(
+            db.search_dids(&DidQuery::with_role(ContactRole::Verifier))
+                .unwrap()
+        ),
+        ["Alice", "Shop"]
+    );
+}
+
+#[tokio::test]
+async fn contacts_are_edited_and_re_resolved() {
+    let db = SQLiteConnector::default();
+    create_database_tables(&db).unwrap();
+
+    let mut document = offline_document().await;
+    let ours = db.save_did(&offline_document().await, "Alice").unwrap();
+    let id = db.save_contact(&document, "Uni", &[]).unwrap();
+
+    db.update_contact(
+        id,
+        "University",
+        &[ContactRole::Issuer, ContactRole::Verifier],
+    )
+    .unwrap();
+    let contact = db.get_did_from_id(id).unwrap();
+    assert_eq!(contact.name(), "University");
+    assert_eq!(
+        contact.roles(),
+        [ContactRole::Issuer, ContactRole::Verifier]
+    );
+    // Only the contacts can be edited
+    assert!(db.update_contact(ours, "Bob", &[]).is_err());
+
+    // The contact published a service since it was imported
+    let service = Service::builder(Object::new())
+        .id(document.id().to_url().join("#linked-domain").unwrap())
+        .type_("LinkedDomains")
+        .service_endpoint(Url::parse("https://university.example").unwrap())
+        .build()
+        .unwrap();
+    document.insert_service(service).unwrap();
+    db.refresh_contact(id, &document).unwrap();
+
+    let contact = db.get_did_from_id(id).unwrap();
+    assert_eq!(
+        contact.services(),
+        ["#linked-domain LinkedDomains https://university.example/"]
+    );
+    assert_eq!(db.get_did_methods(id).unwrap().len(), 1);
+    assert!(db.refresh_contact(ours, &document).is_err());
+}
+
+#[tokio::test]
+async fn migrated_dids_are_ours() {
+    let db = SQLiteConnector::default();
+    // The table before the contacts
+    db.execute(
+        r#"
+        CREATE TABLE dids (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+            did TEXT,
+            fragment TEXT,
+            name TEXT,
+            status TEXT NOT NULL DEFAULT 'active',
+            method TEXT NOT NULL DEFAULT 'iota',
+            network TEXT
+        )"#,
+        [],
+    )
+    .unwrap();
+    db.execute(
+        "INSERT INTO dids (did, fragment, name, method) VALUES ('did:key:z6Mk', 'z6Mk', 'Alice', 'key')",
+        [],
+    )
+    .unwrap();
+
+    create_database_tables(&db).unwrap();
+    // Running the migration again changes nothing
+    migrate_did_contacts(&db).unwrap();
+
+    let dids = db.search_dids(&DidQuery::owned()).unwrap();
+    assert_eq!(names(dids), ["Alice"]);
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use common::new_document;
+use tcc::{
+    add_column_if_missing, create_database_tables, migrate_did_status, migrate_vc_jpt, DBConnector,
+    DidQuery, DidSortColumn, DidStatus, SQLiteConnector, VcQuery, VcSortColumn,
+};
+
+async fn database_with_dids(names: &[&str]) -> (SQLiteConnector, Vec<i64>) {
+    let db = SQLiteConnector::default();
+    create_database_tables(&db).unwrap();
+
+    let mut ids = Vec::new();
+    for name in names {
+        let document = new_document().await;
+        ids.push(db.save_did_document(&document, name).unwrap());
+    }
+    (db, ids)
+}
+
+fn names(query_result: Vec<tcc::Did>) -> Vec<String> {
+    query_result
+        .iter()
+        .map(|did| did.name().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn search_dids_filters_and_sorts() {
+    let (db, _) = database_with_dids(&["Bob", "alice", "Alicia", "100%"]).await;
+
+    let query = DidQuery {
+        text: "ALI".to_string(),
+        sort: DidSortColumn::Name,
+        ..Default::default()
+    };
+    assert_eq!(names(db.search_dids(&query).unwrap()), ["Alicia", "alice"]);
+
+    // The wildcards typed by the user are matched literally
+    let query = DidQuery {
+        text: "%".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(names(db.search_dids(&query).unwrap()), ["100%"]);
+
+    let query = DidQuery {
+        descending: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        names(db.search_dids(&query).unwrap()),
+        ["100%", "Alicia", "alice", "Bob"]
+    );
+}
+
+#[tokio::test]
+async fn unreadable_rows_are_reported_as_errors() {
+    let (db, _) = database_with_dids(&["Alice"]).await;
+    db.execute(
+        "INSERT INTO dids (did, fragment, name, created_at) VALUES (?1, ?2, ?3, ?4)",
+        ["did:iota:tst:0x01", "key-1", "Broken", "yesterday"],
+    )
+    .unwrap();
+
+    assert!(db.search_dids(&DidQuery::default()).is_err());
+}
+
+#[tokio::test]
+async fn search_vcs_filters_by_holder_and_type() {
+    let (db, ids) = database_with_dids(&["Issuer", "Alice", "Bob"]).await;
+    let (issuer, alice, bob) = (ids[0], ids[1], ids[2]);
+
+    db.save_vc("jwt-1", issuer, alice, "UniversityDegree", false)
+        .unwrap();
+    db.save_vc("jwt-2", issuer, bob, "DriverLicense", true)
+        .unwrap();
+    db.save_vc("jwt-3", issuer, bob, "UniversityDegree", false)
+        .unwrap();
+
+    let query = VcQuery {
+        text: "bob".to_string(),
+        sort: VcSortColumn::Type,
+        descending: false,
+    };
+    let vcs = db.search_vcs(&query).unwrap();
+    let tokens: Vec<&str> = vcs.iter().map(|vc| vc.vc()).collect();
+    assert_eq!(tokens, ["jwt-2", "jwt-3"]);
+    assert!(vcs.iter().all(|vc| vc.holder().name() == "Bob"));
+
+    let query = VcQuery {
+        text: "degree".to_string(),
+        sort: VcSortColumn::Sd,
+        descending: false,
+    };
+    assert_eq!(db.search_vcs(&query).unwrap().len(), 2);
+
+    assert_eq!(db.get_stored_vcs().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn deactivated_and_destroyed_dids_keep_their_row() {
+    let (db, ids) = database_with_dids(&["Alice", "Bob", "Carol"]).await;
+    // Running the migration on an up to date table changes nothing
+    migrate_did_status(&db).unwrap();
+    assert_eq!(
+        db.get_did_from_id(ids[0]).unwrap().status(),
+        DidStatus::Active
+    );
+
+    db.set_did_status(ids[0], DidStatus::Deactivated).unwrap();
+    db.set_did_status(ids[1], DidStatus::Destroyed).unwrap();
+
+    let dids = db.get_stored_dids().unwrap();
+    assert_eq!(dids.len(), 3);
+    assert_eq!(dids[0].status(), DidStatus::Deactivated);
+    assert_eq!(dids[1].status(), DidStatus::Destroyed);
+
+    // Only the active DIDs are offered to sign
+    assert_eq!(
+        names(db.search_dids(&DidQuery::active()).unwrap()),
+        ["Carol"]
+    );
+}
+
+#[test]
+fn migrations_only_add_the_missing_columns() {
+    let db = SQLiteConnector::default();
+    db.execute("CREATE TABLE legacy (id INTEGER PRIMARY KEY)", [])
+        .unwrap();
+    assert!(!db.has_column("legacy", "status").unwrap());
+
+    let definition = "TEXT NOT NULL DEFAULT 'active'";
+    assert!(add_column_if_missing(&db, "legacy", "status", definition).unwrap());
+    assert!(db.has_column("legacy", "status").unwrap());
+    assert!(!add_column_if_missing(&db, "legacy", "status", definition).unwrap());
+}
+
+#[tokio::test]
+async fn vcs_keep_their_token_format() {
+    let (db, ids) = database_with_dids(&["Issuer", "Alice"]).await;
+    // Running the migration on an up to date table changes nothing
+    migrate_vc_jpt(&db).unwrap();
+
+    let degree = "UniversityDegree".to_string();
+    let jwt = db.save_vc("jwt", ids[0], ids[1], &degree, false).unwrap();
+    let sd_jwt = db
+        .save_vc("sd-jwt~", ids[0], ids[1], &degree, true)
+        .unwrap();
+    let jpt = db.save_jpt_vc("jpt", ids[0], ids[1], &degree).unwrap();
+
+    let jpt = db.get_vc_from_id(jpt).unwrap();
+    assert!(jpt.jpt() && !jpt.sd());
+    assert_eq!(jpt.format(), "JPT");
+    assert_eq!(jpt.holder().name(), "Alice");
+    assert_eq!(db.get_vc_from_id(sd_jwt).unwrap().format(), "SD-JWT");
+    assert_eq!(db.get_vc_from_id(jwt).unwrap().format(), "JWT");
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::{iota_document, new_document};
+use identity_iota::verification::{MethodRelationship, MethodScope};
+use tcc::{create_database_tables, migrate_did_fragments, DBConnector, SQLiteConnector};
+
+#[tokio::test]
+async fn saving_a_did_records_every_method() {
+    let db = SQLiteConnector::default();
+    create_database_tables(&db).unwrap();
+
+    let document = iota_document(
+        "tst",
+        &[
+            MethodScope::VerificationMethod,
+            MethodRelationship::Authentication.into(),
+        ],
+    )
+    .await;
+    let did_id = db.save_did_document(&document, "Alice").unwrap();
+
+    let methods = db.get_did_methods(did_id).unwrap();
+    let scopes: Vec<&str> = methods.iter().map(|method| method.scope()).collect();
+    assert_eq!(scopes, ["VerificationMethod", "Authentication"]);
+    assert_eq!(
+        methods[0].fragment(),
+        db.get_did_from_id(did_id).unwrap().fragment()
+    );
+
+    db.save_did_method(did_id, "added", "AssertionMethod", "ES256")
+        .unwrap();
+    let methods = db.get_did_methods(did_id).unwrap();
+    assert_eq!(methods.len(), 3);
+    assert_eq!(methods[2].fragment(), "added");
+    assert_eq!(methods[2].alg(), "ES256");
+}
+
+#[tokio::test]
+async fn existing_fragments_are_migrated_once() {
+    let db = SQLiteConnector::default();
+    create_database_tables(&db).unwrap();
+
+    // A DID saved before the methods were recorded
+    db.execute(
+        "INSERT INTO dids (did, fragment, name) VALUES ('did:iota:tst:0x01', 'key-1', 'Bob')",
+        [],
+    )
+    .unwrap();
+
+    assert_eq!(migrate_did_fragments(&db).unwrap(), 1);
+    assert_eq!(migrate_did_fragments(&db).unwrap(), 0);
+
+    let did_id = db.get_stored_dids().unwrap()[0].id();
+    let methods = db.get_did_methods(did_id).unwrap();
+    assert_eq!(methods.len(), 1);
+    assert_eq!(methods[0].fragment(), "key-1");
+    assert_eq!(methods[0].scope(), "VerificationMethod");
+    // Every key was an Ed25519 one before other algorithms were supported
+    assert_eq!(methods[0].alg(), "EdDSA");
+}
+
+#[tokio::test]
+async fn rotated_methods_are_due_after_their_grace_period() {
+    let db = SQLiteConnector::default();
+    create_database_tables(&db).unwrap();
+
+    let document = new_document().await;
+    let did_id = db.save_did_document(&document, "Issuer").unwrap();
+    let old_method = db.get_did_methods(did_id).unwrap().remove(0);
+
+    db.save_did_method(did_id, "new-key", "VerificationMethod", "EdDSA")
+        .unwrap();
+    db.set_did_fragment(did_id, "new-key").unwrap();
+    assert_eq!(db.get_did_from_id(did_id).unwrap().fragment(), "new-key");
+
+    db.retire_did_method(old_method.id(), 30).unwrap();
+    assert!(db.get_due_did_methods().unwrap().is_empty());
+
+    db.retire_did_method(old_method.id(), 0).unwrap();
+    let due = db.get_due_did_methods().unwrap();
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].fragment(), old_method.fragment());
+
+    db.remove_did_method(old_method.id()).unwrap();
+    assert!(db.get_due_did_methods().unwrap().is_empty());
+    assert!(db.get_did_methods(did_id).unwrap()[0].removed());
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use common::new_document;
+use identity_iota::core::{Object, Url};
+use identity_iota::did::DID;
+use identity_iota::document::{Service, ServiceEndpoint};
+use identity_iota::iota::IotaDocument;
+use tcc::{
+    create_database_tables, document_services, parse_service_endpoint, DBConnector, SQLiteConnector,
+};
+
+async fn document_with_service() -> IotaDocument {
+    let mut document = new_document().await;
+    let service = Service::builder(Object::new())
+        .id(document.id().to_url().join("#linked-domain").unwrap())
+        .type_("LinkedDomains")
+        .service_endpoint(Url::parse("https://example.com/").unwrap())
+        .build()
+        .unwrap();
+    document.insert_service(service).unwrap();
+    document
+}
+
+#[test]
+fn parses_urls_and_json_endpoints() {
+    let endpoint = parse_service_endpoint(" https://example.com/ ").unwrap();
+    assert!(matches!(endpoint, ServiceEndpoint::One(_)));
+
+    let endpoint = parse_service_endpoint(r#"{"origins": ["https://example.com/"]}"#).unwrap();
+    assert!(matches!(endpoint, ServiceEndpoint::Map(_)));
+
+    assert!(parse_service_endpoint("not a url").is_err());
+    assert!(parse_service_endpoint(r#"{"origins": "https://example.com/"}"#).is_err());
+}
+
+#[tokio::test]
+async fn services_are_listed_with_their_did() {
+    let db = SQLiteConnector::default();
+    create_database_tables(&db).unwrap();
+
+    let document = document_with_service().await;
+    assert_eq!(
+        document_services(document.core_document()),
+        [(
+            "linked-domain".to_string(),
+            "LinkedDomains".to_string(),
+            "https://example.com/".to_string()
+        )]
+    );
+    let did_id = db.save_did_document(&document, "Alice").unwrap();
+    assert_eq!(
+        db.get_did_from_id(did_id).unwrap().services(),
+        ["#linked-domain LinkedDomains https://example.com/"]
+    );
+
+    // Saving a service with the same fragment replaces it
+    db.save_did_service(
+        did_id,
+        "linked-domain",
+        "LinkedDomains",
+        "https://example.org/",
+    )
+    .unwrap();
+    db.save_did_service(
+        did_id,
+        "messaging",
+        "DIDCommMessaging",
+        "https://example.com/didcomm",
+    )
+    .unwrap();
+    assert_eq!(
+        db.get_stored_dids().unwrap()[0].services(),
+        [
+            "#linked-domain LinkedDomains https://example.org/",
+            "#messaging DIDCommMessaging https://example.com/didcomm"
+        ]
+    );
+
+    db.remove_did_service(did_id, "linked-domain").unwrap();
+    db.remove_did_service(did_id, "messaging").unwrap();
+    assert!(db.get_did_from_id(did_id).unwrap().services().is_empty());
+}
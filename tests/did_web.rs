@@ -0,0 +1,130 @@
+use identity_iota::core::{FromJson, Object, Url};
+use identity_iota::credential::{
+    Credential, CredentialBuilder, FailFast, JwtCredentialValidationOptions,
+    JwtCredentialValidator, Subject,
+};
+use identity_iota::did::{CoreDID, DID};
+use identity_iota::document::CoreDocument;
+use identity_iota::resolver::Resolver;
+use identity_iota::storage::{
+    JwkDocumentExt, JwkMemStore, JwsSignatureOptions, KeyIdMemstore, Storage,
+};
+use serde_json::json;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use tcc::{
+    create_did_web, did_web, did_web_url, export_did_web, CompositeJwsVerifier, DidWebResolver,
+    KeyAlgorithm,
+};
+use uuid::Uuid;
+
+/// Serves the files of `root` over HTTP on a random port, returning its base URL.
+fn static_file_server(root: PathBuf) -> Url {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut header = String::new();
+            while reader.read_line(&mut header).unwrap() > 2 {
+                header.clear();
+            }
+
+            let path = request_line.split(' ').nth(1).unwrap_or("/");
+            let response = match std::fs::read(root.join(path.trim_start_matches('/'))) {
+                Ok(body) => {
+                    let mut response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        body.len()
+                    )
+                    .into_bytes();
+                    response.extend(body);
+                    response
+                }
+                Err(_) => {
+                    b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                        .to_vec()
+                }
+            };
+            stream.write_all(&response).unwrap();
+        }
+    });
+    Url::parse(format!("http://127.0.0.1:{}/", port)).unwrap()
+}
+
+#[test]
+fn did_web_follows_the_domain_and_path() {
+    let did = did_web("example.com").unwrap();
+    assert_eq!(did.as_str(), "did:web:example.com");
+    assert_eq!(
+        did_web_url(&did, None).unwrap().as_str(),
+        "https://example.com/.well-known/did.json"
+    );
+
+    let did = did_web("localhost:8080/users/alice/").unwrap();
+    assert_eq!(did.as_str(), "did:web:localhost%3A8080:users:alice");
+    assert_eq!(
+        did_web_url(&did, None).unwrap().as_str(),
+        "https://localhost:8080/users/alice/did.json"
+    );
+    let base_url = Url::parse("http://127.0.0.1:9000/hosted/").unwrap();
+    assert_eq!(
+        did_web_url(&did, Some(&base_url)).unwrap().as_str(),
+        "http://127.0.0.1:9000/hosted/localhost:8080/users/alice/did.json"
+    );
+
+    assert!(did_web("").is_err());
+    assert!(did_web("https://example.com").is_err());
+    assert!(did_web("example.com//alice").is_err());
+    assert!(did_web_url(&CoreDID::parse("did:key:z6Mk").unwrap(), None).is_err());
+}
+
+#[tokio::test]
+async fn hosted_did_web_documents_are_resolved_over_http() {
+    let directory = std::env::temp_dir().join(format!("petrus_did_web_{}", Uuid::new_v4()));
+    let storage = Storage::new(JwkMemStore::new(), KeyIdMemstore::new());
+    let (issuer_document, fragment) =
+        create_did_web(&storage, "issuer.example/university", KeyAlgorithm::Ed25519)
+            .await
+            .unwrap();
+    let file = export_did_web(&issuer_document, &directory).unwrap();
+    assert!(file.ends_with("issuer.example/university/did.json"));
+
+    let mut resolver = Resolver::<CoreDocument>::new();
+    DidWebResolver::new(Some(static_file_server(directory))).attach_to(&mut resolver);
+    let resolved: CoreDocument = resolver.resolve(issuer_document.id()).await.unwrap();
+    assert_eq!(resolved, issuer_document);
+
+    let credential: Credential = CredentialBuilder::default()
+        .issuer(Url::parse(issuer_document.id().as_str()).unwrap())
+        .type_("UniversityDegreeCredential")
+        .subject(Subject::from_json_value(json!({"name": "Alice"})).unwrap())
+        .build()
+        .unwrap();
+    let jwt = issuer_document
+        .create_credential_jwt(
+            &credential,
+            &storage,
+            &fragment,
+            &JwsSignatureOptions::default(),
+            None,
+        )
+        .await
+        .unwrap();
+    JwtCredentialValidator::with_signature_verifier(CompositeJwsVerifier)
+        .validate::<_, Object>(
+            &jwt,
+            &resolved,
+            &JwtCredentialValidationOptions::default(),
+            FailFast::FirstError,
+        )
+        .unwrap();
+
+    // Nothing is hosted for this one
+    let missing = did_web("issuer.example/unknown").unwrap();
+    assert!(resolver.resolve(&missing).await.is_err());
+}
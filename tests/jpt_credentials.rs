@@ -0,0 +1,128 @@
+use identity_iota::core::{FromJson, Object, Url};
+use identity_iota::credential::{
+    Credential, CredentialBuilder, FailFast, JptCredentialValidationOptions,
+    JptCredentialValidator, JptPresentationValidationOptions, JptPresentationValidator,
+    JwpCredentialOptions, JwpPresentationOptions, SelectiveDisclosurePresentation, Subject,
+};
+use identity_iota::did::DID;
+use identity_iota::iota::{IotaDocument, NetworkName};
+use identity_iota::storage::{JwkMemStore, JwpDocumentExt, KeyIdMemstore, Storage};
+use identity_iota::verification::MethodScope;
+use jsonprooftoken::jpa::algs::ProofAlgorithm;
+use serde_json::json;
+use tcc::jpt_claim_paths;
+
+fn subject() -> serde_json::Value {
+    json!({
+        "id": "did:iota:tst:0x02",
+        "name": "Alice",
+        "degree": {"type": "BachelorDegree", "name": "Bachelor of Science"},
+        "courses": ["Algebra", "Logic"]
+    })
+}
+
+#[test]
+fn claim_paths_follow_the_conceal_syntax() {
+    let mut paths = jpt_claim_paths(&subject(), "");
+    paths.sort();
+    assert_eq!(
+        paths,
+        [
+            "courses[0]",
+            "courses[1]",
+            "degree.name",
+            "degree.type",
+            "name"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn presentations_reveal_only_the_selected_claims() {
+    let network_name = NetworkName::try_from("tst").unwrap();
+    let mut issuer_document = IotaDocument::new(&network_name);
+    let storage = Storage::new(JwkMemStore::new(), KeyIdMemstore::new());
+    let fragment = issuer_document
+        .generate_method_jwp(
+            &storage,
+            JwkMemStore::BLS12381G2_KEY_TYPE,
+            ProofAlgorithm::BLS12381_SHA256,
+            None,
+            MethodScope::VerificationMethod,
+        )
+        .await
+        .unwrap();
+
+    let credential: Credential = CredentialBuilder::default()
+        .issuer(Url::parse(issuer_document.id().as_str()).unwrap())
+        .type_("UniversityDegreeCredential")
+        .subject(Subject::from_json_value(subject()).unwrap())
+        .build()
+        .unwrap();
+    let jpt = issuer_document
+        .create_credential_jpt(
+            &credential,
+            &storage,
+            &fragment,
+            &JwpCredentialOptions::default(),
+            None,
+        )
+        .await
+        .unwrap();
+    let decoded = JptCredentialValidator::validate::<_, Object>(
+        &jpt,
+        &issuer_document,
+        &JptCredentialValidationOptions::default(),
+        FailFast::FirstError,
+    )
+    .unwrap();
+
+    let mut presentation = SelectiveDisclosurePresentation::new(&decoded.decoded_jwp);
+    for claim in jpt_claim_paths(&subject(), "") {
+        if claim != "degree.name" {
+            presentation.conceal_in_subject(&claim).unwrap();
+        }
+    }
+    let method_id = decoded
+        .decoded_jwp
+        .get_issuer_protected_header()
+        .kid()
+        .unwrap();
+    let presentation_jpt = issuer_document
+        .create_presentation_jpt(
+            &mut presentation,
+            method_id,
+            &JwpPresentationOptions::default().nonce("nonce"),
+        )
+        .await
+        .unwrap();
+
+    let revealed = JptPresentationValidator::validate::<_, Object>(
+        &presentation_jpt,
+        &issuer_document,
+        &JptPresentationValidationOptions::default().nonce("nonce"),
+        FailFast::FirstError,
+    )
+    .unwrap();
+    let claims = &revealed
+        .credential
+        .credential_subject
+        .first()
+        .unwrap()
+        .properties;
+    assert_eq!(claims["degree"]["name"], "Bachelor of Science");
+    // The concealed claims are left as nulls
+    assert!(claims["degree"]["type"].is_null());
+    let revealed_json = serde_json::to_string(claims).unwrap();
+    assert!(!revealed_json.contains("Alice"));
+    assert!(!revealed_json.contains("Algebra"));
+
+    // The proof is bound to the nonce of the verifier
+    assert!(JptPresentationValidator::validate::<_, Object>(
+        &presentation_jpt,
+        &issuer_document,
+        &JptPresentationValidationOptions::default().nonce("other"),
+        FailFast::FirstError,
+    )
+    .is_err());
+}
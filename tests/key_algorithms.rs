@@ -0,0 +1,163 @@
+use identity_iota::iota::{IotaDocument, NetworkName};
+use identity_iota::storage::{JwkDocumentExt, JwkMemStore, JwkStorage, KeyIdMemstore, Storage};
+use identity_iota::verification::jwk::Jwk;
+use identity_iota::verification::jws::{JwsAlgorithm, JwsVerifier, VerificationInput};
+use identity_iota::verification::{MethodScope, VerificationMethod};
+use identity_stronghold::StrongholdStorage;
+use iota_sdk::client::secret::stronghold::StrongholdSecretManager;
+use iota_sdk::client::Password;
+use p256::ecdsa::signature::Signer;
+use rand::rngs::OsRng;
+use tcc::{
+    create_database_tables, CompositeJwsVerifier, DBConnector, KeyAlgorithm, SQLiteConnector,
+    StrongholdKeyStorage,
+};
+
+fn input(alg: JwsAlgorithm, signing_input: &[u8], signature: Vec<u8>) -> VerificationInput {
+    VerificationInput {
+        alg,
+        signing_input: signing_input.into(),
+        decoded_signature: signature.into(),
+    }
+}
+
+#[tokio::test]
+async fn methods_record_the_algorithm_of_their_key() {
+    let db = SQLiteConnector::default();
+    create_database_tables(&db).unwrap();
+
+    let network_name = NetworkName::try_from("tst").unwrap();
+    let mut document = IotaDocument::new(&network_name);
+    let storage = Storage::new(JwkMemStore::new(), KeyIdMemstore::new());
+    document
+        .generate_method(
+            &storage,
+            JwkMemStore::ED25519_KEY_TYPE,
+            JwsAlgorithm::EdDSA,
+            None,
+            MethodScope::VerificationMethod,
+        )
+        .await
+        .unwrap();
+
+    // A P-256 key without `alg`, whose algorithm comes from its curve
+    let public_key = p256::SecretKey::random(&mut OsRng).public_key();
+    let jwk: Jwk = serde_json::from_str(&public_key.to_jwk_string()).unwrap();
+    assert_eq!(KeyAlgorithm::of_jwk(&jwk), Some(KeyAlgorithm::P256));
+    let method =
+        VerificationMethod::new_from_jwk(document.id().clone(), jwk, Some("p256")).unwrap();
+    document
+        .insert_method(method, MethodScope::VerificationMethod)
+        .unwrap();
+
+    let did_id = db.save_did_document(&document, "Alice").unwrap();
+    let algs: Vec<String> = db
+        .get_did_methods(did_id)
+        .unwrap()
+        .iter()
+        .map(|method| method.alg().to_string())
+        .collect();
+    assert_eq!(algs, ["EdDSA", "ES256"]);
+}
+
+#[test]
+fn composite_verifier_dispatches_on_the_alg() {
+    let p256_key = p256::SecretKey::random(&mut OsRng);
+    let p256_jwk: Jwk = serde_json::from_str(&p256_key.public_key().to_jwk_string()).unwrap();
+    let signature: p256::ecdsa::Signature =
+        p256::ecdsa::SigningKey::from(p256_key).sign(b"header.payload");
+    let p256_signature = signature.to_bytes().to_vec();
+
+    let k256_key = k256::SecretKey::random(&mut OsRng);
+    let k256_jwk: Jwk = serde_json::from_str(&k256_key.public_key().to_jwk_string()).unwrap();
+    let signature: k256::ecdsa::Signature =
+        k256::ecdsa::SigningKey::from(k256_key).sign(b"header.payload");
+    let k256_signature = signature.to_bytes().to_vec();
+
+    let verifier = CompositeJwsVerifier;
+    assert!(verifier
+        .verify(
+            input(
+                JwsAlgorithm::ES256,
+                b"header.payload",
+                p256_signature.clone()
+            ),
+            &p256_jwk
+        )
+        .is_ok());
+    assert!(verifier
+        .verify(
+            input(JwsAlgorithm::ES256K, b"header.payload", k256_signature),
+            &k256_jwk
+        )
+        .is_ok());
+
+    // A tampered payload, and an algorithm Petrus does not generate keys for
+    assert!(verifier
+        .verify(
+            input(
+                JwsAlgorithm::ES256,
+                b"header.tampered",
+                p256_signature.clone()
+            ),
+            &p256_jwk
+        )
+        .is_err());
+    assert!(verifier
+        .verify(
+            input(JwsAlgorithm::ES384, b"header.payload", p256_signature),
+            &p256_jwk
+        )
+        .is_err());
+}
+
+#[test]
+fn algorithms_round_trip_through_their_jws_name() {
+    for algorithm in KeyAlgorithm::all() {
+        let name = algorithm.jws_algorithm().name();
+        assert_eq!(KeyAlgorithm::from_jws_name(name).unwrap(), algorithm);
+        assert_eq!(
+            KeyAlgorithm::from_key_type(&algorithm.key_type()),
+            Some(algorithm)
+        );
+    }
+    assert!(KeyAlgorithm::from_jws_name("RS256").is_err());
+}
+
+#[tokio::test]
+async fn ecdsa_keys_only_sign_for_their_public_key() {
+    let mut path = std::env::temp_dir();
+    path.push(format!(
+        "petrus_key_storage_{}.stronghold",
+        uuid::Uuid::new_v4()
+    ));
+    let stronghold = StrongholdSecretManager::builder()
+        .password(Password::from("key_algorithms".to_owned()))
+        .build(path)
+        .unwrap();
+    let storage = StrongholdKeyStorage::new(StrongholdStorage::new(stronghold));
+
+    let key = storage
+        .generate(KeyAlgorithm::P256_KEY_TYPE, JwsAlgorithm::ES256)
+        .await
+        .unwrap();
+    let signature = storage
+        .sign(&key.key_id, b"header.payload", &key.jwk)
+        .await
+        .unwrap();
+    assert!(CompositeJwsVerifier
+        .verify(
+            input(JwsAlgorithm::ES256, b"header.payload", signature),
+            &key.jwk
+        )
+        .is_ok());
+
+    let other = storage
+        .generate(KeyAlgorithm::P256_KEY_TYPE, JwsAlgorithm::ES256)
+        .await
+        .unwrap();
+    assert!(storage
+        .sign(&key.key_id, b"header.payload", &other.jwk)
+        .await
+        .is_err());
+}
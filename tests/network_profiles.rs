@@ -0,0 +1,124 @@
+mod common;
+
+use common::iota_document;
+use identity_iota::storage::{JwkMemStore, KeyIdMemstore, Storage};
+use identity_iota::verification::MethodScope;
+use tcc::{
+    create_database_tables, create_offline_did, migrate_did_network, DBConnector, DidKind,
+    KeyAlgorithm, NetworkProfile, SQLiteConnector, DEFAULT_NETWORK_PROFILE,
+};
+use uuid::Uuid;
+
+#[test]
+fn builtin_profiles_cover_the_public_networks() {
+    let profiles = NetworkProfile::builtin();
+
+    let testnet = NetworkProfile::find(&profiles, "testnet").unwrap();
+    assert_eq!(testnet.hrp, "tst");
+    assert!(testnet.has_faucet());
+    assert_eq!(
+        testnet.label(),
+        "testnet (https://api.testnet.iotaledger.net, faucet)"
+    );
+
+    let shimmer = NetworkProfile::find(&profiles, "shimmer").unwrap();
+    assert_eq!(shimmer.hrp, "smr");
+    assert!(!shimmer.has_faucet());
+    assert_eq!(shimmer.label(), "shimmer (https://api.shimmer.network)");
+
+    let error = NetworkProfile::find(&profiles, "devnet").unwrap_err();
+    assert!(error.to_string().contains("shimmer-testnet"), "{}", error);
+}
+
+#[test]
+fn private_tangle_profiles_are_read_from_a_file() {
+    let path = std::env::temp_dir().join(format!("petrus_networks_{}.json", Uuid::new_v4()));
+    std::fs::write(
+        &path,
+        r#"[{"name": "private", "endpoints": ["http://10.0.0.1:14265", "http://10.0.0.2:14265"],
+             "hrp": "prv"}]"#,
+    )
+    .unwrap();
+
+    let profiles = NetworkProfile::from_file(&path).unwrap();
+    assert_eq!(
+        profiles,
+        [NetworkProfile::new(
+            "private",
+            &["http://10.0.0.1:14265", "http://10.0.0.2:14265"],
+            None,
+            "prv",
+        )]
+    );
+
+    // A profile needs a node to connect to
+    std::fs::write(
+        &path,
+        r#"[{"name": "empty", "endpoints": [], "hrp": "prv"}]"#,
+    )
+    .unwrap();
+    assert!(NetworkProfile::from_file(&path).is_err());
+}
+
+#[tokio::test]
+async fn dids_record_the_network_they_live_on() {
+    let db = SQLiteConnector::default();
+    create_database_tables(&db).unwrap();
+
+    let id = db
+        .save_did_document(
+            &iota_document("smr", &[MethodScope::VerificationMethod]).await,
+            "Alice",
+        )
+        .unwrap();
+    assert_eq!(db.get_did_from_id(id).unwrap().network(), None);
+    db.set_did_network(id, "shimmer").unwrap();
+    assert_eq!(db.get_did_from_id(id).unwrap().network(), Some("shimmer"));
+
+    // Only the IOTA DIDs live on a network
+    let storage = Storage::new(JwkMemStore::new(), KeyIdMemstore::new());
+    let (document, _) = create_offline_did(&storage, DidKind::Key, KeyAlgorithm::Ed25519)
+        .await
+        .unwrap();
+    let id = db.save_did(&document, "Bob").unwrap();
+    assert_eq!(db.get_did_from_id(id).unwrap().network(), None);
+}
+
+#[tokio::test]
+async fn migrated_iota_dids_live_on_the_default_network() {
+    let db = SQLiteConnector::default();
+    // The table before the network profiles
+    db.execute(
+        r#"
+        CREATE TABLE dids (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+            did TEXT,
+            fragment TEXT,
+            name TEXT,
+            status TEXT NOT NULL DEFAULT 'active',
+            method TEXT NOT NULL DEFAULT 'iota'
+        )"#,
+        [],
+    )
+    .unwrap();
+    let document = iota_document("tst", &[MethodScope::VerificationMethod]).await;
+    db.execute(
+        "INSERT INTO dids (did, fragment, name) VALUES (?1, 'key-1', 'Alice')",
+        [document.id().to_string()],
+    )
+    .unwrap();
+    db.execute(
+        "INSERT INTO dids (did, fragment, name, method) VALUES ('did:key:z6Mk', 'z6Mk', 'Bob', 'key')",
+        [],
+    )
+    .unwrap();
+
+    create_database_tables(&db).unwrap();
+    // Running the migration again changes nothing
+    migrate_did_network(&db).unwrap();
+
+    let dids = db.get_stored_dids().unwrap();
+    assert_eq!(dids[0].network(), Some(DEFAULT_NETWORK_PROFILE));
+    assert_eq!(dids[1].network(), None);
+}
@@ -0,0 +1,143 @@
+use identity_iota::core::{FromJson, Object, Url};
+use identity_iota::credential::{
+    Credential, CredentialBuilder, FailFast, JwtCredentialValidationOptions,
+    JwtCredentialValidator, JwtCredentialValidatorUtils, Subject,
+};
+use identity_iota::did::{CoreDID, DID};
+use identity_iota::document::CoreDocument;
+use identity_iota::resolver::Resolver;
+use identity_iota::storage::{
+    JwkDocumentExt, JwkMemStore, JwsSignatureOptions, KeyIdMemstore, Storage,
+};
+use identity_iota::verification::jwk::Jwk;
+use rand::rngs::OsRng;
+use serde_json::json;
+use tcc::{
+    create_database_tables, create_offline_did, did_jwk, did_key, expand_offline_did,
+    resolve_offline_did, CompositeJwsVerifier, DBConnector, DidKind, KeyAlgorithm, SQLiteConnector,
+};
+
+fn p256_jwk() -> Jwk {
+    let public_key = p256::SecretKey::random(&mut OsRng).public_key();
+    serde_json::from_str(&public_key.to_jwk_string()).unwrap()
+}
+
+fn k256_jwk() -> Jwk {
+    let public_key = k256::SecretKey::random(&mut OsRng).public_key();
+    serde_json::from_str(&public_key.to_jwk_string()).unwrap()
+}
+
+fn single_method_jwk(document: &CoreDocument) -> Jwk {
+    let methods = document.methods(None);
+    assert_eq!(methods.len(), 1);
+    methods[0].data().public_key_jwk().unwrap().clone()
+}
+
+#[test]
+fn did_key_holds_the_public_key() {
+    for (jwk, prefix) in [(p256_jwk(), "did:key:zDn"), (k256_jwk(), "did:key:zQ3s")] {
+        let did = did_key(&jwk).unwrap();
+        assert!(did.as_str().starts_with(prefix), "{}", did);
+
+        let document = expand_offline_did(&did).unwrap();
+        let method = &document.methods(None)[0];
+        assert_eq!(method.id().fragment(), Some(did.method_id()));
+        let expanded = single_method_jwk(&document);
+        assert_eq!(
+            expanded.try_ec_params().unwrap().x,
+            jwk.try_ec_params().unwrap().x
+        );
+        assert_eq!(
+            expanded.try_ec_params().unwrap().y,
+            jwk.try_ec_params().unwrap().y
+        );
+        assert_eq!(document.assertion_method().len(), 1);
+        assert!(document.key_agreement().is_empty());
+    }
+
+    assert!(expand_offline_did(&CoreDID::parse("did:key:zUnknown").unwrap()).is_err());
+}
+
+#[test]
+fn did_jwk_holds_the_jwk() {
+    let jwk = p256_jwk();
+    let did = did_jwk(&jwk).unwrap();
+    assert_eq!(did.method(), "jwk");
+
+    let document = expand_offline_did(&did).unwrap();
+    assert_eq!(document.methods(None)[0].id().fragment(), Some("0"));
+    assert_eq!(single_method_jwk(&document), jwk);
+    // A JWK without `use` can also agree on keys
+    assert_eq!(document.authentication().len(), 1);
+    assert_eq!(document.key_agreement().len(), 1);
+}
+
+#[tokio::test]
+async fn offline_dids_sign_credentials_resolved_without_a_node() {
+    let storage = Storage::new(JwkMemStore::new(), KeyIdMemstore::new());
+    let mut resolver = Resolver::<CoreDocument>::new();
+    resolver.attach_handler("key".to_owned(), resolve_offline_did);
+    resolver.attach_handler("jwk".to_owned(), resolve_offline_did);
+
+    for kind in [DidKind::Key, DidKind::Jwk] {
+        let (issuer_document, fragment) = create_offline_did(&storage, kind, KeyAlgorithm::Ed25519)
+            .await
+            .unwrap();
+        assert_eq!(issuer_document.id().method(), kind.method());
+
+        let credential: Credential = CredentialBuilder::default()
+            .issuer(Url::parse(issuer_document.id().as_str()).unwrap())
+            .type_("UniversityDegreeCredential")
+            .subject(Subject::from_json_value(json!({"name": "Alice"})).unwrap())
+            .build()
+            .unwrap();
+        let jwt = issuer_document
+            .create_credential_jwt(
+                &credential,
+                &storage,
+                &fragment,
+                &JwsSignatureOptions::default(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let issuer: CoreDID = JwtCredentialValidatorUtils::extract_issuer_from_jwt(&jwt).unwrap();
+        let resolved: CoreDocument = resolver.resolve(&issuer).await.unwrap();
+        assert_eq!(resolved, issuer_document);
+        JwtCredentialValidator::with_signature_verifier(CompositeJwsVerifier)
+            .validate::<_, Object>(
+                &jwt,
+                &resolved,
+                &JwtCredentialValidationOptions::default(),
+                FailFast::FirstError,
+            )
+            .unwrap();
+    }
+
+    assert!(
+        create_offline_did(&storage, DidKind::Iota, KeyAlgorithm::Ed25519)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn offline_dids_are_stored_with_their_method() {
+    let db = SQLiteConnector::default();
+    create_database_tables(&db).unwrap();
+    let storage = Storage::new(JwkMemStore::new(), KeyIdMemstore::new());
+
+    let (document, fragment) = create_offline_did(&storage, DidKind::Jwk, KeyAlgorithm::Ed25519)
+        .await
+        .unwrap();
+    let id = db.save_did(&document, "Alice").unwrap();
+
+    let did = db.get_did_from_id(id).unwrap();
+    assert_eq!(did.method(), "jwk");
+    assert_eq!(did.fragment(), fragment);
+    assert_eq!(did.did(), document.id().as_str());
+    // Only the DIDs on the ledger can be updated
+    assert!(did.iota_did().is_err());
+    assert_eq!(db.get_did_methods(id).unwrap().len(), 1);
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use common::{credential_builder, offline_document};
+use identity_iota::credential::Credential;
+use identity_iota::document::CoreDocument;
+use tcc::{
+    check_revocation, create_database_tables, migrate_vc_revocation, new_revocation_service,
+    revocation_service, revocation_status, update_revocation_service, DBConnector, SQLiteConnector,
+};
+
+// Self-issued, the subject does not matter to the status checks
+fn credential(issuer_document: &CoreDocument, index: u32) -> Credential {
+    credential_builder(
+        issuer_document,
+        "UniversityDegreeCredential",
+        issuer_document,
+    )
+    .status(revocation_status(issuer_document, index).unwrap())
+    .build()
+    .unwrap()
+}
+
+// Publishes the bitmap with the VC at `index` revoked, or unrevoked
+fn set_revoked(document: &mut CoreDocument, index: u32, revoked: bool) {
+    let service = revocation_service(document).unwrap();
+    let service = update_revocation_service(service, index, revoked).unwrap();
+    document.remove_service(service.id());
+    document.insert_service(service).unwrap();
+}
+
+#[tokio::test]
+async fn revoked_vcs_fail_the_status_check() {
+    let mut issuer_document = offline_document().await;
+    // Without a bitmap there is nothing to point the status at
+    assert!(revocation_status(&issuer_document, 0).is_err());
+
+    let service = new_revocation_service(&issuer_document).unwrap();
+    issuer_document.insert_service(service).unwrap();
+    let service = revocation_service(&issuer_document).unwrap();
+    assert_eq!(service.id().fragment(), Some("revocation"));
+
+    let first = credential(&issuer_document, 0);
+    let second = credential(&issuer_document, 1);
+    check_revocation(&first, &issuer_document).unwrap();
+
+    set_revoked(&mut issuer_document, 0, true);
+    let error = check_revocation(&first, &issuer_document).unwrap_err();
+    assert!(error.to_string().contains("revoked"), "{}", error);
+    // The other VCs of the issuer are untouched
+    check_revocation(&second, &issuer_document).unwrap();
+
+    set_revoked(&mut issuer_document, 0, false);
+    check_revocation(&first, &issuer_document).unwrap();
+}
+
+#[tokio::test]
+async fn status_indexes_are_given_per_issuer() {
+    let db = SQLiteConnector::default();
+    create_database_tables(&db).unwrap();
+
+    let alice = db.save_did(&offline_document().await, "Alice").unwrap();
+    let bob = db.save_did(&offline_document().await, "Bob").unwrap();
+    let degree = "UniversityDegree".to_string();
+
+    assert_eq!(db.next_status_index(alice).unwrap(), 0);
+    let first = db.save_vc("jwt-1", alice, bob, &degree, false).unwrap();
+    db.set_vc_status_index(first, 0, None).unwrap();
+    let second = db.save_vc("jwt-2", alice, bob, &degree, false).unwrap();
+    db.set_vc_status_index(second, 1, None).unwrap();
+    // A VC issued before the bitmap was attached
+    let plain = db.save_vc("jwt-3", alice, bob, &degree, false).unwrap();
+
+    assert_eq!(db.next_status_index(alice).unwrap(), 2);
+    assert_eq!(db.next_status_index(bob).unwrap(), 0);
+
+    db.set_vc_revoked(second, true).unwrap();
+    let vc = db.get_vc_from_id(second).unwrap();
+    assert_eq!(vc.status_index(), Some(1));
+    assert!(vc.revoked());
+    assert_eq!(vc.revocation(), "revoked (index 1)");
+    assert!(!db.get_vc_from_id(first).unwrap().revoked());
+
+    assert!(db.set_vc_revoked(plain, true).is_err());
+    assert_eq!(
+        db.get_vc_from_id(plain).unwrap().revocation(),
+        "not revocable"
+    );
+}
+
+#[tokio::test]
+async fn migrated_vcs_cannot_be_revoked() {
+    let db = SQLiteConnector::default();
+    // The table before the revocation
+    db.execute(
+        r#"
+        CREATE TABLE vcs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            vc TEXT NOT NULL,
+            type TEXT NOT NULL,
+            issuer INTEGER NOT NULL,
+            holder INTEGER NOT NULL,
+            sd BOOLEAN DEFAULT FALSE,
+            jpt BOOLEAN NOT NULL DEFAULT FALSE
+        )"#,
+        [],
+    )
+    .unwrap();
+
+    create_database_tables(&db).unwrap();
+    // Running the migration again changes nothing
+    migrate_vc_revocation(&db).unwrap();
+
+    let alice = db.save_did(&offline_document().await, "Alice").unwrap();
+    let id = db
+        .save_vc("jwt", alice, alice, "UniversityDegree", false)
+        .unwrap();
+    assert_eq!(db.get_vc_from_id(id).unwrap().status_index(), None);
+}
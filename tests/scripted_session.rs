@@ -0,0 +1,323 @@
+mod common;
+
+use async_trait::async_trait;
+use common::new_document;
+use std::sync::Arc;
+use tcc::{
+    App, AppContext, Command, CommandRegistration, CommandRegistry, InputSource, MainMenuCommand,
+    Menu, RecordingInput, ScreenEvent, ScreenState, ScriptedInput,
+};
+use termion::event::Key;
+
+// The list screens read their page size from the configuration, which requires these variables
+fn set_test_environment() {
+    for (key, value) in [
+        ("API_ENDPOINT", "http://localhost:14265"),
+        ("FAUCET_ENDPOINT", "http://localhost:8091/api/enqueue"),
+        ("STRONGHOLD_PASSWORD", "headless"),
+        (
+            "STRONGHOLD_VAULT_PATH",
+            "/tmp/petrus_headless/vault.stronghold",
+        ),
+        ("NETWORK_ADDRESS", "00"),
+        ("CREDENTIALS_TEMPLATE_DIRECTORY", "db/credentials/templates"),
+        ("CREDENTIALS_SD_DIRECTORY", "/tmp/petrus_headless"),
+    ] {
+        if std::env::var(key).is_err() {
+            std::env::set_var(key, value);
+        }
+    }
+}
+
+/// Runs the app against `script` and checks that the session ended exactly when the script did.
+async fn run_session(script: &[&str], setup: impl FnOnce(&AppContext)) {
+    run_session_with_registry(script, CommandRegistry::with_default_commands(), setup).await;
+}
+
+async fn run_session_with_registry(
+    script: &[&str],
+    registry: CommandRegistry,
+    setup: impl FnOnce(&AppContext),
+) {
+    set_test_environment();
+
+    let input = Arc::new(ScriptedInput::new(script.iter().copied()));
+    let context = AppContext::headless_app_context(Box::new(input.clone()))
+        .await
+        .unwrap();
+    setup(&context);
+
+    App::with_registry(context, registry).run().await;
+
+    assert_eq!(input.remaining(), 0, "the session ended before the script");
+}
+
+/// Screen of the Create VC menu that always fails.
+struct FailingCommand;
+
+#[async_trait]
+impl Command for FailingCommand {
+    async fn execute(&mut self) -> ScreenEvent {
+        ScreenEvent::Error("Something went wrong".to_string())
+    }
+
+    fn print_tile(&self) {}
+}
+
+fn registry_with_failing_command() -> CommandRegistry {
+    let mut registry = CommandRegistry::with_default_commands();
+    registry.register(
+        CommandRegistration::new(ScreenState::Custom("failing"), |_, _| {
+            Box::new(FailingCommand)
+        })
+        .menu_entry(
+            ScreenState::CreateVCMenu,
+            "Fail",
+            ScreenEvent::Custom("fail"),
+        ),
+    );
+    registry
+}
+
+#[tokio::test]
+async fn exits_from_main_menu() {
+    run_session(&["0", "key"], |_| {}).await;
+}
+
+#[tokio::test]
+async fn escape_cancels_exit() {
+    run_session(&["0", "esc", "0", "key"], |_| {}).await;
+}
+
+#[tokio::test]
+async fn invalid_menu_keys_are_ignored() {
+    run_session(&["x", "h", "0", "key"], |_| {}).await;
+}
+
+#[tokio::test]
+async fn navigates_menus_with_arrow_keys() {
+    // Create VC -> esc back to the main menu, wrap around to Exit
+    run_session(
+        &["down", "down", "down", "enter", "esc", "up", "enter", "key"],
+        |_| {},
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn navigates_submenus_back_to_main_menu() {
+    // List Items -> Back, Create VC -> Back, Exit
+    run_session(&["1", "4", "4", "5", "0", "key"], |_| {}).await;
+}
+
+#[tokio::test]
+async fn error_screen_retries_the_failed_screen() {
+    // Create VC -> Fail -> Retry -> Back to the Create VC menu -> Back, Exit
+    run_session_with_registry(
+        &["4", "5", "1", "2", "6", "0", "key"],
+        registry_with_failing_command(),
+        |_| {},
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn unknown_transition_opens_the_error_screen() {
+    let mut registry = CommandRegistry::with_default_commands();
+    // The only option of this menu emits an event no screen handles
+    registry.register(
+        CommandRegistration::new(ScreenState::Custom("broken"), |context, _| {
+            Box::new(MainMenuCommand::new(
+                context,
+                vec![("Nowhere".to_string(), ScreenEvent::Custom("nowhere"))],
+            ))
+        })
+        .menu_entry(
+            ScreenState::MainMenu,
+            "Broken",
+            ScreenEvent::Custom("broken"),
+        ),
+    );
+
+    // Broken, the last entry -> Nowhere -> Back to the main menu, Exit
+    run_session_with_registry(&["up", "enter", "1", "2", "0", "key"], registry, |_| {}).await;
+}
+
+#[tokio::test]
+async fn lists_stored_dids() {
+    let document = new_document().await;
+
+    // List Items -> List DIDs -> quit the table, back to List Items -> Back, Exit
+    run_session(&["1", "1", "q", "4", "0", "key"], |context| {
+        context.db.save_did_document(&document, "Alice").unwrap();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn did_details_without_a_node_open_the_error_screen() {
+    let document = new_document().await;
+
+    // List Items -> DID details -> choose Alice, resolving fails -> Back to List Items -> Back,
+    // Exit
+    run_session(&["1", "3", "enter", "2", "4", "0", "key"], |context| {
+        context.db.save_did_document(&document, "Alice").unwrap();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn only_y_confirms_destroying_a_did() {
+    let document = new_document().await;
+
+    // Update a DID -> Destroy a DID -> choose Alice, a stray enter cancels -> Back, Exit
+    run_session(&["3", "6", "enter", "enter", "8", "0", "key"], |context| {
+        context.db.save_did_document(&document, "Alice").unwrap();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn escape_goes_back_from_a_did_picker() {
+    let document = new_document().await;
+
+    // Update a DID -> Destroy a DID -> esc instead of choosing a DID -> Back, Exit
+    run_session(&["3", "6", "esc", "8", "0", "key"], |context| {
+        context.db.save_did_document(&document, "Alice").unwrap();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn empty_menu_selects_nothing() {
+    let input = ScriptedInput::new(Vec::<String>::new());
+    assert_eq!(Menu::new("Empty", Vec::new()).select(&input).await, None);
+}
+
+#[tokio::test]
+async fn no_retired_keys_to_remove() {
+    // Update a DID -> Remove retired keys, nothing is due -> Back, Exit
+    run_session(&["3", "4", "key", "8", "0", "key"], |_| {}).await;
+}
+
+#[tokio::test]
+async fn switch_network_can_be_cancelled() {
+    // Switch network -> esc back to the main menu, Exit
+    run_session(&["9", "esc", "0", "key"], |_| {}).await;
+}
+
+#[tokio::test]
+async fn editing_contacts_requires_one() {
+    // Contacts -> Edit a contact, none is stored -> Back to the main menu, Exit
+    run_session(&["8", "2", "2", "0", "key"], |_| {}).await;
+}
+
+#[tokio::test]
+async fn signing_status_lists_requires_one() {
+    // Create VC -> Manage status lists -> Re-sign, none is stored -> Back to the Create VC menu
+    // -> Back, Exit
+    run_session(&["4", "4", "3", "2", "5", "0", "key"], |_| {}).await;
+}
+
+#[tokio::test]
+async fn recorded_session_can_be_replayed() {
+    set_test_environment();
+    let mut path = std::env::temp_dir();
+    path.push("petrus_recorded_session.txt");
+
+    let recorder = RecordingInput::new(
+        Box::new(ScriptedInput::new(["1", "4", "0", "esc", "0", "key"])),
+        &path,
+    )
+    .unwrap();
+    let context = AppContext::headless_app_context(Box::new(recorder))
+        .await
+        .unwrap();
+    App::new(context).run().await;
+
+    let replay = ScriptedInput::from_file(&path).unwrap();
+    assert_eq!(replay.remaining(), 6);
+    assert_eq!(replay.read_line().await, "1");
+    run_session(
+        &std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .collect::<Vec<_>>(),
+        |_| {},
+    )
+    .await;
+}
+
+/// Presses the given keys, in order.
+struct KeyPresses(std::sync::Mutex<std::vec::IntoIter<Key>>);
+
+#[async_trait]
+impl InputSource for KeyPresses {
+    async fn read_line(&self) -> String {
+        unreachable!("only keys are pressed")
+    }
+
+    async fn read_key(&self) -> Key {
+        self.0.lock().unwrap().next().unwrap()
+    }
+}
+
+#[tokio::test]
+async fn recorded_keys_are_replayed_exactly() {
+    let mut keys = vec![
+        Key::Esc,
+        Key::Char('\n'),
+        Key::Char(' '),
+        Key::Char('\t'),
+        Key::BackTab,
+        Key::Backspace,
+        Key::Delete,
+        Key::Insert,
+        Key::Up,
+        Key::Down,
+        Key::Left,
+        Key::Right,
+        Key::ShiftUp,
+        Key::ShiftDown,
+        Key::ShiftLeft,
+        Key::ShiftRight,
+        Key::AltUp,
+        Key::AltDown,
+        Key::AltLeft,
+        Key::AltRight,
+        Key::CtrlUp,
+        Key::CtrlDown,
+        Key::CtrlLeft,
+        Key::CtrlRight,
+        Key::Home,
+        Key::End,
+        Key::CtrlHome,
+        Key::CtrlEnd,
+        Key::PageUp,
+        Key::PageDown,
+        Key::Null,
+        Key::Ctrl('c'),
+        Key::Alt('x'),
+        Key::F(1),
+        Key::F(12),
+    ];
+    // Every character, including the ones spelling the names of the special keys
+    keys.extend("0123456789/qyjkfpsrx-é".chars().map(Key::Char));
+
+    let mut path = std::env::temp_dir();
+    path.push("petrus_recorded_keys.txt");
+    let recorder = RecordingInput::new(
+        Box::new(KeyPresses(std::sync::Mutex::new(keys.clone().into_iter()))),
+        &path,
+    )
+    .unwrap();
+    for _ in &keys {
+        recorder.read_key().await;
+    }
+
+    let replay = ScriptedInput::from_file(&path).unwrap();
+    for key in keys {
+        assert_eq!(replay.read_key().await, key);
+    }
+    assert_eq!(replay.remaining(), 0);
+}
@@ -0,0 +1,171 @@
+mod common;
+
+use common::{credential_builder, TestDid};
+use identity_iota::core::Url;
+use identity_iota::credential::status_list_2021::StatusList2021Credential;
+use identity_iota::credential::Credential;
+use std::path::Path;
+use tcc::{
+    check_credential_status, check_status_list, create_database_tables, export_status_list,
+    new_status_list_credential, parse_status_list_credential, set_status_list_entry,
+    status_list_path, status_list_status, DBConnector, RevocationCommand, SQLiteConnector,
+    StatusListPurpose,
+};
+use uuid::Uuid;
+
+const LIST_URL: &str = "https://issuer.example/status/1";
+
+// Self-issued, the subject does not matter to the status checks
+fn listed_vc(issuer: &TestDid, purpose: StatusListPurpose, index: u32) -> Credential {
+    credential_builder(
+        &issuer.document,
+        "UniversityDegreeCredential",
+        &issuer.document,
+    )
+    .status(status_list_status(LIST_URL, purpose, index).unwrap())
+    .build()
+    .unwrap()
+}
+
+// Signs the list and writes it where the verifiers read it from
+async fn publish(issuer: &TestDid, list: &StatusList2021Credential, directory: &Path) {
+    let jwt = issuer.sign(&list.clone().into_inner()).await;
+    export_status_list(&jwt, &Url::parse(LIST_URL).unwrap(), directory).unwrap();
+}
+
+#[tokio::test]
+async fn suspended_vcs_can_be_reinstated() {
+    let issuer = TestDid::new().await;
+    let url = Url::parse(LIST_URL).unwrap();
+    let mut list =
+        new_status_list_credential(&issuer.document, url.clone(), StatusListPurpose::Suspension)
+            .unwrap();
+    assert_eq!(list.id.as_ref(), Some(&url));
+
+    let credential = listed_vc(&issuer, StatusListPurpose::Suspension, 5);
+    check_status_list(&credential, &list).unwrap();
+
+    set_status_list_entry(&mut list, 5, true).unwrap();
+    let error = check_status_list(&credential, &list).unwrap_err();
+    assert!(error.to_string().contains("suspended"), "{}", error);
+    // The other entries are untouched
+    check_status_list(&listed_vc(&issuer, StatusListPurpose::Suspension, 4), &list).unwrap();
+
+    set_status_list_entry(&mut list, 5, false).unwrap();
+    check_status_list(&credential, &list).unwrap();
+
+    // The list is stored as JSON between two changes
+    let json = serde_json::to_string(&list).unwrap();
+    assert_eq!(parse_status_list_credential(&json).unwrap(), list);
+}
+
+#[tokio::test]
+async fn revocations_are_final() {
+    let issuer = TestDid::new().await;
+    let mut list = new_status_list_credential(
+        &issuer.document,
+        Url::parse(LIST_URL).unwrap(),
+        StatusListPurpose::Revocation,
+    )
+    .unwrap();
+
+    let credential = listed_vc(&issuer, StatusListPurpose::Revocation, 0);
+    set_status_list_entry(&mut list, 0, true).unwrap();
+    let error = check_status_list(&credential, &list).unwrap_err();
+    assert!(error.to_string().contains("revoked"), "{}", error);
+    assert!(set_status_list_entry(&mut list, 0, false).is_err());
+}
+
+#[tokio::test]
+async fn verifiers_read_the_exported_list() {
+    let issuer = TestDid::new().await;
+    let directory = std::env::temp_dir().join(format!("petrus_status_lists_{}", Uuid::new_v4()));
+    let url = Url::parse(LIST_URL).unwrap();
+    assert_eq!(
+        status_list_path(&directory, &url).unwrap(),
+        directory.join("issuer.example").join("status/1")
+    );
+
+    let credential = listed_vc(&issuer, StatusListPurpose::Suspension, 2);
+    // Nothing was exported yet
+    assert!(check_credential_status(&credential, &issuer.document, &directory).is_err());
+
+    let mut list =
+        new_status_list_credential(&issuer.document, url, StatusListPurpose::Suspension).unwrap();
+    publish(&issuer, &list, &directory).await;
+    check_credential_status(&credential, &issuer.document, &directory).unwrap();
+
+    set_status_list_entry(&mut list, 2, true).unwrap();
+    publish(&issuer, &list, &directory).await;
+    assert!(check_credential_status(&credential, &issuer.document, &directory).is_err());
+
+    // A list signed by someone else is not trusted
+    let other = TestDid::new().await;
+    assert!(check_credential_status(&credential, &other.document, &directory).is_err());
+}
+
+#[tokio::test]
+async fn vcs_record_their_status_list() {
+    let db = SQLiteConnector::default();
+    create_database_tables(&db).unwrap();
+
+    let issuer = TestDid::new().await;
+    let alice = db.save_did(&issuer.document, "Alice").unwrap();
+    let list = db
+        .save_status_list(alice, StatusListPurpose::Suspension, LIST_URL, "{}", "jwt")
+        .unwrap();
+    assert_eq!(db.get_status_lists(Some(alice)).unwrap().len(), 1);
+    assert!(db.get_status_lists(Some(alice + 1)).unwrap().is_empty());
+    assert_eq!(
+        db.get_status_list(list).unwrap().purpose(),
+        StatusListPurpose::Suspension
+    );
+
+    let degree = "UniversityDegree".to_string();
+    let id = db.save_vc("jwt-1", alice, alice, &degree, false).unwrap();
+    assert_eq!(db.next_status_list_index(list).unwrap(), 0);
+    db.set_vc_status_index(id, 0, Some(list)).unwrap();
+    assert_eq!(db.next_status_list_index(list).unwrap(), 1);
+    // The revocation bitmap of the issuer keeps its own indexes
+    assert_eq!(db.next_status_index(alice).unwrap(), 0);
+
+    db.set_vc_revoked(id, true).unwrap();
+    let vc = db.get_vc_from_id(id).unwrap();
+    assert_eq!(vc.status_list(), Some(list));
+    assert_eq!(vc.revocation(), "suspended (status list 1, index 0)");
+
+    db.update_status_list(list, "{\"updated\": true}", "jwt-2")
+        .unwrap();
+    assert_eq!(db.get_status_list(list).unwrap().jwt(), "jwt-2");
+}
+
+#[tokio::test]
+async fn status_list_vcs_are_not_revoked_in_the_bitmap() {
+    let db = SQLiteConnector::default();
+    create_database_tables(&db).unwrap();
+
+    let issuer = TestDid::new().await;
+    let alice = db.save_did(&issuer.document, "Alice").unwrap();
+    let list = db
+        .save_status_list(alice, StatusListPurpose::Revocation, LIST_URL, "{}", "jwt")
+        .unwrap();
+    let degree = "UniversityDegree".to_string();
+    // Both at index 0, one in the bitmap and one in the list
+    let in_bitmap = db.save_vc("jwt-1", alice, alice, &degree, false).unwrap();
+    db.set_vc_status_index(in_bitmap, 0, None).unwrap();
+    let in_list = db.save_vc("jwt-2", alice, alice, &degree, false).unwrap();
+    db.set_vc_status_index(in_list, 0, Some(list)).unwrap();
+
+    let offered = |revoked: bool| -> Vec<i64> {
+        RevocationCommand::revocable_vcs(db.get_stored_vcs().unwrap(), revoked)
+            .iter()
+            .map(|vc| vc.id())
+            .collect()
+    };
+    assert_eq!(offered(true), vec![in_bitmap]);
+
+    db.set_vc_revoked(in_bitmap, true).unwrap();
+    db.set_vc_revoked(in_list, true).unwrap();
+    assert_eq!(offered(false), vec![in_bitmap]);
+    assert!(offered(true).is_empty());
+}
@@ -0,0 +1,152 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use sd_jwt_payload::{KeyBindingJwtClaims, SdJwt, SdObjectEncoder, Sha256Hasher};
+use serde_json::json;
+use tcc::{inspect_token, CliRunner, TokenKind};
+
+// The inspector does not check signatures, so the tokens are signed with a placeholder
+fn unsigned_jwt(header: &serde_json::Value, payload: &str) -> String {
+    format!(
+        "{}.{}.c2lnbmF0dXJl",
+        URL_SAFE_NO_PAD.encode(header.to_string()),
+        URL_SAFE_NO_PAD.encode(payload)
+    )
+}
+
+/// SD-JWT hiding the name and the first nationality of the subject, with two decoy digests.
+fn sd_jwt() -> (String, Vec<String>) {
+    let payload = json!({
+        "iss": "did:iota:tst:0x01",
+        "vc": {
+            "credentialSubject": {
+                "name": "Alice",
+                "nationalities": ["IT", "DE"]
+            }
+        }
+    });
+    let mut encoder = SdObjectEncoder::new(&payload.to_string()).unwrap();
+    let disclosures: Vec<String> = vec![
+        encoder
+            .conceal("/vc/credentialSubject/name", None)
+            .unwrap()
+            .to_string(),
+        encoder
+            .conceal("/vc/credentialSubject/nationalities/0", None)
+            .unwrap()
+            .to_string(),
+    ];
+    encoder.add_decoys("/vc/credentialSubject", 2).unwrap();
+    encoder.add_sd_alg_property();
+
+    let jwt = unsigned_jwt(
+        &json!({"alg": "EdDSA", "typ": "sd-jwt"}),
+        &encoder.try_to_string().unwrap(),
+    );
+    (jwt, disclosures)
+}
+
+#[test]
+fn inspects_a_plain_jwt() {
+    let jwt = unsigned_jwt(&json!({"alg": "EdDSA"}), r#"{"iss":"did:iota:tst:0x01"}"#);
+
+    let inspected = inspect_token(&jwt, None, None).unwrap();
+    assert_eq!(inspected.kind, TokenKind::Jwt);
+    assert_eq!(inspected.header["alg"], "EdDSA");
+    assert_eq!(inspected.payload["iss"], "did:iota:tst:0x01");
+    assert!(inspected.disclosures.is_empty());
+}
+
+#[test]
+fn maps_disclosures_to_their_digests() {
+    let (jwt, disclosures) = sd_jwt();
+    let token = SdJwt::new(jwt, disclosures, None).presentation();
+
+    let inspected = inspect_token(&token, None, None).unwrap();
+    assert_eq!(inspected.kind, TokenKind::SdJwt);
+    assert_eq!(inspected.disclosures.len(), 2);
+
+    let name = &inspected.disclosures[0];
+    assert_eq!(name.key.as_deref(), Some("name"));
+    assert_eq!(name.value, "Alice");
+    assert_eq!(name.locations, ["/vc/credentialSubject/_sd"]);
+
+    let nationality = &inspected.disclosures[1];
+    assert_eq!(nationality.key, None);
+    assert_eq!(nationality.value, "IT");
+    assert_eq!(
+        nationality.locations,
+        ["/vc/credentialSubject/nationalities/0"]
+    );
+
+    // Only the decoys are left without a disclosure
+    assert_eq!(inspected.undisclosed_digests.len(), 2);
+    assert!(inspected.key_binding.is_none());
+}
+
+#[test]
+fn checks_the_key_binding_jwt() {
+    let (jwt, disclosures) = sd_jwt();
+    let claims = serde_json::to_string(&KeyBindingJwtClaims::new(
+        &Sha256Hasher::new(),
+        jwt.clone(),
+        disclosures.clone(),
+        "nonce-1".to_string(),
+        "did:iota:tst:0x02".to_string(),
+        0,
+    ))
+    .unwrap();
+    let kb_jwt = unsigned_jwt(
+        &json!({"alg": "EdDSA", "typ": KeyBindingJwtClaims::KB_JWT_HEADER_TYP}),
+        &claims,
+    );
+    let token = SdJwt::new(jwt, disclosures, Some(kb_jwt)).presentation();
+
+    let inspected = inspect_token(&token, Some("nonce-2"), Some("did:iota:tst:0x02")).unwrap();
+    assert_eq!(inspected.kind, TokenKind::SdJwtWithKeyBinding);
+
+    let key_binding = inspected.key_binding.unwrap();
+    assert!(key_binding.sd_hash_matches);
+    assert_eq!(key_binding.nonce_matches, Some(false));
+    assert_eq!(key_binding.aud_matches, Some(true));
+    assert_eq!(key_binding.payload["nonce"], "nonce-1");
+}
+
+#[test]
+fn detects_a_tampered_presentation() {
+    let (jwt, disclosures) = sd_jwt();
+    let claims = serde_json::to_string(&KeyBindingJwtClaims::new(
+        &Sha256Hasher::new(),
+        jwt.clone(),
+        disclosures.clone(),
+        "nonce".to_string(),
+        "did:iota:tst:0x02".to_string(),
+        0,
+    ))
+    .unwrap();
+    let kb_jwt = unsigned_jwt(&json!({"alg": "EdDSA"}), &claims);
+
+    // The key-binding JWT covers both disclosures, but only one is presented
+    let token = SdJwt::new(jwt, disclosures[..1].to_vec(), Some(kb_jwt)).presentation();
+
+    let key_binding = inspect_token(&token, None, None)
+        .unwrap()
+        .key_binding
+        .unwrap();
+    assert!(!key_binding.sd_hash_matches);
+    assert_eq!(key_binding.nonce_matches, None);
+}
+
+#[test]
+fn only_named_claims_are_selected_for_disclosure() {
+    let (_, disclosures) = sd_jwt();
+    let name = vec!["name".to_string()];
+
+    assert_eq!(
+        CliRunner::select_disclosures(&disclosures[..1], &name).unwrap(),
+        disclosures[..1]
+    );
+    assert!(CliRunner::select_disclosures(&disclosures[..1], &["age".to_string()]).is_err());
+    // The first nationality is an array element, it has no claim name to select it by
+    let err = CliRunner::select_disclosures(&disclosures, &name).unwrap_err();
+    assert!(err.to_string().contains("array element"), "{}", err);
+}
@@ -0,0 +1,185 @@
+mod common;
+
+use chrono::NaiveDateTime;
+use common::{credential_builder, offline_document, TestDid};
+use identity_iota::core::Timestamp;
+use identity_iota::credential::{Credential, Jwt};
+use identity_iota::document::CoreDocument;
+use tcc::{
+    check_validity, create_database_tables, migrate_vc_expiration, parse_date, parse_validity_end,
+    template_validity, DBConnector, SQLiteConnector, Validity, VerifyVCCommand,
+};
+use uuid::Uuid;
+
+fn date(input: &str) -> Timestamp {
+    parse_date(input).unwrap()
+}
+
+// Self-issued, the subject does not matter to the window checks
+fn credential(issuer_document: &CoreDocument, validity: &Validity) -> Credential {
+    validity
+        .apply(credential_builder(
+            issuer_document,
+            "DriversLicense",
+            issuer_document,
+        ))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn durations_end_the_window_after_its_start() {
+    let start = date("2030-01-31");
+    assert_eq!(start, date("2030-01-31T00:00:00Z"));
+
+    assert_eq!(
+        parse_validity_end("12h", start).unwrap(),
+        date("2030-01-31T12:00:00Z")
+    );
+    assert_eq!(
+        parse_validity_end("90d", start).unwrap(),
+        date("2030-05-01")
+    );
+    assert_eq!(parse_validity_end("2w", start).unwrap(), date("2030-02-14"));
+    // Months end on the last day of a shorter month
+    assert_eq!(
+        parse_validity_end("1mo", start).unwrap(),
+        date("2030-02-28")
+    );
+    assert_eq!(parse_validity_end("5y", start).unwrap(), date("2035-01-31"));
+    // A date ends the window wherever it starts
+    assert_eq!(
+        parse_validity_end("2031-06-01", start).unwrap(),
+        date("2031-06-01")
+    );
+
+    for invalid in ["", "5", "y", "5x", "-5d", "2030-02-30"] {
+        assert!(parse_validity_end(invalid, start).is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn validity_windows_are_checked_when_issuing() {
+    let validity = Validity::parse(Some("2030-01-01"), Some("1y")).unwrap();
+    assert_eq!(validity.valid_from, date("2030-01-01"));
+    assert_eq!(validity.expires_at, Some(date("2031-01-01")));
+    assert_eq!(
+        validity.expiration(),
+        Some(NaiveDateTime::parse_from_str("2031-01-01 00:00:00", "%Y-%m-%d %H:%M:%S").unwrap())
+    );
+
+    for never in [None, Some(""), Some("never")] {
+        let validity = Validity::parse(None, never).unwrap();
+        assert_eq!(validity.expires_at, None);
+        assert_eq!(validity.expiration(), None);
+    }
+
+    let error = Validity::parse(Some("2030-01-01"), Some("2029-01-01")).unwrap_err();
+    assert!(
+        error.to_string().contains("before it is valid"),
+        "{}",
+        error
+    );
+    assert!(Validity::parse(Some("tomorrow"), None).is_err());
+}
+
+#[test]
+fn templates_have_default_validities() {
+    let file = std::env::temp_dir().join(format!("petrus_validity_{}.json", Uuid::new_v4()));
+    std::fs::write(&file, r#"{"drivers_license.json": "5y"}"#).unwrap();
+
+    assert_eq!(
+        template_validity(&file, "drivers_license.json").unwrap(),
+        Some("5y".to_string())
+    );
+    assert_eq!(template_validity(&file, "diploma.json").unwrap(), None);
+    assert!(template_validity(&file.with_extension("missing"), "diploma.json").is_err());
+}
+
+#[tokio::test]
+async fn verifiers_tell_why_a_vc_is_not_valid() {
+    let issuer = TestDid::new().await;
+    let issuer_document = &issuer.document;
+    let now = Timestamp::now_utc();
+
+    let expired = credential(
+        issuer_document,
+        &Validity::parse(Some("2020-01-01"), Some("1y")).unwrap(),
+    );
+    let error = check_validity(&expired, now).unwrap_err();
+    assert!(
+        error.to_string().contains("expired on 2021-01-01"),
+        "{}",
+        error
+    );
+
+    let future = credential(
+        issuer_document,
+        &Validity::parse(Some("2999-01-01"), None).unwrap(),
+    );
+    let error = check_validity(&future, now).unwrap_err();
+    assert!(error.to_string().contains("not valid yet"), "{}", error);
+
+    let current = credential(
+        issuer_document,
+        &Validity::parse(None, Some("90d")).unwrap(),
+    );
+    check_validity(&current, now).unwrap();
+    check_validity(&credential(issuer_document, &Validity::unlimited()), now).unwrap();
+
+    // The signature of a VC outside of its window still verifies, its dates are checked apart
+    let jwt = Jwt::new(issuer.sign(&expired).await);
+    let decoded = VerifyVCCommand::verify_normal_vc(&jwt, issuer_document).unwrap();
+    assert_eq!(decoded.credential.expiration_date, Some(date("2021-01-01")));
+    assert!(check_validity(&decoded.credential, now).is_err());
+}
+
+#[tokio::test]
+async fn vcs_record_when_they_expire() {
+    let db = SQLiteConnector::default();
+    // The table before the expiration
+    db.execute(
+        r#"
+        CREATE TABLE vcs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            vc TEXT NOT NULL,
+            type TEXT NOT NULL,
+            issuer INTEGER NOT NULL,
+            holder INTEGER NOT NULL,
+            sd BOOLEAN DEFAULT FALSE,
+            jpt BOOLEAN NOT NULL DEFAULT FALSE,
+            status_index INTEGER,
+            revoked BOOLEAN NOT NULL DEFAULT FALSE
+        )"#,
+        [],
+    )
+    .unwrap();
+    create_database_tables(&db).unwrap();
+    // Running the migration again changes nothing
+    migrate_vc_expiration(&db).unwrap();
+
+    let alice = db.save_did(&offline_document().await, "Alice").unwrap();
+    let license = "DriversLicense".to_string();
+
+    let plain = db.save_vc("jwt-1", alice, alice, &license, false).unwrap();
+    assert_eq!(db.get_vc_from_id(plain).unwrap().expires_at(), None);
+    assert_eq!(db.get_vc_from_id(plain).unwrap().expiry(), "never");
+
+    let validity = Validity::parse(Some("2020-01-01"), Some("1y")).unwrap();
+    let expired = db.save_vc("jwt-2", alice, alice, &license, false).unwrap();
+    db.set_vc_expiration(expired, validity.expiration().unwrap())
+        .unwrap();
+    let vc = db.get_vc_from_id(expired).unwrap();
+    assert_eq!(vc.expires_at(), validity.expiration());
+    assert_eq!(vc.expiry(), "2021-01-01 00:00:00 (expired)");
+
+    let validity = Validity::parse(Some("2020-01-01"), Some("2999-12-31")).unwrap();
+    let valid = db.save_vc("jwt-3", alice, alice, &license, false).unwrap();
+    db.set_vc_expiration(valid, validity.expiration().unwrap())
+        .unwrap();
+    assert_eq!(
+        db.get_vc_from_id(valid).unwrap().expiry(),
+        "2999-12-31 00:00:00"
+    );
+}
@@ -0,0 +1,226 @@
+mod common;
+
+use common::Wallet;
+use identity_iota::did::DID;
+use identity_iota::document::CoreDocument;
+use serde_json::json;
+use std::path::PathBuf;
+use tcc::{
+    inspect_token, CheckOutcome, DBConnector, Validity, Vc, VerificationCheck, VerificationReport,
+    VerifyVCCommand,
+};
+use uuid::Uuid;
+
+fn status_list_directory() -> PathBuf {
+    std::env::temp_dir().join(format!("petrus_report_{}", Uuid::new_v4()))
+}
+
+fn verify(vc: &Vc, issuer_document: anyhow::Result<CoreDocument>) -> VerificationReport {
+    let issuer = vc.issuer().did().to_string();
+    VerifyVCCommand::verification_report(vc, &issuer, issuer_document, &status_list_directory())
+}
+
+fn outcomes(report: &VerificationReport) -> Vec<(VerificationCheck, CheckOutcome)> {
+    report
+        .checks
+        .iter()
+        .map(|result| (result.check, result.outcome))
+        .collect()
+}
+
+#[tokio::test]
+async fn valid_vcs_pass_every_check_that_applies() {
+    let wallet = Wallet::new().await;
+    let vc = wallet
+        .issue(&wallet.holder.document, &Validity::unlimited())
+        .await;
+
+    let report = verify(&vc, Ok(wallet.issuer.document.clone()));
+    assert!(report.valid);
+    assert!(report.failures().is_empty());
+    assert_eq!(
+        outcomes(&report),
+        vec![
+            (VerificationCheck::IssuerResolution, CheckOutcome::Pass),
+            (VerificationCheck::SdDigests, CheckOutcome::Skip),
+            (VerificationCheck::Signature, CheckOutcome::Pass),
+            (VerificationCheck::Expiry, CheckOutcome::Pass),
+            (VerificationCheck::Status, CheckOutcome::Skip),
+            (VerificationCheck::SubjectHolder, CheckOutcome::Pass),
+        ]
+    );
+    assert!(report.credential.is_some());
+}
+
+#[tokio::test]
+async fn every_failure_is_reported() {
+    let wallet = Wallet::new().await;
+    // Expired, and issued to someone else than its holder
+    let validity = Validity::parse(Some("2020-01-01"), Some("1y")).unwrap();
+    let vc = wallet.issue(&wallet.issuer.document, &validity).await;
+
+    let report = verify(&vc, Ok(wallet.issuer.document.clone()));
+    assert!(!report.valid);
+    assert_eq!(
+        report.outcome(VerificationCheck::Signature),
+        Some(CheckOutcome::Pass)
+    );
+    assert_eq!(
+        report.outcome(VerificationCheck::Expiry),
+        Some(CheckOutcome::Fail)
+    );
+    assert_eq!(
+        report.outcome(VerificationCheck::SubjectHolder),
+        Some(CheckOutcome::Fail)
+    );
+
+    let failures = report.failures();
+    assert_eq!(failures.len(), 2);
+    assert!(
+        failures[0].reason.contains("expired on 2021-01-01"),
+        "{}",
+        failures[0].reason
+    );
+}
+
+#[tokio::test]
+async fn checks_needing_a_failed_one_are_skipped() {
+    let wallet = Wallet::new().await;
+    let vc = wallet
+        .issue(&wallet.holder.document, &Validity::unlimited())
+        .await;
+
+    // Verified against the wrong DID document, the signature does not match
+    let report = verify(&vc, Ok(wallet.holder.document.clone()));
+    assert_eq!(
+        outcomes(&report),
+        vec![
+            (VerificationCheck::IssuerResolution, CheckOutcome::Pass),
+            (VerificationCheck::SdDigests, CheckOutcome::Skip),
+            (VerificationCheck::Signature, CheckOutcome::Fail),
+            (VerificationCheck::Expiry, CheckOutcome::Skip),
+            (VerificationCheck::Status, CheckOutcome::Skip),
+            (VerificationCheck::SubjectHolder, CheckOutcome::Skip),
+        ]
+    );
+    assert!(report.credential.is_none());
+
+    let report = verify(&vc, Err(anyhow::anyhow!("DID not found")));
+    assert_eq!(report.failures().len(), 1);
+    assert_eq!(report.failures()[0].reason, "DID not found");
+    assert!(report.checks[1..]
+        .iter()
+        .all(|result| result.outcome == CheckOutcome::Skip));
+}
+
+#[tokio::test]
+async fn reports_are_exported_as_json() {
+    let wallet = Wallet::new().await;
+    let vc = wallet
+        .issue(&wallet.issuer.document, &Validity::unlimited())
+        .await;
+    let report = verify(&vc, Ok(wallet.issuer.document.clone()));
+
+    let file = std::env::temp_dir().join(format!("petrus_report_{}.json", Uuid::new_v4()));
+    report.export(&file).unwrap();
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+
+    assert_eq!(json["vc_id"], vc.id());
+    assert_eq!(json["valid"], false);
+    assert_eq!(json["checks"][0]["check"], "issuer_resolution");
+    assert_eq!(json["checks"][0]["outcome"], "pass");
+    assert_eq!(json["checks"][5]["check"], "subject_holder");
+    assert_eq!(json["checks"][5]["outcome"], "fail");
+}
+
+#[tokio::test]
+async fn tokens_are_verified_against_the_issuer_they_name() {
+    let wallet = Wallet::new().await;
+    let jwt = wallet
+        .sign(&wallet.holder.document, &Validity::unlimited())
+        .await;
+
+    let inspected = inspect_token(&jwt, None, None).unwrap();
+    let issuer = VerifyVCCommand::token_issuer(&inspected).unwrap();
+    assert_eq!(issuer, wallet.issuer.document.id().as_str());
+    assert_eq!(
+        wallet.db.find_did(&issuer).unwrap().unwrap().name(),
+        "Alice"
+    );
+
+    let report = VerifyVCCommand::token_verification_report(
+        &format!("{}\n", jwt),
+        Ok(wallet.issuer.document.clone()),
+        &status_list_directory(),
+    )
+    .unwrap();
+    assert!(report.valid);
+    assert_eq!(report.vc_id, None);
+    assert_eq!(report.issuer, issuer);
+    assert_eq!(report.format, "JWT");
+    assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+    // Nobody stored it for a holder
+    assert_eq!(
+        report.outcome(VerificationCheck::SubjectHolder),
+        Some(CheckOutcome::Skip)
+    );
+
+    // Resolved to another DID, the key named by the token is not the issuer's
+    let report = VerifyVCCommand::token_verification_report(
+        &jwt,
+        Ok(wallet.holder.document.clone()),
+        &status_list_directory(),
+    )
+    .unwrap();
+    assert!(!report.valid);
+    assert_eq!(report.warnings.len(), 1);
+    assert!(
+        report.warnings[0].contains("not to the issuer"),
+        "{}",
+        report.warnings[0]
+    );
+
+    assert!(VerifyVCCommand::token_verification_report(
+        "not a token",
+        Ok(wallet.issuer.document.clone()),
+        &status_list_directory()
+    )
+    .is_err());
+}
+
+#[tokio::test]
+async fn kids_must_be_methods_of_the_issuer() {
+    let wallet = Wallet::new().await;
+    let issuer = &wallet.issuer.document;
+    let method = issuer.methods(None)[0].id().clone();
+    let fragment = format!("#{}", method.fragment().unwrap());
+
+    let kid = |kid: &str| json!({"alg": "EdDSA", "kid": kid});
+    assert_eq!(
+        VerifyVCCommand::check_kid(&kid(&method.to_string()), issuer),
+        None
+    );
+    // Relative to the issuer DID
+    assert_eq!(VerifyVCCommand::check_kid(&kid(&fragment), issuer), None);
+
+    let warning = VerifyVCCommand::check_kid(&kid("#missing"), issuer).unwrap();
+    assert!(warning.contains("not a method"), "{}", warning);
+    let other = format!("{}{}", wallet.holder.document.id(), fragment);
+    let warning = VerifyVCCommand::check_kid(&kid(&other), issuer).unwrap();
+    assert!(warning.contains("not to the issuer"), "{}", warning);
+    assert!(VerifyVCCommand::check_kid(&json!({"alg": "EdDSA"}), issuer).is_some());
+}
+
+#[tokio::test]
+async fn stored_vcs_name_their_issuer() {
+    let wallet = Wallet::new().await;
+    let vc = wallet
+        .issue(&wallet.holder.document, &Validity::unlimited())
+        .await;
+
+    assert_eq!(
+        VerifyVCCommand::stored_vc_issuer(&vc).unwrap(),
+        wallet.issuer.document.id().as_str()
+    );
+}